@@ -9,7 +9,9 @@ pub struct CallDef {
     entry_point: String,
     args: RuntimeArgs,
     amount: U512,
-    is_mut: bool
+    is_mut: bool,
+    #[serde(default)]
+    is_view: bool
 }
 
 impl CallDef {
@@ -35,7 +37,8 @@ impl CallDef {
             entry_point: method.to_string(),
             args,
             amount: U512::zero(),
-            is_mut
+            is_mut,
+            is_view: false
         }
     }
 
@@ -63,6 +66,21 @@ impl CallDef {
         self
     }
 
+    /// Marks the call as a call of a view entrypoint, the only kind of call allowed from a view.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use odra_core::CallDef;
+    /// # use casper_types::RuntimeArgs;
+    /// let call_def = CallDef::new("my_method", false, RuntimeArgs::new()).into_view();
+    /// assert!(call_def.is_view());
+    /// ```
+    pub fn into_view(mut self) -> Self {
+        self.is_view = true;
+        self
+    }
+
     /// Returns a reference to the entry point name of the `CallDef` instance.
     pub fn entry_point(&self) -> &str {
         &self.entry_point
@@ -104,6 +122,11 @@ impl CallDef {
     pub fn is_mut(&self) -> bool {
         self.is_mut
     }
+
+    /// Returns whether the called entrypoint is a view.
+    pub fn is_view(&self) -> bool {
+        self.is_view
+    }
 }
 
 #[cfg(test)]
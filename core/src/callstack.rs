@@ -109,6 +109,11 @@ impl Callstack {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+
+    /// Returns an iterator over the callstack elements, starting from the bottom most one.
+    pub fn iter(&self) -> Iter<'_, CallstackElement> {
+        self.0.iter()
+    }
 }
 
 #[cfg(test)]
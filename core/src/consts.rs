@@ -59,3 +59,15 @@ pub const CONSTRUCTOR_GROUP_NAME: &str = "constructor_group";
 
 /// Constructor name
 pub const CONSTRUCTOR_NAME: &str = "init";
+
+/// The name under which the receive entry point of a contract is exported.
+///
+/// A transfer of native tokens to a contract calls the entry point under this name.
+pub const RECEIVE_ENTRY_POINT: &str = "__odra_receive";
+
+/// The name under which the fallback entry point of a contract is exported.
+///
+/// The host calls the entry point under this name instead of an entry point the contract does not define.
+/// Casper resolves the entry point before the contract is executed, so contracts calling an undefined
+/// entry point of another contract revert.
+pub const FALLBACK_ENTRY_POINT: &str = "__odra_fallback";
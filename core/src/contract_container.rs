@@ -1,4 +1,4 @@
-use crate::entry_point_callback::EntryPointsCaller;
+use crate::entry_point_callback::{self, EntryPointsCaller};
 use crate::prelude::*;
 use crate::{CallDef, VmError};
 use casper_types::bytesrepr::Bytes;
@@ -27,11 +27,14 @@ impl ContractContainer {
     }
 
     /// Calls the entry point with the given call definition.
+    ///
+    /// If the contract does not define the called entry point, but defines a fallback
+    /// entry point (`#[odra(fallback)]`), the fallback entry point is called instead.
     pub fn call(&self, call_def: CallDef) -> OdraResult<Bytes> {
+        let entry_points = self.entry_points_caller.entry_points();
+        let call_def = entry_point_callback::resolve_fallback(entry_points, call_def);
         // find the entry point
-        let ep = self
-            .entry_points_caller
-            .entry_points()
+        let ep = entry_points
             .iter()
             .find(|ep| ep.name == call_def.entry_point())
             .ok_or_else(|| {
@@ -45,6 +48,12 @@ impl ContractContainer {
         }
         self.entry_points_caller.call(call_def)
     }
+
+    /// Returns the call of the receive entry point (`#[odra(receive)]`) transferring `amount`
+    /// to the contract, if the contract defines one.
+    pub fn receive_call(&self, amount: U512) -> Option<CallDef> {
+        entry_point_callback::receive_call(self.entry_points_caller.entry_points(), amount)
+    }
 }
 
 #[derive(PartialEq, Eq, Clone, Copy)]
@@ -57,7 +66,7 @@ enum ExecutionContext {
 mod tests {
     use super::{ContractContainer, ExecutionContext};
     use crate::contract_context::MockContractContext;
-    use crate::entry_point_callback::{Argument, EntryPoint, EntryPointsCaller};
+    use crate::entry_point_callback::{Argument, EntryPoint, EntryPointKind, EntryPointsCaller};
     use crate::host::{HostEnv, MockHostContext};
     use crate::{casper_types::RuntimeArgs, VmError};
     use crate::{prelude::*, CallDef, ContractEnv};
@@ -90,6 +99,19 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_call_unknown_entrypoint_calls_fallback() {
+        // Given an instance with a fallback entrypoint.
+        let instance = ContractContainer::with_fallback();
+
+        // When call an unknown entrypoint.
+        let call_def = CallDef::new("unknown", false, RuntimeArgs::new());
+        let result = instance.call(call_def);
+
+        // Then the fallback entrypoint is called.
+        assert_eq!(result, Ok(vec![1, 2, 3].into()));
+    }
+
     impl ContractContainer {
         fn empty() -> Self {
             let ctx = Rc::new(RefCell::new(MockHostContext::new()));
@@ -106,12 +128,20 @@ mod tests {
         }
 
         fn with_entrypoint(args: Vec<&str>) -> Self {
-            let entry_points = vec![EntryPoint::new(
+            Self::with_entrypoints(vec![EntryPoint::new(
                 String::from(TEST_ENTRYPOINT),
                 args.iter()
                     .map(|name| Argument::new::<u32>(String::from(*name)))
                     .collect()
-            )];
+            )])
+        }
+
+        fn with_fallback() -> Self {
+            Self::with_entrypoints(vec![EntryPoint::new(String::from(TEST_ENTRYPOINT), vec![])
+                .with_kind(EntryPointKind::Fallback)])
+        }
+
+        fn with_entrypoints(entry_points: Vec<EntryPoint>) -> Self {
             let mut ctx = MockHostContext::new();
             ctx.expect_contract_env().returning(|| {
                 ContractEnv::new(0, Rc::new(RefCell::new(MockContractContext::new())))
@@ -2,7 +2,8 @@ use casper_types::CLValue;
 
 use crate::call_def::CallDef;
 use crate::casper_types::bytesrepr::Bytes;
-use crate::casper_types::U512;
+use crate::casper_types::{RuntimeArgs, U512};
+use crate::factory::ContractTemplate;
 use crate::prelude::*;
 
 /// Trait representing the context of a smart contract.
@@ -84,6 +85,18 @@ pub trait ContractContext {
     /// The result of the contract call as a byte array.
     fn call_contract(&self, address: Address, call_def: CallDef) -> Bytes;
 
+    /// Installs a new contract from the given template.
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - The template of the contract to install.
+    /// * `init_args` - The arguments of the constructor, if `None` the constructor is not called.
+    ///
+    /// # Returns
+    ///
+    /// The address of the installed contract.
+    fn new_contract(&self, template: &ContractTemplate, init_args: Option<RuntimeArgs>) -> Address;
+
     /// Retrieves the current block time.
     ///
     /// # Returns
@@ -160,4 +173,11 @@ pub trait ContractContext {
     ///
     /// The computed hash as a fixed-size byte array of length 32.
     fn hash(&self, bytes: &[u8]) -> [u8; 32];
+
+    /// Prints a debug message.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message to print.
+    fn log(&self, message: &str);
 }
@@ -85,6 +85,11 @@ pub trait HasIdent {
 pub trait HasEntrypoints {
     /// Returns the list of contract's entrypoints.
     fn entrypoints() -> Vec<Entrypoint>;
+
+    /// Returns the names of the interfaces implemented by the contract.
+    fn interfaces() -> Vec<String> {
+        Vec::new()
+    }
 }
 
 /// A trait that should be implemented by each smart contract to allow the backend.
@@ -109,7 +114,10 @@ pub struct ContractBlueprint {
     /// The events defined in the contract.
     pub events: Vec<Event>,
     /// The entrypoints defined in the contract.
-    pub entrypoints: Vec<Entrypoint>
+    pub entrypoints: Vec<Entrypoint>,
+    /// The names of the interfaces implemented by the contract.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub interfaces: Vec<String>
 }
 
 impl ContractBlueprint {
@@ -121,13 +129,14 @@ impl ContractBlueprint {
     ///
     /// # Returns
     ///
-    /// A new instance of `ContractBlueprint` with the name, events, and entrypoints
+    /// A new instance of `ContractBlueprint` with the name, events, entrypoints and interfaces
     /// obtained from the type `T`.
     pub fn new<T: HasIdent + HasEvents + HasEntrypoints>() -> Self {
         Self {
             name: T::ident(),
             events: T::events(),
-            entrypoints: T::entrypoints()
+            entrypoints: T::entrypoints(),
+            interfaces: T::interfaces()
         }
     }
 
@@ -4,7 +4,8 @@ use crate::args::EntrypointArgument;
 use crate::call_def::CallDef;
 use crate::casper_types::bytesrepr::{deserialize_from_slice, Bytes, FromBytes, ToBytes};
 use crate::casper_types::crypto::PublicKey;
-use crate::casper_types::{CLTyped, CLValue, BLAKE2B_DIGEST_LENGTH, U512};
+use crate::casper_types::{CLTyped, CLValue, RuntimeArgs, BLAKE2B_DIGEST_LENGTH, U512};
+use crate::factory::ContractTemplate;
 use crate::module::Revertible;
 pub use crate::ContractContext;
 use crate::{consts, prelude::*, utils};
@@ -31,7 +32,9 @@ pub trait ContractRef {
 pub struct ContractEnv {
     index: u32,
     mapping_data: Vec<u8>,
-    backend: Rc<RefCell<dyn ContractContext>>
+    backend: Rc<RefCell<dyn ContractContext>>,
+    read_only: bool,
+    named_args: Option<Rc<RuntimeArgs>>
 }
 
 impl Revertible for ContractEnv {
@@ -46,7 +49,40 @@ impl ContractEnv {
         Self {
             index,
             mapping_data: Vec::new(),
-            backend
+            backend,
+            read_only: false,
+            named_args: None
+        }
+    }
+
+    /// Returns the environment which reads the entrypoint arguments from the given `args`
+    /// instead of the arguments passed by the caller.
+    ///
+    /// Used to execute the calls of a batch, see [multicall](crate::multicall).
+    pub fn with_named_args(mut self, args: RuntimeArgs) -> Self {
+        self.named_args = Some(Rc::new(args));
+        self
+    }
+
+    /// Returns the environment of a view entrypoint.
+    ///
+    /// Any attempt to modify the state in a read-only environment, i.e. writing to the storage,
+    /// emitting an event, transferring tokens or calling an entrypoint other than a view,
+    /// reverts with [ExecutionError::WriteInView].
+    pub fn into_read_only(mut self) -> Self {
+        self.read_only = true;
+        self
+    }
+
+    /// Returns true if the environment does not allow modifying the state.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Reverts if the environment is read-only.
+    fn assert_writable(&self) {
+        if self.read_only {
+            self.revert(ExecutionError::WriteInView);
         }
     }
 
@@ -71,7 +107,9 @@ impl ContractEnv {
         Self {
             index: (self.index << 4) + index as u32,
             mapping_data: self.mapping_data.clone(),
-            backend: self.backend.clone()
+            backend: self.backend.clone(),
+            read_only: self.read_only,
+            named_args: self.named_args.clone()
         }
     }
 
@@ -89,6 +127,7 @@ impl ContractEnv {
 
     /// Sets the value associated with the given key in the contract storage.
     pub fn set_value<T: ToBytes + CLTyped>(&self, key: &[u8], value: T) {
+        self.assert_writable();
         let result = value.to_bytes().map_err(ExecutionError::from);
         let bytes = result.unwrap_or_revert(self);
         self.backend.borrow().set_value(key, bytes.into());
@@ -103,6 +142,7 @@ impl ContractEnv {
 
     /// Sets the value associated with the given named key in the contract storage.
     pub fn set_named_value<T: CLTyped + ToBytes, U: AsRef<str>>(&self, name: U, value: T) {
+        self.assert_writable();
         let key = name.as_ref();
         // todo: map errors to correct Odra errors
         let cl_value = CLValue::from_t(value).unwrap_or_revert(self);
@@ -134,6 +174,7 @@ impl ContractEnv {
         key: &[u8],
         value: T
     ) {
+        self.assert_writable();
         let dictionary_name = dictionary_name.as_ref();
         let cl_value = CLValue::from_t(value)
             .map_err(|_| ExecutionError::Formatting)
@@ -145,6 +186,7 @@ impl ContractEnv {
 
     /// Removes the dictionary from the contract storage.
     pub fn remove_dictionary<U: AsRef<str>>(&self, dictionary_name: U) {
+        self.assert_writable();
         let dictionary_name = dictionary_name.as_ref();
         self.backend.borrow().remove_dictionary(dictionary_name);
     }
@@ -160,12 +202,31 @@ impl ContractEnv {
     /// # Returns
     ///
     /// The result of the contract call. If any error occurs during the call, the contract will revert.
+    /// A read-only environment reverts if the called entrypoint is not a view,
+    /// see [CallDef::into_view].
     pub fn call_contract<T: FromBytes>(&self, address: Address, call: CallDef) -> T {
+        if !call.is_view() {
+            self.assert_writable();
+        }
         let backend = self.backend.borrow();
         let bytes = backend.call_contract(address, call);
         deserialize_from_slice(bytes).unwrap_or_revert(self)
     }
 
+    /// Installs a new contract from the given template and returns its address.
+    ///
+    /// If `init_args` are given, the constructor of the new contract is called, the caller of the constructor
+    /// is the current contract. A read-only environment reverts.
+    pub fn new_contract(
+        &self,
+        template: &ContractTemplate,
+        init_args: Option<RuntimeArgs>
+    ) -> Address {
+        self.assert_writable();
+        let backend = self.backend.borrow();
+        backend.new_contract(template, init_args)
+    }
+
     /// Returns the address of the current contract.
     pub fn self_address(&self) -> Address {
         let backend = self.backend.borrow();
@@ -174,6 +235,7 @@ impl ContractEnv {
 
     /// Transfers tokens to the specified address.
     pub fn transfer_tokens(&self, to: &Address, amount: &U512) {
+        self.assert_writable();
         let backend = self.backend.borrow();
         backend.transfer_tokens(to, amount)
     }
@@ -204,6 +266,7 @@ impl ContractEnv {
 
     /// Emits an event with the specified data.
     pub fn emit_event<T: ToBytes + EventInstance>(&self, event: T) {
+        self.assert_writable();
         let backend = self.backend.borrow();
         let result = event.to_bytes().map_err(ExecutionError::from);
         let bytes = result.unwrap_or_revert(self);
@@ -240,6 +303,15 @@ impl ContractEnv {
     pub fn hash<T: AsRef<[u8]>>(&self, value: T) -> [u8; BLAKE2B_DIGEST_LENGTH] {
         self.backend.borrow().hash(value.as_ref())
     }
+
+    /// Prints a debug message.
+    ///
+    /// The message is printed by the test backends. In wasm, it is printed by the host `print` function
+    /// if the contract is built with the `debug-log` feature, otherwise the call has no effect.
+    /// Prefer the `odra::log!` macro, which compiles to nothing in wasm without the feature.
+    pub fn log<T: AsRef<str>>(&self, message: T) {
+        self.backend.borrow().log(message.as_ref())
+    }
 }
 
 /// Represents the environment accessible in the contract execution context.
@@ -263,6 +335,8 @@ impl ExecutionEnv {
     }
 
     /// Performs non-reentrant checks before executing a function.
+    ///
+    /// A view entrypoint only checks the reentrancy guard, it does not set it.
     pub fn non_reentrant_before(&self) {
         // Check if reentrancy guard is set to true
         let status: bool = self
@@ -273,6 +347,9 @@ impl ExecutionEnv {
             // Revert execution with ReentrantCall error
             self.env.revert(ExecutionError::ReentrantCall);
         }
+        if self.env.is_read_only() {
+            return;
+        }
         // Set reentrancy guard to true
         self.env
             .set_value(consts::REENTRANCY_GUARD.as_slice(), true);
@@ -280,6 +357,9 @@ impl ExecutionEnv {
 
     /// Resets the reentrancy guard after executing a function.
     pub fn non_reentrant_after(&self) {
+        if self.env.is_read_only() {
+            return;
+        }
         // Set reentrancy guard to false
         self.env
             .set_value(consts::REENTRANCY_GUARD.as_slice(), false);
@@ -302,6 +382,14 @@ impl ExecutionEnv {
     /// The deserialized value of the named argument. If the argument does not exist or deserialization fails,
     /// the contract will revert.
     pub fn get_named_arg<T: FromBytes + EntrypointArgument>(&self, name: &str) -> T {
+        if let Some(args) = &self.env.named_args {
+            let bytes = args.get(name).map(|value| value.inner_bytes().as_slice());
+            if T::is_required() && bytes.is_none() {
+                self.env.revert(ExecutionError::MissingArg);
+            }
+            let result = bytes.map(|bytes| deserialize_from_slice(bytes).unwrap_or_revert(self));
+            return T::unwrap(result, &self.env);
+        }
         if T::is_required() {
             let result = self.env.backend.borrow().get_named_arg_bytes(name);
             match result {
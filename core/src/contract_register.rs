@@ -18,6 +18,11 @@ impl ContractRegister {
         self.contracts.insert(addr, container);
     }
 
+    /// Removes the contract registered under the given address.
+    pub fn remove(&mut self, addr: &Address) {
+        self.contracts.remove(addr);
+    }
+
     /// Calls the entry point with the given call definition.
     ///
     /// Returns bytes representing the result of the call or an error if the address
@@ -29,6 +34,15 @@ impl ContractRegister {
         Err(OdraError::VmError(VmError::InvalidContractAddress))
     }
 
+    /// Returns a copy of the contract registered under the given address
+    /// or an error if the address is not present in the register.
+    pub fn get(&self, addr: &Address) -> OdraResult<ContractContainer> {
+        self.contracts
+            .get(addr)
+            .cloned()
+            .ok_or(OdraError::VmError(VmError::InvalidContractAddress))
+    }
+
     /// Post install hook.
     pub fn post_install(&mut self, addr: &Address) {
         if let Some(contract) = self.contracts.get_mut(addr) {
@@ -17,24 +17,47 @@ use casper_types::{PublicKey, SecretKey};
 pub fn generate_key_pairs(amount: u8) -> BTreeMap<Address, (SecretKey, PublicKey)> {
     let mut accounts = BTreeMap::new();
     for i in 0..amount {
-        // Create keypair.
-        let secret_key = SecretKey::ed25519_from_bytes([i; 32]).unwrap_or_else(|_| {
-            panic!(
-                "Couldn't construct a secret key from {}. This shouldn't happen!",
-                i
-            )
-        });
-        let public_key = PublicKey::from(&secret_key);
-
-        // Create an AccountHash from a public key.
-        let account_addr = AccountHash::from(&public_key);
-
-        let address = account_addr.try_into().unwrap_or_else(|_| {
-            panic!("Couldn't convert AccountHash to Address. This shouldn't happen!")
-        });
-
-        // Create a GenesisAccount.
-        accounts.insert(address, (secret_key, public_key));
+        let (address, key_pair) = key_pair_from_bytes([i; 32]);
+        accounts.insert(address, key_pair);
     }
     accounts
 }
+
+/// Generates a key pair deterministically derived from the given label.
+///
+/// The same label always yields the same account.
+pub fn generate_labelled_key_pair(label: &str) -> (Address, (SecretKey, PublicKey)) {
+    key_pair_from_seed(format!("label:{}", label))
+}
+
+/// Generates a key pair of the n-th account created after the genesis.
+///
+/// The key pairs are generated deterministically from the index and never collide
+/// with the key pairs generated by [generate_labelled_key_pair].
+pub fn generate_indexed_key_pair(index: usize) -> (Address, (SecretKey, PublicKey)) {
+    key_pair_from_seed(format!("account:{}", index))
+}
+
+fn key_pair_from_seed(seed: String) -> (Address, (SecretKey, PublicKey)) {
+    key_pair_from_bytes(casper_types::crypto::blake2b(seed))
+}
+
+fn key_pair_from_bytes(bytes: [u8; 32]) -> (Address, (SecretKey, PublicKey)) {
+    // Create keypair.
+    let secret_key = SecretKey::ed25519_from_bytes(bytes).unwrap_or_else(|_| {
+        panic!(
+            "Couldn't construct a secret key from {:?}. This shouldn't happen!",
+            bytes
+        )
+    });
+    let public_key = PublicKey::from(&secret_key);
+
+    // Create an AccountHash from a public key.
+    let account_addr = AccountHash::from(&public_key);
+
+    let address = account_addr.try_into().unwrap_or_else(|_| {
+        panic!("Couldn't convert AccountHash to Address. This shouldn't happen!")
+    });
+
+    (address, (secret_key, public_key))
+}
@@ -5,6 +5,7 @@ use casper_types::CLType;
 use crate::args::EntrypointArgument;
 use crate::call_def::CallDef;
 use crate::casper_types::bytesrepr::Bytes;
+use crate::casper_types::{RuntimeArgs, U512};
 use crate::{host::HostEnv, prelude::*, ContractEnv};
 
 /// A struct representing an entry point caller.
@@ -67,7 +68,21 @@ pub struct EntryPoint {
     /// The collection of arguments to the entry point.
     pub args: Vec<Argument>,
     /// A flag indicating whether the entry point is payable.
-    pub is_payable: bool
+    pub is_payable: bool,
+    /// The way the entry point is called by the host.
+    pub kind: EntryPointKind
+}
+
+/// Defines how an entry point is called by the host.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EntryPointKind {
+    /// An entry point called by its name.
+    #[default]
+    Regular,
+    /// An entry point called when native tokens are transferred to the contract (`#[odra(receive)]`).
+    Receive,
+    /// An entry point called instead of an entry point the contract does not define (`#[odra(fallback)]`).
+    Fallback
 }
 
 impl EntryPoint {
@@ -76,7 +91,8 @@ impl EntryPoint {
         Self {
             name,
             args,
-            is_payable: false
+            is_payable: false,
+            kind: EntryPointKind::Regular
         }
     }
 
@@ -85,9 +101,45 @@ impl EntryPoint {
         Self {
             name,
             args,
-            is_payable: true
+            is_payable: true,
+            kind: EntryPointKind::Regular
         }
     }
+
+    /// Returns the entry point with the given kind.
+    pub fn with_kind(self, kind: EntryPointKind) -> Self {
+        Self { kind, ..self }
+    }
+}
+
+/// Returns the call of the receive entry point transferring `amount` to a contract
+/// with the given entry points, if the contract defines one.
+pub fn receive_call(entry_points: &[EntryPoint], amount: U512) -> Option<CallDef> {
+    let receive = entry_points
+        .iter()
+        .find(|ep| ep.kind == EntryPointKind::Receive)?;
+    let mut args = RuntimeArgs::new();
+    let _ = args.insert("amount", amount);
+    Some(CallDef::new(receive.name.clone(), true, args).with_amount(amount))
+}
+
+/// Redirects a call of an entry point missing in the given entry points to the fallback entry point,
+/// if the contract defines one. Otherwise, returns the call unchanged.
+pub fn resolve_fallback(entry_points: &[EntryPoint], call_def: CallDef) -> CallDef {
+    if entry_points
+        .iter()
+        .any(|ep| ep.name == call_def.entry_point())
+    {
+        return call_def;
+    }
+    match entry_points
+        .iter()
+        .find(|ep| ep.kind == EntryPointKind::Fallback)
+    {
+        Some(fallback) => CallDef::new(fallback.name.clone(), true, call_def.args().clone())
+            .with_amount(call_def.amount()),
+        None => call_def
+    }
 }
 
 /// A struct representing an argument to entry point.
@@ -108,3 +160,48 @@ impl Argument {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry_points() -> Vec<EntryPoint> {
+        vec![
+            EntryPoint::new(String::from("get"), vec![]),
+            EntryPoint::new_payable(String::from("donate"), vec![])
+                .with_kind(EntryPointKind::Receive),
+            EntryPoint::new(String::from("fallback"), vec![]).with_kind(EntryPointKind::Fallback),
+        ]
+    }
+
+    #[test]
+    fn test_receive_call() {
+        let call_def = receive_call(&entry_points(), U512::from(100)).unwrap();
+        assert_eq!(call_def.entry_point(), "donate");
+        assert_eq!(call_def.amount(), U512::from(100));
+
+        let no_receive = vec![EntryPoint::new(String::from("get"), vec![])];
+        assert!(receive_call(&no_receive, U512::from(100)).is_none());
+    }
+
+    #[test]
+    fn test_resolve_fallback() {
+        let known = CallDef::new("get", false, RuntimeArgs::new());
+        assert_eq!(
+            resolve_fallback(&entry_points(), known).entry_point(),
+            "get"
+        );
+
+        let unknown = CallDef::new("unknown", true, RuntimeArgs::new());
+        assert_eq!(
+            resolve_fallback(&entry_points(), unknown.clone()).entry_point(),
+            "fallback"
+        );
+
+        let no_fallback = vec![EntryPoint::new(String::from("get"), vec![])];
+        assert_eq!(
+            resolve_fallback(&no_fallback, unknown).entry_point(),
+            "unknown"
+        );
+    }
+}
@@ -137,6 +137,10 @@ pub enum ExecutionError {
     MissingAddress = 123,
     /// Out of gas error
     OutOfGas = 124,
+    /// Modifying the state in a view entrypoint.
+    WriteInView = 125,
+    /// Calling an entrypoint the contract does not define.
+    UnknownEntrypoint = 126,
     /// Maximum code for user errors
     MaxUserError = 64535,
     /// User error too high. The code should be in range 0..32767.
@@ -187,6 +191,8 @@ pub enum VmError {
         /// Found type.
         found: CLType
     },
+    /// The operation is not supported by the backend.
+    UnsupportedOperation(String),
     /// Non-specified error with a custom message.
     Other(String),
     /// Unspecified error.
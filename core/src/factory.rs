@@ -0,0 +1,76 @@
+//! Templates of contracts installed by other contracts.
+//!
+//! A contract generated for a module marked with `#[odra::module(factory)]` installs new instances
+//! of the module. The wasm of such a factory bundles the entry points of the installed contract,
+//! as a contract installed by another contract shares its code.
+use crate::prelude::*;
+#[cfg(target_arch = "wasm32")]
+use crate::{casper_event_standard::Schemas, casper_types::EntryPoints};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{entry_point_callback::EntryPointsCaller, host::HostEnv};
+
+/// Describes a contract which can be installed by another contract.
+#[derive(Clone)]
+pub struct ContractTemplate {
+    name: String,
+    #[cfg(not(target_arch = "wasm32"))]
+    entry_points_caller: fn(&HostEnv) -> EntryPointsCaller,
+    #[cfg(target_arch = "wasm32")]
+    entry_points: EntryPoints,
+    #[cfg(target_arch = "wasm32")]
+    events: Schemas
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ContractTemplate {
+    /// Creates a new template of a contract which entry points are called by
+    /// the [EntryPointsCaller] returned by the given function.
+    pub fn new(name: &str, entry_points_caller: fn(&HostEnv) -> EntryPointsCaller) -> Self {
+        Self {
+            name: name.to_string(),
+            entry_points_caller
+        }
+    }
+
+    /// Returns the [EntryPointsCaller] of a new instance of the contract.
+    pub fn entry_points_caller(&self, env: &HostEnv) -> EntryPointsCaller {
+        (self.entry_points_caller)(env)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl ContractTemplate {
+    /// Creates a new template of a contract with the given entry points and event schemas.
+    pub fn new(name: &str, entry_points: EntryPoints, events: Schemas) -> Self {
+        Self {
+            name: name.to_string(),
+            entry_points,
+            events
+        }
+    }
+
+    /// Returns the entry points of the contract.
+    pub fn entry_points(&self) -> EntryPoints {
+        self.entry_points.clone()
+    }
+
+    /// Returns the schemas of the events emitted by the contract.
+    pub fn events(&self) -> Schemas {
+        self.events.clone()
+    }
+}
+
+impl ContractTemplate {
+    /// Returns the name of the contract.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// A module which can be installed by another contract.
+///
+/// Implemented for modules marked with `#[odra::module(factory)]`.
+pub trait HasTemplate {
+    /// Returns the template of the contract.
+    fn template() -> ContractTemplate;
+}
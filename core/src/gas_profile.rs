@@ -0,0 +1,193 @@
+//! Module with structs for profiling the gas usage.
+use crate::prelude::*;
+use core::fmt::{Display, Formatter, Result};
+use serde::{Deserialize, Serialize};
+
+/// A category the gas is attributed to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum GasCategory {
+    /// Installation of a contract.
+    Deploy,
+    /// Calls to contracts.
+    Calls,
+    /// Reads and writes of variables and dictionaries.
+    Storage,
+    /// Emission of events.
+    Events,
+    /// Hashing and other cryptographic operations.
+    Crypto,
+    /// Transfers of native tokens.
+    Transfers,
+    /// Execution not attributed to any other category.
+    Execution
+}
+
+impl GasCategory {
+    /// Returns the name of the category used as a frame in a folded stack.
+    pub fn name(&self) -> &'static str {
+        match self {
+            GasCategory::Deploy => "deploy",
+            GasCategory::Calls => "calls",
+            GasCategory::Storage => "storage",
+            GasCategory::Events => "events",
+            GasCategory::Crypto => "crypto",
+            GasCategory::Transfers => "transfers",
+            GasCategory::Execution => "execution"
+        }
+    }
+}
+
+impl Display for GasCategory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// The gas used by a single call stack in a single category.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasSample {
+    /// The call stack, from the entry point called by an account to the innermost call.
+    ///
+    /// Every frame has the form of `Contract::entry_point`.
+    pub stack: Vec<String>,
+    /// The category the gas is attributed to.
+    pub category: GasCategory,
+    /// The gas used.
+    pub gas: u64
+}
+
+/// The gas used by contracts, attributed to the nested calls and the gas categories.
+///
+/// The profile can be rendered as folded stacks, which is the input format of
+/// flamegraph tools (e.g. `inferno-flamegraph`), or as JSON.
+///
+/// The profile depends on the backend. Only the OdraVm attributes the gas to nested calls
+/// and categories. The CasperVm records one frame per deploy, the called entry point,
+/// with all the gas in the [GasCategory::Execution] category (or [GasCategory::Deploy]
+/// for a contract installation). On a livenet the profile is empty.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasProfile(Vec<GasSample>);
+
+impl GasProfile {
+    /// Returns a new, empty gas profile.
+    pub fn new() -> Self {
+        GasProfile::default()
+    }
+
+    /// Attributes the gas to the given call stack and category.
+    pub fn record(&mut self, stack: Vec<String>, category: GasCategory, gas: u64) {
+        if gas == 0 {
+            return;
+        }
+        match self
+            .0
+            .iter_mut()
+            .find(|sample| sample.category == category && sample.stack == stack)
+        {
+            Some(sample) => sample.gas = sample.gas.saturating_add(gas),
+            None => self.0.push(GasSample {
+                stack,
+                category,
+                gas
+            })
+        }
+    }
+
+    /// Returns an iterator over the samples of the profile.
+    pub fn iter(&self) -> Iter<'_, GasSample> {
+        self.0.iter()
+    }
+
+    /// Returns the total gas used.
+    pub fn total(&self) -> u64 {
+        self.0
+            .iter()
+            .fold(0, |total, sample| total.saturating_add(sample.gas))
+    }
+
+    /// Returns the gas used by every entry point called by an account, including the nested calls.
+    pub fn entry_points(&self) -> BTreeMap<String, u64> {
+        let mut result = BTreeMap::new();
+        for sample in &self.0 {
+            if let Some(entry_point) = sample.stack.first() {
+                let gas = result.entry(entry_point.clone()).or_insert(0u64);
+                *gas = gas.saturating_add(sample.gas);
+            }
+        }
+        result
+    }
+
+    /// Returns the gas used in every category.
+    pub fn categories(&self) -> BTreeMap<GasCategory, u64> {
+        let mut result = BTreeMap::new();
+        for sample in &self.0 {
+            let gas = result.entry(sample.category).or_insert(0u64);
+            *gas = gas.saturating_add(sample.gas);
+        }
+        result
+    }
+
+    /// Renders the profile as folded stacks, one `frame;frame;category gas` line per sample.
+    pub fn to_folded(&self) -> String {
+        let mut result = String::new();
+        for sample in &self.0 {
+            for frame in &sample.stack {
+                result.push_str(frame);
+                result.push(';');
+            }
+            result.push_str(&format!("{} {}\n", sample.category.name(), sample.gas));
+        }
+        result
+    }
+
+    /// Renders the profile as JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Gas profile should serialize to JSON")
+    }
+}
+
+impl IntoIterator for GasProfile {
+    type Item = GasSample;
+    type IntoIter = IntoIter<GasSample>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gas_profile() {
+        let mut profile = GasProfile::new();
+        let mint = vec![String::from("Token::mint")];
+        let nested = vec![String::from("Token::mint"), String::from("Hook::notify")];
+        profile.record(mint.clone(), GasCategory::Calls, 100);
+        profile.record(mint.clone(), GasCategory::Storage, 20);
+        profile.record(nested.clone(), GasCategory::Storage, 5);
+        profile.record(mint.clone(), GasCategory::Storage, 10);
+        profile.record(vec![String::from("Token::burn")], GasCategory::Events, 7);
+
+        assert_eq!(profile.total(), 142);
+        assert_eq!(
+            profile.entry_points(),
+            BTreeMap::from([
+                (String::from("Token::burn"), 7),
+                (String::from("Token::mint"), 135)
+            ])
+        );
+        assert_eq!(profile.categories().get(&GasCategory::Storage), Some(&35));
+        assert_eq!(
+            profile.to_folded(),
+            "Token::mint;calls 100\n\
+             Token::mint;storage 30\n\
+             Token::mint;Hook::notify;storage 5\n\
+             Token::burn;events 7\n"
+        );
+
+        let json = profile.to_json();
+        assert_eq!(serde_json::from_str::<GasProfile>(&json).unwrap(), profile);
+    }
+}
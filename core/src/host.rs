@@ -1,10 +1,11 @@
 //! A module that provides the interface for interacting with the host environment.
 
 use crate::address::Addressable;
+use crate::entry_point_callback::EntryPointsCaller;
+use crate::gas_profile::GasProfile;
 use crate::gas_report::GasReport;
 use crate::{
-    call_result::CallResult, entry_point_callback::EntryPointsCaller, CallDef, ContractCallResult,
-    ContractEnv, EventError, VmError
+    call_result::CallResult, CallDef, ContractCallResult, ContractEnv, EventError, VmError
 };
 #[cfg(not(target_arch = "wasm32"))]
 use crate::{consts, contract::OdraContract, contract_def::HasIdent};
@@ -227,6 +228,54 @@ impl<T: OdraContract> HostRefLoader<T::HostRef> for T {
     }
 }
 
+/// The default CSPR balance of an account created by a test environment.
+pub const DEFAULT_ACCOUNT_BALANCE: u64 = 100_000_000_000_000_000;
+
+/// CSPR balances of the accounts created at the genesis of a test environment.
+///
+/// The balance of every account is the default balance unless overridden for
+/// a given account index. Accounts created after the genesis get the default balance.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GenesisBalances {
+    default_balance: U512,
+    balances: BTreeMap<usize, U512>
+}
+
+impl GenesisBalances {
+    /// Creates a new configuration with the given default balance.
+    pub fn new(default_balance: U512) -> Self {
+        Self {
+            default_balance,
+            balances: BTreeMap::new()
+        }
+    }
+
+    /// Overrides the balance of the account at the given index.
+    pub fn with_balance(mut self, index: usize, balance: U512) -> Self {
+        self.balances.insert(index, balance);
+        self
+    }
+
+    /// Returns the balance of the account at the given index.
+    pub fn balance(&self, index: usize) -> U512 {
+        self.balances
+            .get(&index)
+            .copied()
+            .unwrap_or(self.default_balance)
+    }
+
+    /// Returns the default balance.
+    pub fn default_balance(&self) -> U512 {
+        self.default_balance
+    }
+}
+
+impl Default for GenesisBalances {
+    fn default() -> Self {
+        Self::new(U512::from(DEFAULT_ACCOUNT_BALANCE))
+    }
+}
+
 /// The `HostContext` trait defines the interface for interacting with the host environment.
 #[cfg_attr(test, mockall::automock)]
 pub trait HostContext {
@@ -242,9 +291,18 @@ pub trait HostContext {
     /// Returns the account address at the specified index.
     fn get_account(&self, index: usize) -> Address;
 
+    /// Creates a new account with the default balance and returns its address.
+    fn new_account(&self) -> Address;
+
+    /// Returns the address of the account with the specified label, creating it on first use.
+    fn account(&self, label: &str) -> Address;
+
     /// Returns the CSPR balance of the specified address.
     fn balance_of(&self, address: &Address) -> U512;
 
+    /// Sets the CSPR balance of the specified address.
+    fn set_balance(&self, address: &Address, amount: U512);
+
     /// Advances the block time by the specified time difference.
     fn advance_block_time(&self, time_diff: u64);
 
@@ -273,6 +331,16 @@ pub trait HostContext {
         entry_points_caller: EntryPointsCaller
     ) -> OdraResult<Address>;
 
+    /// Installs a wasm file not built with Odra, e.g. a third-party contract, with the given runtime arguments.
+    ///
+    /// The contract is expected to store its package hash under `package_hash_key` in the caller's named keys.
+    fn install_wasm(
+        &self,
+        wasm_path: &str,
+        args: RuntimeArgs,
+        package_hash_key: &str
+    ) -> OdraResult<Address>;
+
     /// Registers an existing contract with the specified address, name, and entry points caller.
     fn register_contract(
         &self,
@@ -287,6 +355,11 @@ pub trait HostContext {
     /// Returns the gas report for the current contract execution.
     fn gas_report(&self) -> GasReport;
 
+    /// Returns the gas profile, attributing the gas to the nested calls and the gas categories.
+    ///
+    /// A backend unable to attribute the gas returns one frame per deploy or an empty profile.
+    fn gas_profile(&self) -> GasProfile;
+
     /// Returns the gas cost of the last contract call.
     fn last_call_gas_cost(&self) -> u64;
 
@@ -329,6 +402,23 @@ impl HostEnv {
         backend.get_account(index)
     }
 
+    /// Creates a new account with the default balance and returns its address.
+    ///
+    /// The new account is also available under the next free index of [get_account](Self::get_account).
+    pub fn new_account(&self) -> Address {
+        let backend = self.backend.borrow();
+        backend.new_account()
+    }
+
+    /// Returns the address of the account with the specified label, e.g. `env.account("alice")`.
+    ///
+    /// The account is created with the default balance on first use,
+    /// subsequent calls with the same label return the same address.
+    pub fn account(&self, label: &str) -> Address {
+        let backend = self.backend.borrow();
+        backend.account(label)
+    }
+
     /// Sets the caller address for the current contract execution.
     pub fn set_caller(&self, address: Address) {
         if address.is_contract() {
@@ -365,6 +455,27 @@ impl HostEnv {
         Ok(deployed_contract)
     }
 
+    /// Installs a wasm file not built with Odra, e.g. a third-party contract, with the given runtime arguments.
+    ///
+    /// The contract is expected to store its package hash (or a contract hash) under `package_hash_key`
+    /// in the caller's named keys. The returned address can be used to call the contract
+    /// using a reference generated by `#[odra::external_contract]`.
+    ///
+    /// Supported only by the Casper backend, other backends return [VmError::UnsupportedOperation].
+    pub fn install_wasm(
+        &self,
+        wasm_path: &str,
+        args: RuntimeArgs,
+        package_hash_key: &str
+    ) -> OdraResult<Address> {
+        let backend = self.backend.borrow();
+        let deployed_contract = backend.install_wasm(wasm_path, args, package_hash_key)?;
+
+        self.deployed_contracts.borrow_mut().push(deployed_contract);
+        self.events_count.borrow_mut().insert(deployed_contract, 0);
+        Ok(deployed_contract)
+    }
+
     /// Registers an existing contract with the specified address, name and entry points caller.
     /// Similar to `new_contract`, but skips the deployment phase.
     pub fn register_contract(
@@ -395,6 +506,10 @@ impl HostEnv {
 
     /// Calls a contract at the specified address with the given call definition. Returns raw,
     /// not serialized bytes.
+    ///
+    /// If the contract does not define the called entry point, but defines a fallback
+    /// entry point (`#[odra(fallback)]`), the fallback entry point is called instead.
+    /// The livenet backend does not support fallback entry points.
     pub fn raw_call_contract(
         &self,
         address: Address,
@@ -455,12 +570,31 @@ impl HostEnv {
         self.backend.borrow().gas_report().clone()
     }
 
+    /// Returns the gas profile of all the calls and deploys executed so far.
+    ///
+    /// Use [GasProfile::to_folded] to generate a flamegraph or [GasProfile::to_json]
+    /// to process the profile with other tools.
+    ///
+    /// Only the OdraVm attributes the gas to nested calls and categories. The CasperVm
+    /// attributes the gas of every deploy as a whole to the called entry point
+    /// in the [GasCategory::Execution](crate::GasCategory::Execution) category,
+    /// and the profile on a livenet is always empty.
+    pub fn gas_profile(&self) -> GasProfile {
+        self.backend.borrow().gas_profile()
+    }
+
     /// Returns the CSPR balance of the specified address.
     pub fn balance_of<T: Addressable>(&self, address: &T) -> U512 {
         let backend = self.backend.borrow();
         backend.balance_of(address.address())
     }
 
+    /// Sets the CSPR balance of the specified address.
+    pub fn set_balance<T: Addressable>(&self, address: &T, amount: U512) {
+        let backend = self.backend.borrow();
+        backend.set_balance(address.address(), amount)
+    }
+
     /// Retrieves an event with the specified index from the specified contract.
     ///
     /// # Returns
@@ -621,12 +755,11 @@ impl HostEnv {
     }
 
     /// Transfers the specified amount of CSPR from the current caller to the specified address.
+    ///
+    /// A transfer to a contract calls its receive entry point (`#[odra(receive)]`).
+    /// If the contract does not define one, the transfer fails with [ExecutionError::TransferToContract].
+    /// The livenet backend rejects all transfers to contracts.
     pub fn transfer(&self, to: Address, amount: U512) -> OdraResult<()> {
-        if to.is_contract() {
-            return Err(OdraError::ExecutionError(
-                ExecutionError::TransferToContract
-            ));
-        }
         let backend = self.backend.borrow();
         backend.transfer(to, amount)
     }
@@ -808,9 +941,10 @@ mod test {
 
     #[test]
     fn test_transfer_to_contract() {
-        // Given a host context that successfully transfers tokens.
+        // Given a host context that rejects transfers to a contract without a receive entry point.
         let mut ctx = MockHostContext::new();
-        ctx.expect_transfer().returning(|_, _| Ok(()));
+        ctx.expect_transfer()
+            .returning(|_, _| Err(ExecutionError::TransferToContract.into()));
         let env = HostEnv::new(Rc::new(RefCell::new(ctx)));
 
         let addr = Address::Contract(ContractPackageHash::new([0; 32]));
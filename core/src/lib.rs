@@ -22,12 +22,15 @@ pub mod crypto;
 pub mod entry_point_callback;
 mod error;
 mod external;
+pub mod factory;
+mod gas_profile;
 mod gas_report;
 // TODO: consider making this not wasm32
 pub mod host;
 mod list;
 mod mapping;
 pub mod module;
+pub mod multicall;
 mod named_keys;
 pub mod prelude;
 mod sequence;
@@ -48,4 +51,5 @@ pub use error::{AddressError, CollectionError, EventError, VmError};
 pub use address::Addressable;
 pub use casper_types;
 pub use contract::OdraContract;
+pub use gas_profile::*;
 pub use gas_report::*;
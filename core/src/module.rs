@@ -45,6 +45,29 @@ impl<T: Module> Revertible for T {
     }
 }
 
+/// A module which entrypoints can be restricted to its owner with `#[odra(only_owner)]`.
+pub trait OwnerGuard {
+    /// Reverts if `caller` is not the owner of the module.
+    fn assert_owner(&self, caller: &Address);
+}
+
+/// A module checking the roles of the entrypoints restricted with `#[odra(only_role(field, "ROLE"))]`,
+/// where `field` stores the module.
+pub trait RoleGuard {
+    /// Reverts if `caller` has not been granted the role named `role`.
+    fn assert_role(&self, role: &str, caller: &Address);
+}
+
+/// The `N`-th type used in the signatures of the entry points of a module.
+///
+/// Implemented for modules that can be delegated to, so the signatures of the delegated
+/// entry points refer to the types through the module and the delegating module
+/// does not need to import them.
+pub trait DelegatedType<const N: usize> {
+    /// The type.
+    type Type;
+}
+
 /// A marker trait for a module component that does not emit events.
 ///
 /// This trait allows to implement `HasEvents` for components like Var, List, Mapping,
@@ -0,0 +1,54 @@
+//! Helpers used by the `multicall` entrypoint generated for modules marked with
+//! `#[odra::module(multicall)]`.
+//!
+//! A batch is a list of calls, every call is a pair of the entrypoint name and the serialized
+//! [RuntimeArgs] of the call. The calls are executed one by one within a single transaction,
+//! if any of them reverts, the whole batch reverts.
+use crate::casper_types::bytesrepr::{Bytes, FromBytes, ToBytes};
+use crate::casper_types::RuntimeArgs;
+use crate::prelude::*;
+use crate::ContractEnv;
+
+/// A call of a batch - the entrypoint name and the serialized runtime args.
+pub type Call = (String, Bytes);
+
+/// Encodes a call of the given entrypoint with the given arguments.
+pub fn encode_call(entry_point: &str, args: RuntimeArgs) -> Call {
+    let args = args
+        .to_bytes()
+        .expect("Runtime args should serialize to bytes");
+    (String::from(entry_point), Bytes::from(args))
+}
+
+/// Returns the environment of a call of a batch, which reads the entrypoint arguments
+/// from the serialized `args` instead of the arguments of the `multicall` entrypoint.
+pub fn call_env(env: &ContractEnv, args: &Bytes) -> ContractEnv {
+    let (args, _) = RuntimeArgs::from_bytes(args)
+        .map_err(ExecutionError::from)
+        .unwrap_or_revert(env);
+    env.clone().with_named_args(args)
+}
+
+/// Serializes the result of a call of a batch.
+pub fn call_result<T: ToBytes>(env: &ContractEnv, result: T) -> Bytes {
+    let bytes = result
+        .to_bytes()
+        .map_err(ExecutionError::from)
+        .unwrap_or_revert(env);
+    Bytes::from(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::casper_types::runtime_args;
+
+    #[test]
+    fn test_encode_call() {
+        let args = runtime_args! { "amount" => 100u32 };
+        let (entry_point, bytes) = encode_call("transfer", args.clone());
+
+        assert_eq!(entry_point, "transfer");
+        assert_eq!(RuntimeArgs::from_bytes(&bytes).unwrap().0, args);
+    }
+}
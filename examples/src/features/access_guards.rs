@@ -0,0 +1,107 @@
+//! Entrypoints restricted with the access control attributes.
+use odra::prelude::*;
+use odra_modules::access::{role_id, AccessControl, Ownable};
+
+/// Name of the minter role.
+pub const MINTER: &str = "MINTER";
+
+/// Errors of the [GuardedToken] contract.
+#[odra::odra_error]
+pub enum Error {
+    /// Minting is paused.
+    Paused = 1
+}
+
+/// A token which entrypoints are restricted with the access control attributes.
+#[odra::module(errors = Error)]
+pub struct GuardedToken {
+    ownable: SubModule<Ownable>,
+    access_control: SubModule<AccessControl>,
+    paused: Var<bool>,
+    total_supply: Var<u64>
+}
+
+#[odra::module]
+impl GuardedToken {
+    /// Initializes the contract, the caller becomes the owner.
+    pub fn init(&mut self) {
+        self.ownable.init();
+    }
+
+    /// Grants the minter role to the given address.
+    #[odra(only_owner = ownable)]
+    pub fn add_minter(&mut self, minter: &Address) {
+        self.access_control
+            .unchecked_grant_role(&role_id(MINTER), minter);
+    }
+
+    /// Pauses minting.
+    #[odra(only_owner = ownable)]
+    pub fn pause(&mut self) {
+        self.paused.set(true);
+    }
+
+    /// Mints new tokens.
+    #[odra(only_role(access_control, "MINTER"), guard = Self::not_paused)]
+    pub fn mint(&mut self, amount: u64) {
+        self.total_supply.add(amount);
+    }
+
+    /// Returns the total supply.
+    pub fn total_supply(&self) -> u64 {
+        self.total_supply.get_or_default()
+    }
+}
+
+impl GuardedToken {
+    fn not_paused(&self) {
+        if self.paused.get_or_default() {
+            self.env().revert(Error::Paused);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, GuardedToken};
+    use odra::host::{Deployer, NoArgs};
+    use odra_modules::access::errors::Error as AccessError;
+
+    #[test]
+    fn only_owner_can_add_minters() {
+        let env = odra_test::env();
+        let mut token = GuardedToken::deploy(&env, NoArgs);
+        let (owner, minter) = (env.get_account(0), env.get_account(1));
+
+        env.set_caller(minter);
+        assert_eq!(
+            token.try_add_minter(&minter),
+            Err(AccessError::CallerNotTheOwner.into())
+        );
+
+        env.set_caller(owner);
+        token.add_minter(&minter);
+        env.set_caller(minter);
+        token.mint(100);
+        assert_eq!(token.total_supply(), 100);
+    }
+
+    #[test]
+    fn only_minters_can_mint() {
+        let env = odra_test::env();
+        let mut token = GuardedToken::deploy(&env, NoArgs);
+
+        assert_eq!(token.try_mint(100), Err(AccessError::MissingRole.into()));
+    }
+
+    #[test]
+    fn guard_is_checked() {
+        let env = odra_test::env();
+        let mut token = GuardedToken::deploy(&env, NoArgs);
+        let owner = env.get_account(0);
+        token.add_minter(&owner);
+
+        token.pause();
+        assert_eq!(token.try_mint(100), Err(Error::Paused.into()));
+    }
+}
@@ -0,0 +1,83 @@
+//! This example demonstrates how to expose all the entry points of a submodule.
+use odra::casper_types::U256;
+use odra::prelude::*;
+use odra_modules::access::Ownable;
+use odra_modules::erc20::Erc20;
+
+/// A token exposing the entry points of its submodules, only the owner can mint new tokens.
+#[odra::module]
+pub struct DelegatingToken {
+    ownable: SubModule<Ownable>,
+    erc20: SubModule<Erc20>
+}
+
+#[odra::module]
+impl DelegatingToken {
+    /// Initializes the token, the caller becomes the owner.
+    pub fn init(&mut self, name: String, symbol: String, initial_supply: U256) {
+        self.ownable.init();
+        self.erc20.init(symbol, name, 18, Some(initial_supply));
+    }
+
+    delegate! {
+        to self.erc20: Erc20 except [mint, burn];
+        to self.ownable: Ownable except [assert_owner, get_optional_owner, unchecked_transfer_ownership];
+    }
+
+    /// Mints new tokens and assigns them to the given address.
+    pub fn mint(&mut self, address: &Address, amount: &U256) {
+        self.ownable.assert_owner(&self.env().caller());
+        self.erc20.mint(address, amount);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use odra::contract_def::HasEntrypoints;
+    use odra::host::Deployer;
+
+    #[test]
+    fn submodule_entrypoints_are_exposed() {
+        let test_env = odra_test::env();
+        let (owner, recipient) = (test_env.get_account(0), test_env.get_account(1));
+        let init_args = DelegatingTokenInitArgs {
+            name: String::from("Plascoin"),
+            symbol: String::from("PLS"),
+            initial_supply: 1_000.into()
+        };
+        let mut token = DelegatingToken::deploy(&test_env, init_args);
+
+        assert_eq!(token.name(), "Plascoin");
+        assert_eq!(token.get_owner(), owner);
+        token.transfer(&recipient, &100.into());
+        assert_eq!(token.balance_of(&recipient), 100.into());
+
+        token.mint(&recipient, &50.into());
+        assert_eq!(token.total_supply(), 1_050.into());
+
+        token.transfer_ownership(&recipient);
+        assert!(token.try_mint(&recipient, &50.into()).is_err());
+    }
+
+    #[test]
+    fn excluded_entrypoints_are_not_exposed() {
+        let entrypoints = DelegatingToken::entrypoints()
+            .into_iter()
+            .map(|ep| ep.name)
+            .collect::<Vec<_>>();
+
+        for name in [
+            "transfer",
+            "balance_of",
+            "get_owner",
+            "renounce_ownership",
+            "mint"
+        ] {
+            assert!(entrypoints.contains(&name.to_string()));
+        }
+        for name in ["burn", "assert_owner", "unchecked_transfer_ownership"] {
+            assert!(!entrypoints.contains(&name.to_string()));
+        }
+    }
+}
@@ -43,4 +43,31 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_party_assertion() {
+        let test_env = odra_test::env();
+        let party_contract = PartyContract::deploy(&test_env, NoArgs);
+        odra_test::assert_emitted!(
+            party_contract,
+            PartyStarted {
+                caller: test_env.get_account(0),
+                block_time: 0
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "emitted events:\n  0: PartyStarted { caller: account-hash-")]
+    fn test_party_assertion_message() {
+        let test_env = odra_test::env();
+        let party_contract = PartyContract::deploy(&test_env, NoArgs);
+        odra_test::assert_emitted!(
+            party_contract,
+            PartyStarted {
+                caller: test_env.get_account(1),
+                block_time: 0
+            }
+        );
+    }
 }
@@ -0,0 +1,136 @@
+//! This example demonstrates how to install new contracts from another contract.
+use odra::casper_types::{runtime_args, RuntimeArgs};
+use odra::factory::HasTemplate;
+use odra::prelude::*;
+
+/// Event emitted when the counter is incremented.
+#[odra::event]
+pub struct Incremented {
+    /// The value of the counter after the increment.
+    pub value: u32
+}
+
+/// A counter installed by the [CounterFactory] contract.
+#[odra::module(events = [Incremented])]
+pub struct Counter {
+    creator: Var<Address>,
+    value: Var<u32>
+}
+
+#[odra::module(factory)]
+impl Counter {
+    /// Initializes the counter with the given value.
+    pub fn init(&mut self, value: u32) {
+        self.creator.set(self.env().caller());
+        self.value.set(value);
+    }
+
+    /// Increments the counter.
+    pub fn increment(&mut self) {
+        let value = self.value.get_or_default() + 1;
+        self.value.set(value);
+        self.env().emit_event(Incremented { value });
+    }
+
+    /// Returns the current value of the counter.
+    pub fn value(&self) -> u32 {
+        self.value.get_or_default()
+    }
+
+    /// Returns the address which installed the counter.
+    pub fn creator(&self) -> Option<Address> {
+        self.creator.get()
+    }
+}
+
+/// Errors of the [CounterRegistry] contract.
+#[odra::odra_error]
+pub enum Error {
+    /// The initial value of a counter must not be zero.
+    ZeroValue = 1
+}
+
+/// A hand-written factory keeping track of the installed [Counter] contracts.
+#[odra::module(errors = Error)]
+pub struct CounterRegistry {
+    counters: List<Address>
+}
+
+/// The installed modules are listed in `factory_of`, so their entry points
+/// are exported from the wasm of the registry. The entry points sharing a name
+/// with an entry point of the registry are not exported.
+#[odra::module(factory_of = [Counter])]
+impl CounterRegistry {
+    /// Installs a new [Counter] contract with the given non-zero value.
+    pub fn add_counter(&mut self, value: u32) -> Address {
+        let template = <Counter as HasTemplate>::template();
+        let args = runtime_args! { "value" => value };
+        let address = self.env().new_contract(&template, Some(args));
+        // The installation is reverted along with the call.
+        if value == 0 {
+            self.env().revert(Error::ZeroValue);
+        }
+        self.counters.push(address);
+        address
+    }
+
+    /// Returns the addresses of the installed counters.
+    pub fn counters(&self) -> Vec<Address> {
+        self.counters.iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CounterFactory, CounterHostRef, CounterRegistry, Error, Incremented};
+    use odra::host::{Deployer, HostRef, NoArgs};
+    use odra::prelude::*;
+
+    #[test]
+    fn factory_installs_contracts() {
+        let test_env = odra_test::env();
+        let mut factory = CounterFactory::deploy(&test_env, NoArgs);
+
+        let first = factory.new_contract(10);
+        let second = factory.new_contract(20);
+        assert_ne!(first, second);
+
+        let mut first = CounterHostRef::new(first, test_env.clone());
+        let second = CounterHostRef::new(second, test_env.clone());
+        assert_eq!(first.value(), 10);
+        assert_eq!(second.value(), 20);
+        assert_eq!(first.creator(), Some(*factory.address()));
+
+        first.increment();
+        assert_eq!(first.value(), 11);
+        assert_eq!(second.value(), 20);
+        assert!(test_env.emitted_event(&first, &Incremented { value: 11 }));
+    }
+
+    #[test]
+    fn registry_installs_contracts() {
+        let test_env = odra_test::env();
+        let mut registry = CounterRegistry::deploy(&test_env, NoArgs);
+
+        let address = registry.add_counter(5);
+        assert_eq!(registry.counters(), vec![address]);
+
+        let mut counter = CounterHostRef::new(address, test_env.clone());
+        counter.increment();
+        assert_eq!(counter.value(), 6);
+        assert_eq!(counter.creator(), Some(*registry.address()));
+    }
+
+    #[test]
+    fn reverted_call_does_not_install_contract() {
+        let test_env = odra_test::env();
+        let mut registry = CounterRegistry::deploy(&test_env, NoArgs);
+
+        assert_eq!(registry.try_add_counter(0), Err(Error::ZeroValue.into()));
+        assert!(registry.counters().is_empty());
+
+        // The counter gets the address the reverted call would have assigned.
+        let address = registry.add_counter(1);
+        assert_eq!(CounterHostRef::new(address, test_env).value(), 1);
+    }
+}
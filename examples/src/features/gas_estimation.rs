@@ -0,0 +1,99 @@
+//! Compares the gas estimated by the OdraVm with the gas measured by the CasperVm.
+extern crate std;
+
+use crate::contracts::owned_token::{OwnedToken, OwnedTokenInitArgs};
+use crate::features::cross_calls::{CrossContract, CrossContractInitArgs, MathEngine};
+use crate::features::events::PartyContract;
+use crate::features::native_token::PublicWallet;
+use crate::features::storage::variable::{DogContract, DogContractInitArgs};
+use odra::host::{Deployer, HostEnv, HostRef, NoArgs};
+use odra::prelude::*;
+use odra::{casper_types::U512, DeployReport};
+
+/// Executes representative calls and returns the gas of every deploy and call.
+fn representative_calls(env: &HostEnv) -> Vec<(&'static str, u64)> {
+    let recipient = env.get_account(1);
+
+    DogContract::deploy(
+        env,
+        DogContractInitArgs {
+            barks: true,
+            weight: 10,
+            name: "Mantus".to_string()
+        }
+    );
+    PartyContract::deploy(env, NoArgs);
+    let mut token = OwnedToken::deploy(
+        env,
+        OwnedTokenInitArgs {
+            name: "Plascoin".to_string(),
+            symbol: "PLS".to_string(),
+            decimals: 10,
+            initial_supply: 10_000.into()
+        }
+    );
+    token.transfer(&recipient, &100.into());
+    token.approve(&recipient, &100.into());
+    token.mint(&recipient, &100.into());
+    token.balance_of(&recipient);
+    let math_engine = MathEngine::deploy(env, NoArgs);
+    let cross_contract = CrossContract::deploy(
+        env,
+        CrossContractInitArgs {
+            math_engine_address: *math_engine.address()
+        }
+    );
+    cross_contract.add_using_another();
+    let mut wallet = PublicWallet::deploy(env, NoArgs);
+    wallet.with_tokens(U512::from(10_000_000_000u64)).deposit();
+    wallet.withdraw(&U512::from(1_000_000_000u64));
+    wallet.balance();
+
+    let names = [
+        "DogContract::init",
+        "PartyContract::init",
+        "OwnedToken::init",
+        "OwnedToken::transfer",
+        "OwnedToken::approve",
+        "OwnedToken::mint",
+        "OwnedToken::balance_of",
+        "MathEngine::deploy",
+        "CrossContract::init",
+        "CrossContract::add_using_another",
+        "PublicWallet::deploy",
+        "PublicWallet::deposit",
+        "PublicWallet::withdraw",
+        "PublicWallet::balance"
+    ];
+    let gas = env.gas_report().into_iter().map(|report| match report {
+        DeployReport::WasmDeploy { gas, .. } | DeployReport::ContractCall { gas, .. } => {
+            gas.as_u64()
+        }
+    });
+    names.into_iter().zip(gas).collect()
+}
+
+/// The maximum relative difference between the estimated and the measured gas.
+const TOLERANCE: f64 = 0.3;
+
+#[test]
+fn odra_vm_estimates_match_casper_vm() {
+    // The CasperVm executes the wasm files, which are built when testing with the casper backend.
+    if std::env::var("ODRA_BACKEND").as_deref() != Ok("casper") {
+        return;
+    }
+    let estimated = representative_calls(&odra_test::odra_vm_env());
+    let measured = representative_calls(&odra_test::casper_vm_env());
+    assert_eq!(estimated.len(), measured.len());
+    for ((name, estimated), (_, measured)) in estimated.into_iter().zip(measured) {
+        let difference = (estimated as f64 - measured as f64).abs() / measured as f64;
+        assert!(
+            difference <= TOLERANCE,
+            "{}: estimated {} differs from measured {} by more than {}%",
+            name,
+            estimated,
+            measured,
+            TOLERANCE * 100.0
+        );
+    }
+}
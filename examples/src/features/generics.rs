@@ -0,0 +1,97 @@
+//! This example demonstrates how to write a module generic over the type of the stored items.
+use odra::casper_types::bytesrepr::{FromBytes, ToBytes};
+use odra::casper_types::CLTyped;
+use odra::prelude::*;
+
+/// Errors of the [Registry] module.
+#[odra::odra_error]
+pub enum Error {
+    /// The item with the given id does not exist.
+    ItemNotFound = 1
+}
+
+/// A registry of items of any serializable type.
+#[odra::module(errors = Error)]
+pub struct Registry<T: ToBytes + FromBytes + CLTyped> {
+    items: Mapping<u32, T>,
+    count: Var<u32>
+}
+
+#[odra::module(instances = [NumberRegistry = Registry<u32>, NameRegistry = Registry<String>])]
+impl<T: ToBytes + FromBytes + CLTyped> Registry<T> {
+    /// Adds the item to the registry and returns its id.
+    pub fn add(&mut self, item: T) -> u32 {
+        let id = self.count.get_or_default();
+        self.items.set(&id, item);
+        self.count.set(id + 1);
+        id
+    }
+
+    /// Returns the item with the given id.
+    pub fn get(&self, id: u32) -> T {
+        match self.items.get(&id) {
+            Some(item) => item,
+            None => self.env().revert(Error::ItemNotFound)
+        }
+    }
+
+    /// Returns the number of items in the registry.
+    pub fn count(&self) -> u32 {
+        self.count.get_or_default()
+    }
+}
+
+/// A contract using a concrete type of the generic [Registry] as a submodule.
+#[odra::module]
+pub struct AddressBook {
+    registry: SubModule<Registry<Address>>
+}
+
+#[odra::module]
+impl AddressBook {
+    /// Registers the caller and returns their id.
+    pub fn register(&mut self) -> u32 {
+        let caller = self.env().caller();
+        self.registry.add(caller)
+    }
+
+    /// Returns the address registered with the given id.
+    pub fn address_of(&self, id: u32) -> Address {
+        self.registry.get(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AddressBook, Error, NameRegistry, NumberRegistry};
+    use odra::host::{Deployer, NoArgs};
+    use odra::prelude::*;
+
+    #[test]
+    fn instances_of_generic_module() {
+        let test_env = odra_test::env();
+        let mut numbers = NumberRegistry::deploy(&test_env, NoArgs);
+        let mut names = NameRegistry::deploy(&test_env, NoArgs);
+
+        assert_eq!(numbers.add(42), 0);
+        assert_eq!(numbers.add(7), 1);
+        assert_eq!(names.add(String::from("Alice")), 0);
+
+        assert_eq!(numbers.get(1), 7);
+        assert_eq!(names.get(0), String::from("Alice"));
+        assert_eq!(numbers.count(), 2);
+        assert_eq!(names.count(), 1);
+        assert_eq!(names.try_get(1), Err(Error::ItemNotFound.into()));
+    }
+
+    #[test]
+    fn generic_module_as_submodule() {
+        let test_env = odra_test::env();
+        let mut book = AddressBook::deploy(&test_env, NoArgs);
+
+        test_env.set_caller(test_env.get_account(1));
+        assert_eq!(book.register(), 0);
+        assert_eq!(book.address_of(0), test_env.get_account(1));
+        assert_eq!(book.try_address_of(1), Err(Error::ItemNotFound.into()));
+    }
+}
@@ -97,4 +97,41 @@ mod tests {
             Error::NotAnOwner.into()
         );
     }
+
+    #[test]
+    fn test_owner_error_assertion() {
+        let test_env = odra_test::env();
+        let mut owned_contract = OwnedContract::deploy(
+            &test_env,
+            OwnedContractInitArgs {
+                name: "OwnedContract".to_string()
+            }
+        );
+
+        test_env.set_caller(test_env.get_account(1));
+        odra_test::assert_reverts!(
+            owned_contract.try_change_name("NewName".to_string()),
+            Error::NotAnOwner
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "expected the call to revert with `OwnerNotSet (user error 1)`, but it reverted with `NotAnOwner (user error 2)`"
+    )]
+    fn test_owner_error_assertion_message() {
+        let test_env = odra_test::env();
+        let mut owned_contract = OwnedContract::deploy(
+            &test_env,
+            OwnedContractInitArgs {
+                name: "OwnedContract".to_string()
+            }
+        );
+
+        test_env.set_caller(test_env.get_account(1));
+        odra_test::assert_reverts!(
+            owned_contract.try_change_name("NewName".to_string()),
+            Error::OwnerNotSet
+        );
+    }
 }
@@ -0,0 +1,147 @@
+//! This example demonstrates how to define an interface of a contract and implement it in modules.
+use odra::prelude::*;
+use odra::ContractRef;
+
+/// An interface of a contract counting calls.
+#[odra::interface]
+pub trait Countable {
+    /// Returns the current value of the counter.
+    fn value(&self) -> u32;
+
+    /// Increments the counter.
+    fn increment(&mut self);
+}
+
+/// A counter incremented by one.
+#[odra::module]
+pub struct SimpleCounter {
+    value: Var<u32>
+}
+
+#[odra::module(implements = [Countable])]
+impl SimpleCounter {
+    /// Initializes the counter with the given value.
+    pub fn init(&mut self, value: u32) {
+        self.value.set(value);
+    }
+
+    /// Resets the counter.
+    pub fn reset(&mut self) {
+        self.value.set(0);
+    }
+}
+
+#[odra::interface]
+impl Countable for SimpleCounter {
+    fn value(&self) -> u32 {
+        self.value.get_or_default()
+    }
+
+    fn increment(&mut self) {
+        self.value.add(1);
+    }
+}
+
+/// A counter incremented by the configured step.
+#[odra::module]
+pub struct StepCounter {
+    value: Var<u32>,
+    step: Var<u32>
+}
+
+#[odra::module(implements = [Countable])]
+impl StepCounter {
+    /// Initializes the counter with the given step.
+    pub fn init(&mut self, step: u32) {
+        self.step.set(step);
+    }
+}
+
+#[odra::interface]
+impl Countable for StepCounter {
+    fn value(&self) -> u32 {
+        self.value.get_or_default()
+    }
+
+    fn increment(&mut self) {
+        self.value.add(self.step.get_or_default());
+    }
+}
+
+/// A contract incrementing any counter implementing the [Countable] interface.
+#[odra::module]
+pub struct CounterClient;
+
+#[odra::module]
+impl CounterClient {
+    /// Increments the counter at the given address the given number of times.
+    pub fn increment(&mut self, counter: Address, times: u32) -> u32 {
+        let mut counter = CountableContractRef::new(self.env(), counter);
+        for _ in 0..times {
+            counter.increment();
+        }
+        counter.value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use odra::contract_def::ContractBlueprint;
+    use odra::host::{Deployer, HostRef, NoArgs};
+
+    fn increment_twice<T: Countable>(counter: &mut T) -> u32 {
+        counter.increment();
+        counter.increment();
+        counter.value()
+    }
+
+    #[test]
+    fn modules_implement_interface() {
+        let test_env = odra_test::env();
+        let mut simple = SimpleCounter::deploy(&test_env, SimpleCounterInitArgs { value: 10 });
+        let mut step = StepCounter::deploy(&test_env, StepCounterInitArgs { step: 5 });
+
+        assert_eq!(increment_twice(&mut simple), 12);
+        assert_eq!(increment_twice(&mut step), 10);
+
+        simple.reset();
+        assert_eq!(simple.value(), 0);
+        assert_eq!(simple.try_value(), Ok(0));
+    }
+
+    #[test]
+    fn interface_refers_to_any_implementation() {
+        let test_env = odra_test::env();
+        let simple = SimpleCounter::deploy(&test_env, SimpleCounterInitArgs { value: 1 });
+        let step = StepCounter::deploy(&test_env, StepCounterInitArgs { step: 3 });
+
+        for (address, expected) in [(*simple.address(), 3), (*step.address(), 6)] {
+            let mut counter = CountableHostRef::new(address, test_env.clone());
+            assert_eq!(increment_twice(&mut counter), expected);
+        }
+    }
+
+    #[test]
+    fn contract_calls_interface() {
+        let test_env = odra_test::env();
+        let step = StepCounter::deploy(&test_env, StepCounterInitArgs { step: 2 });
+        let mut client = CounterClient::deploy(&test_env, NoArgs);
+
+        assert_eq!(client.increment(*step.address(), 3), 6);
+        assert_eq!(step.value(), 6);
+    }
+
+    #[test]
+    fn schema_lists_implemented_interfaces() {
+        let blueprint = ContractBlueprint::new::<SimpleCounter>();
+        assert_eq!(blueprint.interfaces, vec![String::from("Countable")]);
+        assert!(blueprint
+            .entrypoints
+            .iter()
+            .any(|ep| ep.name == "increment"));
+
+        let blueprint = ContractBlueprint::new::<CounterClient>();
+        assert!(blueprint.interfaces.is_empty());
+    }
+}
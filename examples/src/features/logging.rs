@@ -0,0 +1,39 @@
+//! This example demonstrates how to print debug messages from inside a contract.
+use odra::prelude::*;
+
+/// Contract that logs every change of its counter.
+#[odra::module]
+pub struct LoggingCounter {
+    count: Var<u32>
+}
+
+#[odra::module]
+impl LoggingCounter {
+    /// Increments the counter by the given value.
+    pub fn add(&mut self, value: u32) {
+        let count = self.count.get_or_default();
+        odra::log!(self.env(), "adding {} to {}", value, count);
+        self.count.set(count + value);
+    }
+
+    /// Returns the current value of the counter.
+    pub fn count(&self) -> u32 {
+        self.count.get_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LoggingCounter;
+    use odra::host::{Deployer, NoArgs};
+
+    #[test]
+    fn test_logging() {
+        let test_env = odra_test::env();
+        let mut counter = LoggingCounter::deploy(&test_env, NoArgs);
+
+        // Prints `[LoggingCounter::add depth=1] adding 3 to 0` in odra-vm.
+        counter.add(3);
+        assert_eq!(counter.count(), 3);
+    }
+}
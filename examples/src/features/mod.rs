@@ -1,18 +1,29 @@
 //! Module containing examples of various Odra features.
 pub mod access_control;
+pub mod access_guards;
 pub mod collecting_events;
 pub mod cross_calls;
 pub mod custom_types;
+pub mod delegation;
 pub mod events;
+pub mod factory;
+#[cfg(test)]
+mod gas_estimation;
+pub mod generics;
 pub mod handling_errors;
 pub mod host_functions;
+pub mod interfaces;
 pub mod livenet;
+pub mod logging;
 pub mod module_nesting;
 pub mod modules;
+pub mod multicall;
 pub mod native_token;
 pub mod optional_args;
 pub mod pauseable;
+pub mod receive;
 pub mod reentrancy_guard;
 pub mod signature_verifier;
 pub mod storage;
 pub mod testing;
+pub mod views;
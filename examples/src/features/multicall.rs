@@ -0,0 +1,105 @@
+//! This example demonstrates how to execute multiple calls to a contract in a single transaction.
+use odra::casper_types::U512;
+use odra::prelude::*;
+
+/// Errors of the [BatchCounter] contract.
+#[odra::odra_error]
+pub enum Error {
+    /// The counter exceeded the limit.
+    LimitExceeded = 1
+}
+
+/// The maximum value of the counter.
+pub const LIMIT: u32 = 100;
+
+/// A counter which entrypoints can be called in a batch.
+#[odra::module(errors = Error)]
+pub struct BatchCounter {
+    value: Var<u32>,
+    deposits: Var<U512>
+}
+
+#[odra::module(multicall)]
+impl BatchCounter {
+    /// Adds the given amount to the counter.
+    pub fn add(&mut self, amount: u32) {
+        let value = self.value.get_or_default() + amount;
+        if value > LIMIT {
+            self.env().revert(Error::LimitExceeded);
+        }
+        self.value.set(value);
+    }
+
+    /// Returns the current value of the counter.
+    pub fn value(&self) -> u32 {
+        self.value.get_or_default()
+    }
+
+    /// Accepts native tokens, payable entrypoints cannot be called in a batch.
+    #[odra(payable)]
+    pub fn deposit(&mut self) {
+        self.deposits.add(self.env().attached_value());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BatchCounter, Error};
+    use odra::casper_types::bytesrepr::FromBytes;
+    use odra::casper_types::RuntimeArgs;
+    use odra::host::{Deployer, NoArgs};
+    use odra::multicall;
+    use odra::prelude::*;
+
+    #[test]
+    fn multicall_executes_calls_in_order() {
+        let test_env = odra_test::env();
+        let mut counter = BatchCounter::deploy(&test_env, NoArgs);
+
+        let batch = counter.batch().add(10).add(20).value();
+        let results = counter.multicall(batch.into_calls());
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(u32::from_bytes(&results[2]).unwrap().0, 30);
+        assert_eq!(counter.value(), 30);
+    }
+
+    #[test]
+    fn multicall_reverts_atomically() {
+        let test_env = odra_test::env();
+        let mut counter = BatchCounter::deploy(&test_env, NoArgs);
+
+        let batch = counter.batch().add(60).add(60);
+        assert_eq!(
+            counter.try_multicall(batch.into_calls()),
+            Err(Error::LimitExceeded.into())
+        );
+        assert_eq!(counter.value(), 0);
+    }
+
+    #[test]
+    fn multicall_rejects_payable_and_unknown_entrypoints() {
+        let test_env = odra_test::env();
+        let mut counter = BatchCounter::deploy(&test_env, NoArgs);
+
+        for entry_point in ["deposit", "unknown"] {
+            let calls = vec![multicall::encode_call(entry_point, RuntimeArgs::new())];
+            assert_eq!(
+                counter.try_multicall(calls),
+                Err(ExecutionError::UnknownEntrypoint.into())
+            );
+        }
+    }
+
+    #[test]
+    fn multicall_requires_call_args() {
+        let test_env = odra_test::env();
+        let mut counter = BatchCounter::deploy(&test_env, NoArgs);
+
+        let calls = vec![multicall::encode_call("add", RuntimeArgs::new())];
+        assert_eq!(
+            counter.try_multicall(calls),
+            Err(ExecutionError::MissingArg.into())
+        );
+    }
+}
@@ -67,4 +67,16 @@ mod tests {
             original_caller_balance - U512::from(100)
         );
     }
+
+    #[test]
+    fn test_withdraw_balance_change() {
+        let test_env = odra_test::env();
+        let mut my_contract = PublicWallet::deploy(&test_env, NoArgs);
+        my_contract.with_tokens(U512::from(100)).deposit();
+        let wallet = *my_contract.address();
+
+        odra_test::assert_balance_change!(test_env, wallet, -25, || {
+            my_contract.withdraw(&U512::from(25))
+        });
+    }
 }
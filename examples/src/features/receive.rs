@@ -0,0 +1,155 @@
+//! This example demonstrates how a contract reacts to plain CSPR transfers and calls of unknown entrypoints.
+use odra::casper_types::U512;
+use odra::prelude::*;
+
+/// Event emitted when the tokens are donated.
+#[odra::event]
+pub struct Donated {
+    /// The donor.
+    pub donor: Address,
+    /// The amount of donated tokens.
+    pub amount: U512
+}
+
+/// A contract collecting donations sent with a plain CSPR transfer.
+#[odra::module(events = [Donated])]
+pub struct DonationBox {
+    total_donations: Var<U512>,
+    unknown_calls: Var<u32>
+}
+
+#[odra::module]
+impl DonationBox {
+    /// Called when the tokens are transferred to the contract.
+    #[odra(receive)]
+    pub fn donate(&mut self) {
+        let amount = self.env().attached_value();
+        self.total_donations.add(amount);
+        self.env().emit_event(Donated {
+            donor: self.env().caller(),
+            amount
+        });
+    }
+
+    /// Called instead of an entrypoint the contract does not define.
+    #[odra(fallback)]
+    pub fn fallback(&mut self) {
+        self.unknown_calls.add(1);
+    }
+
+    /// Returns the sum of all donations.
+    pub fn total_donations(&self) -> U512 {
+        self.total_donations.get_or_default()
+    }
+
+    /// Returns the number of calls of unknown entrypoints.
+    pub fn unknown_calls(&self) -> u32 {
+        self.unknown_calls.get_or_default()
+    }
+}
+
+/// A contract forwarding donations to other contracts.
+#[odra::module]
+pub struct Donor;
+
+#[odra::module]
+impl Donor {
+    /// Transfers the attached tokens to the given contract.
+    #[odra(payable)]
+    pub fn donate(&mut self, recipient: Address) {
+        let amount = self.env().attached_value();
+        self.env().transfer_tokens(&recipient, &amount);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Donated, DonationBox, Donor};
+    use crate::features::native_token::PublicWallet;
+    use odra::casper_types::{RuntimeArgs, U512};
+    use odra::host::{Deployer, HostRef, NoArgs};
+    use odra::prelude::*;
+    use odra::CallDef;
+
+    #[test]
+    fn transfer_calls_receive() {
+        let test_env = odra_test::env();
+        let donation_box = DonationBox::deploy(&test_env, NoArgs);
+        let donor = test_env.get_account(1);
+
+        test_env.set_caller(donor);
+        assert_eq!(
+            test_env.transfer(*donation_box.address(), U512::from(100)),
+            Ok(())
+        );
+
+        assert_eq!(test_env.balance_of(&donation_box), U512::from(100));
+        assert_eq!(donation_box.total_donations(), U512::from(100));
+        assert!(test_env.emitted_event(
+            &donation_box,
+            &Donated {
+                donor,
+                amount: U512::from(100)
+            }
+        ));
+    }
+
+    #[test]
+    fn transfer_to_contract_without_receive_fails() {
+        let test_env = odra_test::env();
+        let wallet = PublicWallet::deploy(&test_env, NoArgs);
+
+        assert_eq!(
+            test_env.transfer(*wallet.address(), U512::from(100)),
+            Err(ExecutionError::TransferToContract.into())
+        );
+    }
+
+    #[test]
+    fn unknown_entrypoint_calls_fallback() {
+        let test_env = odra_test::env();
+        let donation_box = DonationBox::deploy(&test_env, NoArgs);
+
+        let call_def = CallDef::new("unknown", true, RuntimeArgs::new());
+        assert_eq!(
+            test_env.call_contract::<()>(*donation_box.address(), call_def),
+            Ok(())
+        );
+        assert_eq!(donation_box.unknown_calls(), 1);
+    }
+
+    #[test]
+    fn contract_transfer_calls_receive() {
+        let test_env = odra_test::env();
+        let donation_box = DonationBox::deploy(&test_env, NoArgs);
+        let donor = Donor::deploy(&test_env, NoArgs);
+
+        donor
+            .with_tokens(U512::from(100))
+            .donate(*donation_box.address());
+
+        assert_eq!(test_env.balance_of(&donor), U512::zero());
+        assert_eq!(test_env.balance_of(&donation_box), U512::from(100));
+        assert_eq!(donation_box.total_donations(), U512::from(100));
+        assert!(test_env.emitted_event(
+            &donation_box,
+            &Donated {
+                donor: *donor.address(),
+                amount: U512::from(100)
+            }
+        ));
+    }
+
+    #[test]
+    fn contract_transfer_to_contract_without_receive_fails() {
+        let test_env = odra_test::env();
+        let wallet = PublicWallet::deploy(&test_env, NoArgs);
+        let donor = Donor::deploy(&test_env, NoArgs);
+
+        assert!(donor
+            .with_tokens(U512::from(100))
+            .try_donate(*wallet.address())
+            .is_err());
+        assert_eq!(test_env.balance_of(&wallet), U512::zero());
+    }
+}
@@ -0,0 +1,129 @@
+//! This example demonstrates read-only (view) entrypoints.
+use odra::prelude::*;
+use odra::ContractRef;
+
+/// Event emitted when the counter is touched.
+#[odra::event]
+pub struct Touched {
+    /// The value of the counter.
+    pub count: u32
+}
+
+/// Counter with entrypoints that are not allowed to modify the state.
+#[odra::module(events = [Touched])]
+pub struct ViewCounter {
+    count: Var<u32>
+}
+
+#[odra::module]
+impl ViewCounter {
+    /// Increments the counter.
+    pub fn increment(&mut self) {
+        self.count.add(1);
+    }
+
+    /// Returns the current value of the counter.
+    #[odra(view)]
+    pub fn count(&self) -> u32 {
+        self.count.get_or_default()
+    }
+
+    /// Tries to emit an event, which reverts in a view.
+    #[odra(view)]
+    pub fn touch(&self) {
+        self.env().emit_event(Touched {
+            count: self.count()
+        });
+    }
+
+    /// Emits an event, entrypoints taking `&self` are not views unless marked with `#[odra(view)]`.
+    pub fn log(&self) {
+        self.env().emit_event(Touched {
+            count: self.count()
+        });
+    }
+
+    /// Tries to increment the counter, which reverts as the entrypoint is marked as a view.
+    #[odra(view)]
+    pub fn increment_and_get(&mut self) -> u32 {
+        self.increment();
+        self.count()
+    }
+
+    /// Tries to increment another counter, which reverts as a view cannot call a mutable entrypoint.
+    #[odra(view)]
+    pub fn increment_other(&self, other: Address) {
+        ViewCounterContractRef::new(self.env(), other).increment();
+    }
+
+    /// Returns the value of another counter.
+    #[odra(view)]
+    pub fn count_other(&self, other: Address) -> u32 {
+        ViewCounterContractRef::new(self.env(), other).count()
+    }
+
+    /// Tries to make another counter emit an event, which reverts as a view can call only views.
+    #[odra(view)]
+    pub fn log_other(&self, other: Address) {
+        ViewCounterContractRef::new(self.env(), other).log();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ViewCounter;
+    use odra::host::{Deployer, HostRef, NoArgs};
+    use odra::prelude::*;
+
+    #[test]
+    fn views_cannot_modify_state() {
+        let test_env = odra_test::env();
+        let mut counter = ViewCounter::deploy(&test_env, NoArgs);
+        counter.increment();
+
+        assert_eq!(counter.try_touch(), Err(ExecutionError::WriteInView.into()));
+        assert_eq!(
+            counter.try_increment_and_get(),
+            Err(ExecutionError::WriteInView.into())
+        );
+        assert_eq!(counter.count(), 1);
+        assert_eq!(test_env.events_count(counter.address()), 0);
+    }
+
+    #[test]
+    fn entrypoints_taking_self_are_not_views_by_default() {
+        let test_env = odra_test::env();
+        let counter = ViewCounter::deploy(&test_env, NoArgs);
+
+        counter.log();
+        assert_eq!(test_env.events_count(counter.address()), 1);
+    }
+
+    #[test]
+    fn views_cannot_call_mutable_entrypoints() {
+        let test_env = odra_test::env();
+        let counter = ViewCounter::deploy(&test_env, NoArgs);
+        let mut other = ViewCounter::deploy(&test_env, NoArgs);
+        other.increment();
+
+        assert_eq!(counter.count_other(*other.address()), 1);
+        assert_eq!(
+            counter.try_increment_other(*other.address()),
+            Err(ExecutionError::WriteInView.into())
+        );
+        assert_eq!(other.count(), 1);
+    }
+
+    #[test]
+    fn views_cannot_call_entrypoints_taking_self() {
+        let test_env = odra_test::env();
+        let counter = ViewCounter::deploy(&test_env, NoArgs);
+        let other = ViewCounter::deploy(&test_env, NoArgs);
+
+        assert_eq!(
+            counter.try_log_other(*other.address()),
+            Err(ExecutionError::WriteInView.into())
+        );
+        assert_eq!(test_env.events_count(other.address()), 0);
+    }
+}
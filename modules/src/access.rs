@@ -4,5 +4,7 @@ pub mod errors;
 pub mod events;
 mod ownable;
 
-pub use access_control::{AccessControl, AccessControlContractRef, Role, DEFAULT_ADMIN_ROLE};
+pub use access_control::{
+    role_id, AccessControl, AccessControlContractRef, Role, DEFAULT_ADMIN_ROLE
+};
 pub use ownable::*;
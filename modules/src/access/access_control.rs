@@ -1,6 +1,7 @@
 //! Access Control module.
 use super::events::*;
 use crate::access::errors::Error;
+use odra::module::RoleGuard;
 use odra::prelude::*;
 
 /// The role identifier.
@@ -9,6 +10,22 @@ pub type Role = [u8; 32];
 /// The default admin role.
 pub const DEFAULT_ADMIN_ROLE: Role = [0u8; 32];
 
+/// Returns the identifier of the role with the given name, the name padded with zeros.
+///
+/// The roles of the entrypoints restricted with `#[odra(only_role(field, "ROLE"))]` are derived
+/// with this function. Panics if the name is longer than 32 bytes, so it fails to compile in a constant.
+pub const fn role_id(name: &str) -> Role {
+    let name = name.as_bytes();
+    assert!(name.len() <= 32, "The role name is longer than 32 bytes");
+    let mut role = [0u8; 32];
+    let mut i = 0;
+    while i < name.len() {
+        role[i] = name[i];
+        i += 1;
+    }
+    role
+}
+
 /// This contract module enables the implementation of role-based access control mechanisms for children
 /// modules.
 ///
@@ -146,3 +163,34 @@ impl AccessControl {
         }
     }
 }
+
+impl RoleGuard for AccessControl {
+    /// Reverts with [Error::MissingRole] if `caller` has not been granted the role
+    /// identified by [role_id] of `role`.
+    fn assert_role(&self, role: &str, caller: &Address) {
+        // a name longer than 32 bytes identifies no role, so no one has it
+        if role.len() > 32 {
+            self.env().revert(Error::MissingRole);
+        }
+        self.check_role(&role_id(role), caller);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::role_id;
+
+    #[test]
+    fn role_id_pads_name_with_zeros() {
+        let role = role_id("MINTER");
+        assert_eq!(&role[..6], b"MINTER");
+        assert!(role[6..].iter().all(|byte| *byte == 0));
+        assert_eq!(role_id(""), super::DEFAULT_ADMIN_ROLE);
+    }
+
+    #[test]
+    #[should_panic(expected = "The role name is longer than 32 bytes")]
+    fn role_id_rejects_long_names() {
+        role_id("A_ROLE_NAME_LONGER_THAN_32_BYTES!");
+    }
+}
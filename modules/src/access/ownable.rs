@@ -1,6 +1,7 @@
 //! Ownable module.
 use crate::access::errors::Error;
 use crate::access::events::{OwnershipTransferStarted, OwnershipTransferred};
+use odra::module::OwnerGuard;
 use odra::prelude::*;
 
 /// This module provides a straightforward access control feature that enables
@@ -78,6 +79,12 @@ impl Ownable {
     }
 }
 
+impl OwnerGuard for Ownable {
+    fn assert_owner(&self, caller: &Address) {
+        Ownable::assert_owner(self, caller);
+    }
+}
+
 /// This module provides a straightforward access control feature that enables
 /// exclusive access to particular functions by an account, known as the owner.
 /// The account that initiates contract deployment is automatically assigned as
@@ -152,6 +159,12 @@ impl Ownable2Step {
     }
 }
 
+impl OwnerGuard for Ownable2Step {
+    fn assert_owner(&self, caller: &Address) {
+        self.ownable.assert_owner(caller);
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
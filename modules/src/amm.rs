@@ -0,0 +1,8 @@
+//! Constant product automated market maker modules.
+pub mod errors;
+pub mod events;
+mod pair;
+mod router;
+
+pub use pair::*;
+pub use router::*;
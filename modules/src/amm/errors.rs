@@ -0,0 +1,41 @@
+//! Errors for AMM modules.
+use odra::prelude::OdraError;
+
+/// AMM-related errors.
+#[odra::odra_error]
+pub enum Error {
+    /// The pair tokens are the same.
+    IdenticalTokens = 54_000,
+    /// The fee exceeds the maximum fee.
+    InvalidFee = 54_001,
+    /// The deposited tokens are worth no liquidity.
+    InsufficientLiquidityMinted = 54_002,
+    /// The burnt liquidity is worth no tokens.
+    InsufficientLiquidityBurned = 54_003,
+    /// The output amount is zero or below the minimum.
+    InsufficientOutputAmount = 54_004,
+    /// The input amount is zero.
+    InsufficientInputAmount = 54_005,
+    /// The reserves cannot cover the output amount.
+    InsufficientLiquidity = 54_006,
+    /// The recipient is one of the pair tokens.
+    InvalidRecipient = 54_007,
+    /// The swap decreases the product of the reserves.
+    InvalidK = 54_008,
+    /// The balance exceeds the maximum reserve.
+    ReserveOverflow = 54_009,
+    /// The deadline has passed.
+    Expired = 55_000,
+    /// No pair is registered for the tokens.
+    UnknownPair = 55_001,
+    /// A pair is already registered for the tokens.
+    PairAlreadyRegistered = 55_002,
+    /// The path has fewer than two tokens.
+    InvalidPath = 55_003,
+    /// The amount of the first token is below the minimum.
+    InsufficientAAmount = 55_004,
+    /// The amount of the second token is below the minimum.
+    InsufficientBAmount = 55_005,
+    /// The input amount exceeds the maximum.
+    ExcessiveInputAmount = 55_006
+}
@@ -0,0 +1,71 @@
+//! Events emitted by the AMM modules.
+use odra::casper_types::U256;
+use odra::prelude::*;
+
+/// Emitted when liquidity is added to a pair.
+#[odra::event]
+pub struct LiquidityAdded {
+    /// The account that added the liquidity.
+    pub sender: Address,
+    /// The amount of the first token deposited.
+    pub amount0: U256,
+    /// The amount of the second token deposited.
+    pub amount1: U256
+}
+
+/// Emitted when liquidity is removed from a pair.
+#[odra::event]
+pub struct LiquidityRemoved {
+    /// The account that removed the liquidity.
+    pub sender: Address,
+    /// The amount of the first token withdrawn.
+    pub amount0: U256,
+    /// The amount of the second token withdrawn.
+    pub amount1: U256,
+    /// The recipient of the tokens.
+    pub to: Address
+}
+
+/// Emitted when tokens are swapped.
+#[odra::event]
+pub struct Swap {
+    /// The account that executed the swap.
+    pub sender: Address,
+    /// The amount of the first token paid in.
+    pub amount0_in: U256,
+    /// The amount of the second token paid in.
+    pub amount1_in: U256,
+    /// The amount of the first token paid out.
+    pub amount0_out: U256,
+    /// The amount of the second token paid out.
+    pub amount1_out: U256,
+    /// The recipient of the output tokens.
+    pub to: Address
+}
+
+/// Emitted when the reserves of a pair are updated.
+#[odra::event]
+pub struct Sync {
+    /// The reserve of the first token.
+    pub reserve0: U256,
+    /// The reserve of the second token.
+    pub reserve1: U256
+}
+
+/// Emitted when the swap fee of a pair is changed.
+#[odra::event]
+pub struct FeeChanged {
+    /// The new fee, in basis points.
+    pub fee: u32
+}
+
+/// Emitted when a pair is registered in the router.
+#[odra::event]
+pub struct PairRegistered {
+    /// The first token of the pair.
+    pub token0: Address,
+    /// The second token of the pair.
+    pub token1: Address,
+    /// The address of the pair.
+    pub pair: Address
+}
@@ -0,0 +1,622 @@
+//! Pair module.
+use super::errors::Error;
+use super::events::*;
+use crate::access::Ownable;
+use crate::cep18_token::{Cep18, Cep18ContractRef};
+use odra::casper_types::account::AccountHash;
+use odra::casper_types::U256;
+use odra::prelude::*;
+use odra::uints::ToU512;
+use odra::ContractRef;
+
+/// The liquidity locked forever by the first deposit.
+pub const MINIMUM_LIQUIDITY: u64 = 1_000;
+/// The denominator of the swap fee, the fee is given in basis points.
+pub const FEE_DENOMINATOR: u32 = 10_000;
+/// The maximum swap fee, 10 percent.
+pub const MAX_FEE: u32 = 1_000;
+
+/// The number of fractional bits of the prices added to the accumulators.
+const PRICE_RESOLUTION: usize = 112;
+
+/// The owner of the locked liquidity, an account nobody controls.
+const LOCKED_LIQUIDITY_OWNER: Address = Address::Account(AccountHash::new([0u8; 32]));
+
+/// This contract module holds the reserves of two [Cep18](crate::cep18_token::Cep18) tokens
+/// and swaps them keeping the product of the reserves constant.
+///
+/// The liquidity providers receive the pair's own Cep18 tokens, representing a share of the reserves.
+/// The swaps pay a configurable fee, which stays in the reserves and accrues to the liquidity providers.
+///
+/// Like in the Uniswap V2 pair, the tokens are transferred to the pair before calling [mint()](Pair::mint),
+/// [burn()](Pair::burn) or [swap()](Pair::swap), which compare the balances of the pair with the reserves.
+/// Accounts should interact with the pair through the [Router](super::Router), which does both in a single call.
+///
+/// The pair accumulates the time-weighted prices of the tokens, which serve as a price oracle resistant
+/// to manipulation within a single block. The prices are fixed-point numbers with 112 fractional bits,
+/// multiplied by the elapsed milliseconds. The accumulators wrap around on overflow, the consumers
+/// compute the differences with wrapping subtraction.
+#[odra::module(
+    events = [LiquidityAdded, LiquidityRemoved, Swap, Sync, FeeChanged],
+    errors = Error
+)]
+pub struct Pair {
+    ownable: SubModule<Ownable>,
+    lp_token: SubModule<Cep18>,
+    token0: External<Cep18ContractRef>,
+    token1: External<Cep18ContractRef>,
+    fee: Var<u32>,
+    reserve0: Var<U256>,
+    reserve1: Var<U256>,
+    block_timestamp_last: Var<u64>,
+    price0_cumulative_last: Var<U256>,
+    price1_cumulative_last: Var<U256>
+}
+
+#[odra::module]
+impl Pair {
+    /// Initializes the pair of the tokens with the swap fee, given in basis points.
+    pub fn init(&mut self, token0: Address, token1: Address, fee: u32) {
+        if token0 == token1 {
+            self.env().revert(Error::IdenticalTokens);
+        }
+        self.ownable.init();
+        self.token0.set(token0);
+        self.token1.set(token1);
+        self.raw_set_fee(fee);
+        self.lp_token.init(
+            "ODRA-LP".to_string(),
+            "Odra LP".to_string(),
+            18,
+            U256::zero(),
+            vec![],
+            vec![],
+            None
+        );
+    }
+
+    /// Mints the liquidity tokens for the tokens transferred to the pair since the last update.
+    ///
+    /// The first deposit locks the [MINIMUM_LIQUIDITY] forever. Returns the minted liquidity.
+    #[odra(non_reentrant)]
+    pub fn mint(&mut self, to: &Address) -> U256 {
+        let (reserve0, reserve1) = self.reserves();
+        let (balance0, balance1) = self.balances();
+        // the balances of a token with a rebasing supply may fall below the reserves
+        let amount0 = balance0
+            .checked_sub(reserve0)
+            .unwrap_or_revert_with(self, Error::InsufficientLiquidityMinted);
+        let amount1 = balance1
+            .checked_sub(reserve1)
+            .unwrap_or_revert_with(self, Error::InsufficientLiquidityMinted);
+
+        let total_supply = self.lp_token.total_supply();
+        let liquidity = if total_supply.is_zero() {
+            let minimum_liquidity = U256::from(MINIMUM_LIQUIDITY);
+            self.lp_token
+                .raw_mint(&LOCKED_LIQUIDITY_OWNER, &minimum_liquidity);
+            (amount0 * amount1)
+                .integer_sqrt()
+                .saturating_sub(minimum_liquidity)
+        } else {
+            (amount0 * total_supply / reserve0).min(amount1 * total_supply / reserve1)
+        };
+        if liquidity.is_zero() {
+            self.env().revert(Error::InsufficientLiquidityMinted);
+        }
+        self.lp_token.raw_mint(to, &liquidity);
+
+        self.update(balance0, balance1, reserve0, reserve1);
+        self.env().emit_event(LiquidityAdded {
+            sender: self.env().caller(),
+            amount0,
+            amount1
+        });
+        liquidity
+    }
+
+    /// Burns the liquidity tokens transferred to the pair and transfers the tokens they are worth.
+    ///
+    /// Returns the amounts of the first and the second token.
+    #[odra(non_reentrant)]
+    pub fn burn(&mut self, to: &Address) -> (U256, U256) {
+        let (reserve0, reserve1) = self.reserves();
+        let (balance0, balance1) = self.balances();
+        let this = self.env().self_address();
+        let liquidity = self.lp_token.balance_of(&this);
+
+        let total_supply = self.lp_token.total_supply();
+        let amount0 = liquidity * balance0 / total_supply;
+        let amount1 = liquidity * balance1 / total_supply;
+        if amount0.is_zero() || amount1.is_zero() {
+            self.env().revert(Error::InsufficientLiquidityBurned);
+        }
+        self.lp_token.raw_burn(&this, &liquidity);
+        self.token0.transfer(to, &amount0);
+        self.token1.transfer(to, &amount1);
+
+        let (balance0, balance1) = self.balances();
+        self.update(balance0, balance1, reserve0, reserve1);
+        self.env().emit_event(LiquidityRemoved {
+            sender: self.env().caller(),
+            amount0,
+            amount1,
+            to: *to
+        });
+        (amount0, amount1)
+    }
+
+    /// Transfers the output amounts to the recipient, in exchange for the tokens transferred to the pair.
+    ///
+    /// The input, reduced by the fee, must keep the product of the reserves.
+    #[odra(non_reentrant)]
+    pub fn swap(&mut self, amount0_out: &U256, amount1_out: &U256, to: &Address) {
+        if amount0_out.is_zero() && amount1_out.is_zero() {
+            self.env().revert(Error::InsufficientOutputAmount);
+        }
+        let (reserve0, reserve1) = self.reserves();
+        if *amount0_out >= reserve0 || *amount1_out >= reserve1 {
+            self.env().revert(Error::InsufficientLiquidity);
+        }
+        if *to == self.token0() || *to == self.token1() {
+            self.env().revert(Error::InvalidRecipient);
+        }
+
+        if !amount0_out.is_zero() {
+            self.token0.transfer(to, amount0_out);
+        }
+        if !amount1_out.is_zero() {
+            self.token1.transfer(to, amount1_out);
+        }
+        let (balance0, balance1) = self.balances();
+        let amount0_in = balance0.saturating_sub(reserve0 - *amount0_out);
+        let amount1_in = balance1.saturating_sub(reserve1 - *amount1_out);
+        if amount0_in.is_zero() && amount1_in.is_zero() {
+            self.env().revert(Error::InsufficientInputAmount);
+        }
+
+        // the products may not fit in U256
+        let fee = U256::from(self.fee());
+        let denominator = U256::from(FEE_DENOMINATOR);
+        let adjusted0 = (balance0 * denominator - amount0_in * fee).to_u512();
+        let adjusted1 = (balance1 * denominator - amount1_in * fee).to_u512();
+        let k = reserve0.to_u512() * reserve1.to_u512() * (denominator * denominator).to_u512();
+        if adjusted0 * adjusted1 < k {
+            self.env().revert(Error::InvalidK);
+        }
+
+        self.update(balance0, balance1, reserve0, reserve1);
+        self.env().emit_event(Swap {
+            sender: self.env().caller(),
+            amount0_in,
+            amount1_in,
+            amount0_out: *amount0_out,
+            amount1_out: *amount1_out,
+            to: *to
+        });
+    }
+
+    /// Transfers the tokens exceeding the reserves to the recipient.
+    #[odra(non_reentrant)]
+    pub fn skim(&mut self, to: &Address) {
+        let (reserve0, reserve1) = self.reserves();
+        let this = self.env().self_address();
+        let excess0 = self.token0.balance_of(&this).saturating_sub(reserve0);
+        let excess1 = self.token1.balance_of(&this).saturating_sub(reserve1);
+        self.token0.transfer(to, &excess0);
+        self.token1.transfer(to, &excess1);
+    }
+
+    /// Updates the reserves to the balances of the pair.
+    #[odra(non_reentrant)]
+    pub fn sync(&mut self) {
+        let (reserve0, reserve1) = self.reserves();
+        let (balance0, balance1) = self.balances();
+        self.update(balance0, balance1, reserve0, reserve1);
+    }
+
+    /// Changes the swap fee, given in basis points.
+    #[odra(only_owner = ownable)]
+    pub fn set_fee(&mut self, fee: u32) {
+        self.raw_set_fee(fee);
+    }
+
+    /// Returns the address of the first token.
+    pub fn token0(&self) -> Address {
+        *self.token0.address()
+    }
+
+    /// Returns the address of the second token.
+    pub fn token1(&self) -> Address {
+        *self.token1.address()
+    }
+
+    /// Returns the swap fee, in basis points.
+    pub fn fee(&self) -> u32 {
+        self.fee.get_or_default()
+    }
+
+    /// Returns the reserves of the tokens and the time of their last update.
+    pub fn get_reserves(&self) -> (U256, U256, u64) {
+        let (reserve0, reserve1) = self.reserves();
+        (
+            reserve0,
+            reserve1,
+            self.block_timestamp_last.get_or_default()
+        )
+    }
+
+    /// Returns the accumulated price of the first token in the second token.
+    pub fn price0_cumulative_last(&self) -> U256 {
+        self.price0_cumulative_last.get_or_default()
+    }
+
+    /// Returns the accumulated price of the second token in the first token.
+    pub fn price1_cumulative_last(&self) -> U256 {
+        self.price1_cumulative_last.get_or_default()
+    }
+
+    delegate! {
+        to self.ownable {
+            fn get_owner(&self) -> Address;
+            fn transfer_ownership(&mut self, new_owner: &Address);
+        }
+
+        to self.lp_token {
+            fn name(&self) -> String;
+            fn symbol(&self) -> String;
+            fn decimals(&self) -> u8;
+            fn total_supply(&self) -> U256;
+            fn balance_of(&self, address: &Address) -> U256;
+            fn allowance(&self, owner: &Address, spender: &Address) -> U256;
+            fn approve(&mut self, spender: &Address, amount: &U256);
+            fn decrease_allowance(&mut self, spender: &Address, decr_by: &U256);
+            fn increase_allowance(&mut self, spender: &Address, inc_by: &U256);
+            fn transfer(&mut self, recipient: &Address, amount: &U256);
+            fn transfer_from(&mut self, owner: &Address, recipient: &Address, amount: &U256);
+        }
+    }
+}
+
+impl Pair {
+    fn reserves(&self) -> (U256, U256) {
+        (
+            self.reserve0.get_or_default(),
+            self.reserve1.get_or_default()
+        )
+    }
+
+    fn balances(&self) -> (U256, U256) {
+        let this = self.env().self_address();
+        let balance0 = self.token0.balance_of(&this);
+        let balance1 = self.token1.balance_of(&this);
+        // the reserves fit in 128 bits, so the products of the reserves and the prices fit in U256
+        let max_reserve = U256::from(u128::MAX);
+        if balance0 > max_reserve || balance1 > max_reserve {
+            self.env().revert(Error::ReserveOverflow);
+        }
+        (balance0, balance1)
+    }
+
+    fn update(&mut self, balance0: U256, balance1: U256, reserve0: U256, reserve1: U256) {
+        let now = self.env().get_block_time();
+        let elapsed = U256::from(now - self.block_timestamp_last.get_or_default());
+        if !elapsed.is_zero() && !reserve0.is_zero() && !reserve1.is_zero() {
+            let price0 = (reserve1 << PRICE_RESOLUTION) / reserve0;
+            let price1 = (reserve0 << PRICE_RESOLUTION) / reserve1;
+            let cumulative0 = self
+                .price0_cumulative_last()
+                .overflowing_add(price0.overflowing_mul(elapsed).0)
+                .0;
+            let cumulative1 = self
+                .price1_cumulative_last()
+                .overflowing_add(price1.overflowing_mul(elapsed).0)
+                .0;
+            self.price0_cumulative_last.set(cumulative0);
+            self.price1_cumulative_last.set(cumulative1);
+        }
+        self.reserve0.set(balance0);
+        self.reserve1.set(balance1);
+        self.block_timestamp_last.set(now);
+        self.env().emit_event(Sync {
+            reserve0: balance0,
+            reserve1: balance1
+        });
+    }
+
+    fn raw_set_fee(&mut self, fee: u32) {
+        if fee > MAX_FEE {
+            self.env().revert(Error::InvalidFee);
+        }
+        self.fee.set(fee);
+        self.env().emit_event(FeeChanged { fee });
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::access::errors::Error as AccessError;
+    use crate::cep18::utils::Cep18Modality;
+    use crate::cep18_token::tests::setup_with_args;
+    use crate::cep18_token::{Cep18HostRef, Cep18InitArgs};
+    use odra::host::{Deployer, HostEnv, HostRef, NoArgs};
+
+    pub const SUPPLY: u64 = 1_000_000_000;
+    pub const FEE: u32 = 30;
+
+    pub fn deploy_token(env: &HostEnv, symbol: &str) -> Cep18HostRef {
+        setup_with_args(
+            env,
+            Cep18InitArgs {
+                symbol: symbol.to_string(),
+                name: symbol.to_string(),
+                decimals: 9,
+                initial_supply: SUPPLY.into(),
+                admin_list: vec![],
+                minter_list: vec![],
+                modality: Some(Cep18Modality::None)
+            }
+        )
+    }
+
+    pub fn deploy_pair(env: &HostEnv, token0: &Cep18HostRef, token1: &Cep18HostRef) -> PairHostRef {
+        Pair::deploy(
+            env,
+            PairInitArgs {
+                token0: *token0.address(),
+                token1: *token1.address(),
+                fee: FEE
+            }
+        )
+    }
+
+    /// A token which balances are set at will, simulating a token with a rebasing supply.
+    #[odra::module]
+    pub struct RebasingToken {
+        balances: Mapping<Address, U256>
+    }
+
+    #[odra::module]
+    impl RebasingToken {
+        pub fn set_balance(&mut self, owner: &Address, amount: &U256) {
+            self.balances.set(owner, *amount);
+        }
+
+        pub fn balance_of(&self, address: &Address) -> U256 {
+            self.balances.get_or_default(address)
+        }
+
+        pub fn transfer(&mut self, recipient: &Address, amount: &U256) {
+            let caller = self.env().caller();
+            self.set_balance(&caller, &(self.balance_of(&caller) - *amount));
+            self.set_balance(recipient, &(self.balance_of(recipient) + *amount));
+        }
+    }
+
+    fn setup() -> (PairHostRef, Cep18HostRef, Cep18HostRef) {
+        let env = odra_test::env();
+        let token0 = deploy_token(&env, "TK0");
+        let token1 = deploy_token(&env, "TK1");
+        let pair = deploy_pair(&env, &token0, &token1);
+        (pair, token0, token1)
+    }
+
+    // Transfers the tokens to the pair and mints the liquidity to the caller.
+    fn add_liquidity(
+        pair: &mut PairHostRef,
+        token0: &mut Cep18HostRef,
+        token1: &mut Cep18HostRef,
+        amount0: u64,
+        amount1: u64
+    ) -> U256 {
+        token0.transfer(pair.address(), &amount0.into());
+        token1.transfer(pair.address(), &amount1.into());
+        pair.mint(&pair.env().caller())
+    }
+
+    #[test]
+    fn liquidity_is_minted_and_burnt() {
+        let (mut pair, mut token0, mut token1) = setup();
+        let env = pair.env().clone();
+        let (owner, alice) = (env.get_account(0), env.get_account(1));
+
+        // the first deposit mints the geometric mean, less the locked liquidity
+        let liquidity = add_liquidity(&mut pair, &mut token0, &mut token1, 1_000_000, 4_000_000);
+        assert_eq!(liquidity, U256::from(1_999_000));
+        assert_eq!(pair.balance_of(&owner), liquidity);
+        assert_eq!(
+            pair.balance_of(&LOCKED_LIQUIDITY_OWNER),
+            U256::from(MINIMUM_LIQUIDITY)
+        );
+        let (reserve0, reserve1, _) = pair.get_reserves();
+        assert_eq!((reserve0, reserve1), (1_000_000.into(), 4_000_000.into()));
+        assert!(env.emitted_event(
+            &pair,
+            &LiquidityAdded {
+                sender: owner,
+                amount0: 1_000_000.into(),
+                amount1: 4_000_000.into()
+            }
+        ));
+
+        // the next deposits mint proportionally to the smaller contribution
+        let liquidity = add_liquidity(&mut pair, &mut token0, &mut token1, 500_000, 3_000_000);
+        assert_eq!(liquidity, U256::from(1_000_000));
+        assert_eq!(pair.total_supply(), U256::from(3_000_000));
+
+        let pair_address = *pair.address();
+        pair.transfer(&pair_address, &1_500_000.into());
+        assert_eq!(
+            pair.burn(&alice),
+            (U256::from(750_000), U256::from(3_500_000))
+        );
+        assert_eq!(token0.balance_of(&alice), U256::from(750_000));
+        assert_eq!(token1.balance_of(&alice), U256::from(3_500_000));
+        assert_eq!(pair.total_supply(), U256::from(1_500_000));
+        assert!(env.emitted_event(
+            &pair,
+            &LiquidityRemoved {
+                sender: owner,
+                amount0: 750_000.into(),
+                amount1: 3_500_000.into(),
+                to: alice
+            }
+        ));
+
+        assert_eq!(
+            pair.try_mint(&owner),
+            Err(Error::InsufficientLiquidityMinted.into())
+        );
+        assert_eq!(
+            pair.try_burn(&owner),
+            Err(Error::InsufficientLiquidityBurned.into())
+        );
+    }
+
+    #[test]
+    fn swap_keeps_product_of_reserves() {
+        let (mut pair, mut token0, mut token1) = setup();
+        let env = pair.env().clone();
+        let (owner, alice) = (env.get_account(0), env.get_account(1));
+        add_liquidity(&mut pair, &mut token0, &mut token1, 1_000_000, 1_000_000);
+
+        assert_eq!(
+            pair.try_swap(&U256::zero(), &U256::zero(), &alice),
+            Err(Error::InsufficientOutputAmount.into())
+        );
+        assert_eq!(
+            pair.try_swap(&U256::zero(), &1_000_000.into(), &alice),
+            Err(Error::InsufficientLiquidity.into())
+        );
+        assert_eq!(
+            pair.try_swap(&U256::zero(), &1.into(), token1.address()),
+            Err(Error::InvalidRecipient.into())
+        );
+        assert_eq!(
+            pair.try_swap(&U256::zero(), &1.into(), &alice),
+            Err(Error::InsufficientInputAmount.into())
+        );
+
+        // the input reduced by the 0.3% fee pays for at most 9_871 tokens
+        token0.transfer(pair.address(), &10_000.into());
+        assert_eq!(
+            pair.try_swap(&U256::zero(), &9_872.into(), &alice),
+            Err(Error::InvalidK.into())
+        );
+        pair.swap(&U256::zero(), &9_871.into(), &alice);
+        assert_eq!(token1.balance_of(&alice), U256::from(9_871));
+        let (reserve0, reserve1, _) = pair.get_reserves();
+        assert_eq!((reserve0, reserve1), (1_010_000.into(), 990_129.into()));
+        assert!(env.emitted_event(
+            &pair,
+            &Swap {
+                sender: owner,
+                amount0_in: 10_000.into(),
+                amount1_in: U256::zero(),
+                amount0_out: U256::zero(),
+                amount1_out: 9_871.into(),
+                to: alice
+            }
+        ));
+    }
+
+    #[test]
+    fn prices_accumulate_over_time() {
+        let (mut pair, mut token0, mut token1) = setup();
+        let env = pair.env().clone();
+        let alice = env.get_account(1);
+        add_liquidity(&mut pair, &mut token0, &mut token1, 1_000_000, 2_000_000);
+        assert_eq!(pair.price0_cumulative_last(), U256::zero());
+
+        // the prices of the reserves before the update accumulate for the elapsed time
+        env.advance_block_time(1_000);
+        token0.transfer(pair.address(), &1_000_000.into());
+        pair.sync();
+        let one = U256::one() << PRICE_RESOLUTION;
+        assert_eq!(pair.price0_cumulative_last(), one * 2 * 1_000);
+        assert_eq!(pair.price1_cumulative_last(), one / 2 * 1_000);
+        let (_, _, timestamp) = pair.get_reserves();
+        assert_eq!(timestamp, env.block_time());
+
+        env.advance_block_time(1_000);
+        pair.sync();
+        assert_eq!(pair.price0_cumulative_last(), one * 3_000);
+        assert_eq!(pair.price1_cumulative_last(), one * 1_500);
+
+        // the excess over the reserves is skimmed
+        token1.transfer(pair.address(), &500.into());
+        pair.skim(&alice);
+        assert_eq!(token1.balance_of(&alice), U256::from(500));
+        let (reserve0, reserve1, _) = pair.get_reserves();
+        assert_eq!((reserve0, reserve1), (2_000_000.into(), 2_000_000.into()));
+    }
+
+    #[test]
+    fn fee_is_configurable() {
+        let (mut pair, mut token0, mut token1) = setup();
+        let env = pair.env().clone();
+        let alice = env.get_account(1);
+        assert_eq!(pair.fee(), FEE);
+        assert_eq!(
+            Pair::try_deploy(
+                &env,
+                PairInitArgs {
+                    token0: *token0.address(),
+                    token1: *token0.address(),
+                    fee: FEE
+                }
+            )
+            .err(),
+            Some(Error::IdenticalTokens.into())
+        );
+
+        assert_eq!(pair.try_set_fee(MAX_FEE + 1), Err(Error::InvalidFee.into()));
+        env.set_caller(alice);
+        assert_eq!(
+            pair.try_set_fee(0),
+            Err(AccessError::CallerNotTheOwner.into())
+        );
+        env.set_caller(env.get_account(0));
+        pair.set_fee(0);
+        assert!(env.emitted_event(&pair, &FeeChanged { fee: 0 }));
+
+        // without the fee, the output keeps the product of the reserves exactly
+        add_liquidity(&mut pair, &mut token0, &mut token1, 1_000_000, 1_000_000);
+        token0.transfer(pair.address(), &250_000.into());
+        pair.swap(&U256::zero(), &200_000.into(), &alice);
+        assert_eq!(token1.balance_of(&alice), U256::from(200_000));
+    }
+
+    #[test]
+    fn balances_below_reserves_are_handled() {
+        let env = odra_test::env();
+        let (owner, alice) = (env.get_account(0), env.get_account(1));
+        let mut token0 = RebasingToken::deploy(&env, NoArgs);
+        let mut token1 = RebasingToken::deploy(&env, NoArgs);
+        let mut pair = Pair::deploy(
+            &env,
+            PairInitArgs {
+                token0: *token0.address(),
+                token1: *token1.address(),
+                fee: FEE
+            }
+        );
+        token0.set_balance(pair.address(), &1_000_000.into());
+        token1.set_balance(pair.address(), &4_000_000.into());
+        pair.mint(&owner);
+
+        // the supply of the first token contracts below the reserve
+        token0.set_balance(pair.address(), &900_000.into());
+        assert_eq!(
+            pair.try_mint(&owner),
+            Err(Error::InsufficientLiquidityMinted.into())
+        );
+
+        // only the excess of the second token is skimmed
+        token1.set_balance(pair.address(), &4_100_000.into());
+        pair.skim(&alice);
+        assert_eq!(token0.balance_of(&alice), U256::zero());
+        assert_eq!(token1.balance_of(&alice), U256::from(100_000));
+    }
+}
@@ -0,0 +1,628 @@
+//! Router module.
+use super::errors::Error;
+use super::events::PairRegistered;
+use super::pair::{PairContractRef, FEE_DENOMINATOR};
+use crate::access::Ownable;
+use crate::cep18_token::Cep18ContractRef;
+use odra::casper_types::U256;
+use odra::prelude::*;
+use odra::ContractRef;
+
+/// This contract module adds and removes liquidity and swaps tokens through the registered [Pair](super::Pair)s,
+/// transferring the tokens of the caller to the pairs and calling the pairs in a single call.
+///
+/// The owner registers a pair for each pair of tokens. The swaps follow a path of tokens,
+/// hopping through the pairs of the consecutive tokens. The caller bounds the amounts,
+/// protecting against the price moving before the call is executed, and sets a deadline,
+/// after which the call reverts. The caller must approve the router to transfer the tokens.
+#[odra::module(events = [PairRegistered], errors = Error)]
+pub struct Router {
+    ownable: SubModule<Ownable>,
+    pairs: Mapping<(Address, Address), Address>
+}
+
+#[odra::module]
+impl Router {
+    /// Initializes the router, the caller becomes the owner.
+    pub fn init(&mut self) {
+        self.ownable.init();
+    }
+
+    /// Registers the pair for its tokens.
+    #[odra(only_owner = ownable)]
+    pub fn register_pair(&mut self, pair: &Address) {
+        let pair_ref = self.pair_ref(pair);
+        let (token0, token1) = (pair_ref.token0(), pair_ref.token1());
+        if self.get_pair(&token0, &token1).is_some() {
+            self.env().revert(Error::PairAlreadyRegistered);
+        }
+        self.pairs.set(&(token0, token1), *pair);
+        self.pairs.set(&(token1, token0), *pair);
+        self.env().emit_event(PairRegistered {
+            token0,
+            token1,
+            pair: *pair
+        });
+    }
+
+    /// Returns the pair registered for the tokens.
+    pub fn get_pair(&self, token_a: &Address, token_b: &Address) -> Option<Address> {
+        self.pairs.get(&(*token_a, *token_b))
+    }
+
+    /// Adds liquidity to the pair of the tokens at the current price, depositing at most the desired amounts.
+    ///
+    /// Returns the deposited amounts and the liquidity minted to the recipient.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_liquidity(
+        &mut self,
+        token_a: &Address,
+        token_b: &Address,
+        amount_a_desired: &U256,
+        amount_b_desired: &U256,
+        amount_a_min: &U256,
+        amount_b_min: &U256,
+        to: &Address,
+        deadline: u64
+    ) -> (U256, U256, U256) {
+        self.ensure(deadline);
+        let pair = self.pair_for(token_a, token_b);
+        let (reserve_a, reserve_b) = self.reserves(&pair, token_a);
+        let (amount_a, amount_b) = if reserve_a.is_zero() && reserve_b.is_zero() {
+            (*amount_a_desired, *amount_b_desired)
+        } else {
+            let amount_b_optimal = self.quote(amount_a_desired, &reserve_a, &reserve_b);
+            if amount_b_optimal <= *amount_b_desired {
+                if amount_b_optimal < *amount_b_min {
+                    self.env().revert(Error::InsufficientBAmount);
+                }
+                (*amount_a_desired, amount_b_optimal)
+            } else {
+                let amount_a_optimal = self.quote(amount_b_desired, &reserve_b, &reserve_a);
+                if amount_a_optimal < *amount_a_min {
+                    self.env().revert(Error::InsufficientAAmount);
+                }
+                (amount_a_optimal, *amount_b_desired)
+            }
+        };
+
+        let caller = self.env().caller();
+        self.token_ref(token_a)
+            .transfer_from(&caller, &pair, &amount_a);
+        self.token_ref(token_b)
+            .transfer_from(&caller, &pair, &amount_b);
+        let liquidity = self.pair_ref(&pair).mint(to);
+        (amount_a, amount_b, liquidity)
+    }
+
+    /// Removes the liquidity of the caller from the pair of the tokens.
+    ///
+    /// Returns the amounts of the tokens transferred to the recipient.
+    #[allow(clippy::too_many_arguments)]
+    pub fn remove_liquidity(
+        &mut self,
+        token_a: &Address,
+        token_b: &Address,
+        liquidity: &U256,
+        amount_a_min: &U256,
+        amount_b_min: &U256,
+        to: &Address,
+        deadline: u64
+    ) -> (U256, U256) {
+        self.ensure(deadline);
+        let pair = self.pair_for(token_a, token_b);
+        let mut pair_ref = self.pair_ref(&pair);
+        pair_ref.transfer_from(&self.env().caller(), &pair, liquidity);
+        let (amount0, amount1) = pair_ref.burn(to);
+        let (amount_a, amount_b) = if *token_a == pair_ref.token0() {
+            (amount0, amount1)
+        } else {
+            (amount1, amount0)
+        };
+        if amount_a < *amount_a_min {
+            self.env().revert(Error::InsufficientAAmount);
+        }
+        if amount_b < *amount_b_min {
+            self.env().revert(Error::InsufficientBAmount);
+        }
+        (amount_a, amount_b)
+    }
+
+    /// Swaps the exact input amount of the first token of the path for at least the minimum
+    /// output amount of the last token.
+    ///
+    /// Returns the amounts of all the tokens of the path.
+    pub fn swap_exact_tokens_for_tokens(
+        &mut self,
+        amount_in: &U256,
+        amount_out_min: &U256,
+        path: Vec<Address>,
+        to: &Address,
+        deadline: u64
+    ) -> Vec<U256> {
+        self.ensure(deadline);
+        let amounts = self.get_amounts_out(amount_in, path.clone());
+        if amounts[amounts.len() - 1] < *amount_out_min {
+            self.env().revert(Error::InsufficientOutputAmount);
+        }
+        self.swap(&amounts, &path, to);
+        amounts
+    }
+
+    /// Swaps at most the maximum input amount of the first token of the path for the exact
+    /// output amount of the last token.
+    ///
+    /// Returns the amounts of all the tokens of the path.
+    pub fn swap_tokens_for_exact_tokens(
+        &mut self,
+        amount_out: &U256,
+        amount_in_max: &U256,
+        path: Vec<Address>,
+        to: &Address,
+        deadline: u64
+    ) -> Vec<U256> {
+        self.ensure(deadline);
+        let amounts = self.get_amounts_in(amount_out, path.clone());
+        if amounts[0] > *amount_in_max {
+            self.env().revert(Error::ExcessiveInputAmount);
+        }
+        self.swap(&amounts, &path, to);
+        amounts
+    }
+
+    /// Returns the amount of the second token worth the amount of the first token at the given reserves.
+    pub fn quote(&self, amount_a: &U256, reserve_a: &U256, reserve_b: &U256) -> U256 {
+        if amount_a.is_zero() {
+            self.env().revert(Error::InsufficientInputAmount);
+        }
+        if reserve_a.is_zero() || reserve_b.is_zero() {
+            self.env().revert(Error::InsufficientLiquidity);
+        }
+        *amount_a * *reserve_b / *reserve_a
+    }
+
+    /// Returns the output amount of a swap of the input amount at the given reserves and fee.
+    pub fn get_amount_out(
+        &self,
+        amount_in: &U256,
+        reserve_in: &U256,
+        reserve_out: &U256,
+        fee: u32
+    ) -> U256 {
+        if amount_in.is_zero() {
+            self.env().revert(Error::InsufficientInputAmount);
+        }
+        if reserve_in.is_zero() || reserve_out.is_zero() {
+            self.env().revert(Error::InsufficientLiquidity);
+        }
+        let amount_in_with_fee = *amount_in * self.fee_multiplier(fee);
+        let numerator = amount_in_with_fee * *reserve_out;
+        let denominator = *reserve_in * U256::from(FEE_DENOMINATOR) + amount_in_with_fee;
+        numerator / denominator
+    }
+
+    /// Returns the input amount of a swap for the output amount at the given reserves and fee.
+    pub fn get_amount_in(
+        &self,
+        amount_out: &U256,
+        reserve_in: &U256,
+        reserve_out: &U256,
+        fee: u32
+    ) -> U256 {
+        if amount_out.is_zero() {
+            self.env().revert(Error::InsufficientOutputAmount);
+        }
+        if reserve_in.is_zero() || *reserve_out <= *amount_out {
+            self.env().revert(Error::InsufficientLiquidity);
+        }
+        let numerator = *reserve_in * *amount_out * U256::from(FEE_DENOMINATOR);
+        let denominator = (*reserve_out - *amount_out) * self.fee_multiplier(fee);
+        numerator / denominator + 1
+    }
+
+    /// Returns the amounts of all the tokens of the path swapping the input amount of the first token.
+    pub fn get_amounts_out(&self, amount_in: &U256, path: Vec<Address>) -> Vec<U256> {
+        self.assert_path(&path);
+        let mut amounts = vec![*amount_in];
+        for hop in path.windows(2) {
+            let pair = self.pair_for(&hop[0], &hop[1]);
+            let (reserve_in, reserve_out) = self.reserves(&pair, &hop[0]);
+            let fee = self.pair_ref(&pair).fee();
+            let amount_out =
+                self.get_amount_out(&amounts[amounts.len() - 1], &reserve_in, &reserve_out, fee);
+            amounts.push(amount_out);
+        }
+        amounts
+    }
+
+    /// Returns the amounts of all the tokens of the path swapping for the output amount of the last token.
+    pub fn get_amounts_in(&self, amount_out: &U256, path: Vec<Address>) -> Vec<U256> {
+        self.assert_path(&path);
+        let mut amounts = vec![*amount_out];
+        for hop in path.windows(2).rev() {
+            let pair = self.pair_for(&hop[0], &hop[1]);
+            let (reserve_in, reserve_out) = self.reserves(&pair, &hop[0]);
+            let fee = self.pair_ref(&pair).fee();
+            let amount_in = self.get_amount_in(&amounts[0], &reserve_in, &reserve_out, fee);
+            amounts.insert(0, amount_in);
+        }
+        amounts
+    }
+
+    delegate! {
+        to self.ownable {
+            fn get_owner(&self) -> Address;
+            fn transfer_ownership(&mut self, new_owner: &Address);
+        }
+    }
+}
+
+impl Router {
+    fn ensure(&self, deadline: u64) {
+        if self.env().get_block_time() > deadline {
+            self.env().revert(Error::Expired);
+        }
+    }
+
+    /// Returns the share of the amount left after the fee, reverts if the fee takes the whole amount.
+    fn fee_multiplier(&self, fee: u32) -> U256 {
+        match FEE_DENOMINATOR.checked_sub(fee) {
+            Some(multiplier) if multiplier > 0 => U256::from(multiplier),
+            _ => self.env().revert(Error::InvalidFee)
+        }
+    }
+
+    fn assert_path(&self, path: &[Address]) {
+        if path.len() < 2 {
+            self.env().revert(Error::InvalidPath);
+        }
+    }
+
+    fn pair_for(&self, token_a: &Address, token_b: &Address) -> Address {
+        self.get_pair(token_a, token_b)
+            .unwrap_or_revert_with(self, Error::UnknownPair)
+    }
+
+    fn pair_ref(&self, pair: &Address) -> PairContractRef {
+        PairContractRef::new(self.env(), *pair)
+    }
+
+    fn token_ref(&self, token: &Address) -> Cep18ContractRef {
+        Cep18ContractRef::new(self.env(), *token)
+    }
+
+    // Returns the reserves of the pair, the reserve of the given token first.
+    fn reserves(&self, pair: &Address, token_a: &Address) -> (U256, U256) {
+        let pair_ref = self.pair_ref(pair);
+        let (reserve0, reserve1, _) = pair_ref.get_reserves();
+        if *token_a == pair_ref.token0() {
+            (reserve0, reserve1)
+        } else {
+            (reserve1, reserve0)
+        }
+    }
+
+    // Transfers the input amount to the first pair and swaps through the pairs of the path,
+    // each pair sends the output to the next one, the last one to the recipient.
+    fn swap(&mut self, amounts: &[U256], path: &[Address], to: &Address) {
+        let caller = self.env().caller();
+        let first_pair = self.pair_for(&path[0], &path[1]);
+        self.token_ref(&path[0])
+            .transfer_from(&caller, &first_pair, &amounts[0]);
+
+        for (i, hop) in path.windows(2).enumerate() {
+            let pair = self.pair_for(&hop[0], &hop[1]);
+            let mut pair_ref = self.pair_ref(&pair);
+            let amount_out = amounts[i + 1];
+            let (amount0_out, amount1_out) = if hop[0] == pair_ref.token0() {
+                (U256::zero(), amount_out)
+            } else {
+                (amount_out, U256::zero())
+            };
+            let recipient = match path.get(i + 2) {
+                Some(next) => self.pair_for(&hop[1], next),
+                None => *to
+            };
+            pair_ref.swap(&amount0_out, &amount1_out, &recipient);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::access::errors::Error as AccessError;
+    use crate::amm::pair::tests::{deploy_pair, deploy_token};
+    use crate::amm::PairHostRef;
+    use crate::cep18_token::Cep18HostRef;
+    use odra::host::{Deployer, HostEnv, HostRef, NoArgs};
+
+    const DEADLINE: u64 = 1_000;
+
+    struct Setup {
+        router: RouterHostRef,
+        tokens: [Cep18HostRef; 3],
+        pairs: [PairHostRef; 2]
+    }
+
+    // Deploys three tokens, the pairs of the first and the second token, and of the third
+    // and the second token, registered in the router.
+    fn setup() -> Setup {
+        let env = odra_test::env();
+        let mut router = Router::deploy(&env, NoArgs);
+        let tokens = ["TKA", "TKB", "TKC"].map(|symbol| deploy_token(&env, symbol));
+        let pairs = [
+            deploy_pair(&env, &tokens[0], &tokens[1]),
+            deploy_pair(&env, &tokens[2], &tokens[1])
+        ];
+        for pair in &pairs {
+            router.register_pair(pair.address());
+        }
+        Setup {
+            router,
+            tokens,
+            pairs
+        }
+    }
+
+    fn approve(env: &HostEnv, setup: &mut Setup, account: Address) {
+        let router = *setup.router.address();
+        for token in setup.tokens.iter_mut() {
+            env.set_caller(env.get_account(0));
+            token.transfer(&account, &10_000_000.into());
+            env.set_caller(account);
+            token.approve(&router, &10_000_000.into());
+        }
+    }
+
+    #[test]
+    fn pairs_are_registered_by_owner() {
+        let mut setup = setup();
+        let env = setup.router.env().clone();
+        let [a, b, c] = setup.tokens.each_ref().map(|token| *token.address());
+        assert_eq!(
+            setup.router.get_pair(&a, &b),
+            Some(*setup.pairs[0].address())
+        );
+        assert_eq!(
+            setup.router.get_pair(&b, &c),
+            Some(*setup.pairs[1].address())
+        );
+        assert_eq!(setup.router.get_pair(&a, &c), None);
+        assert!(env.emitted_event(
+            &setup.router,
+            &PairRegistered {
+                token0: c,
+                token1: b,
+                pair: *setup.pairs[1].address()
+            }
+        ));
+
+        assert_eq!(
+            setup.router.try_register_pair(setup.pairs[0].address()),
+            Err(Error::PairAlreadyRegistered.into())
+        );
+        env.set_caller(env.get_account(1));
+        let pair = deploy_pair(&env, &setup.tokens[0], &setup.tokens[2]);
+        assert_eq!(
+            setup.router.try_register_pair(pair.address()),
+            Err(AccessError::CallerNotTheOwner.into())
+        );
+    }
+
+    #[test]
+    fn liquidity_is_added_at_current_price() {
+        let mut setup = setup();
+        let env = setup.router.env().clone();
+        let alice = env.get_account(1);
+        approve(&env, &mut setup, alice);
+        let [a, b, c] = setup.tokens.each_ref().map(|token| *token.address());
+        let zero = U256::zero();
+
+        // the first deposit sets the price
+        let (amount_a, amount_b, liquidity) = setup.router.add_liquidity(
+            &a,
+            &b,
+            &1_000_000.into(),
+            &2_000_000.into(),
+            &zero,
+            &zero,
+            &alice,
+            DEADLINE
+        );
+        assert_eq!((amount_a, amount_b), (1_000_000.into(), 2_000_000.into()));
+        assert_eq!(liquidity, U256::from(1_413_213));
+
+        // the next deposits are limited by the desired amounts at the current price
+        let result = setup.router.add_liquidity(
+            &a,
+            &b,
+            &100_000.into(),
+            &300_000.into(),
+            &zero,
+            &zero,
+            &alice,
+            DEADLINE
+        );
+        assert_eq!((result.0, result.1), (100_000.into(), 200_000.into()));
+        let result = setup.router.add_liquidity(
+            &b,
+            &a,
+            &100_000.into(),
+            &200_000.into(),
+            &zero,
+            &zero,
+            &alice,
+            DEADLINE
+        );
+        assert_eq!((result.0, result.1), (100_000.into(), 50_000.into()));
+        assert_eq!(
+            setup.router.try_add_liquidity(
+                &a,
+                &b,
+                &100_000.into(),
+                &300_000.into(),
+                &zero,
+                &250_000.into(),
+                &alice,
+                DEADLINE
+            ),
+            Err(Error::InsufficientBAmount.into())
+        );
+        assert_eq!(
+            setup.router.try_add_liquidity(
+                &a,
+                &b,
+                &200_000.into(),
+                &100_000.into(),
+                &60_000.into(),
+                &zero,
+                &alice,
+                DEADLINE
+            ),
+            Err(Error::InsufficientAAmount.into())
+        );
+        assert_eq!(
+            setup
+                .router
+                .try_add_liquidity(&a, &c, &zero, &zero, &zero, &zero, &alice, DEADLINE),
+            Err(Error::UnknownPair.into())
+        );
+
+        // the liquidity is removed at the current price
+        let liquidity = setup.pairs[0].balance_of(&alice);
+        setup.pairs[0].approve(setup.router.address(), &liquidity);
+        assert_eq!(
+            setup
+                .router
+                .try_remove_liquidity(&b, &a, &liquidity, &zero, &zero, &alice, DEADLINE),
+            Ok((2_298_585.into(), 1_149_292.into()))
+        );
+        assert_eq!(setup.pairs[0].balance_of(&alice), zero);
+
+        env.advance_block_time(DEADLINE + 1);
+        assert_eq!(
+            setup.router.try_add_liquidity(
+                &a,
+                &b,
+                &100_000.into(),
+                &200_000.into(),
+                &zero,
+                &zero,
+                &alice,
+                DEADLINE
+            ),
+            Err(Error::Expired.into())
+        );
+    }
+
+    #[test]
+    fn tokens_are_swapped_along_path() {
+        let mut setup = setup();
+        let env = setup.router.env().clone();
+        let (alice, bob) = (env.get_account(1), env.get_account(2));
+        approve(&env, &mut setup, alice);
+        let [a, b, c] = setup.tokens.each_ref().map(|token| *token.address());
+        let zero = U256::zero();
+        setup.router.add_liquidity(
+            &a,
+            &b,
+            &1_000_000.into(),
+            &2_000_000.into(),
+            &zero,
+            &zero,
+            &alice,
+            DEADLINE
+        );
+        setup.router.add_liquidity(
+            &c,
+            &b,
+            &1_000_000.into(),
+            &2_000_000.into(),
+            &zero,
+            &zero,
+            &alice,
+            DEADLINE
+        );
+
+        let path = vec![a, b, c];
+        let amounts = vec![10_000.into(), 19_743.into(), 9_745.into()];
+        assert_eq!(
+            setup.router.get_amounts_out(&10_000.into(), path.clone()),
+            amounts
+        );
+        assert_eq!(
+            setup.router.try_swap_exact_tokens_for_tokens(
+                &10_000.into(),
+                &9_746.into(),
+                path.clone(),
+                &bob,
+                DEADLINE
+            ),
+            Err(Error::InsufficientOutputAmount.into())
+        );
+        assert_eq!(
+            setup.router.swap_exact_tokens_for_tokens(
+                &10_000.into(),
+                &9_745.into(),
+                path.clone(),
+                &bob,
+                DEADLINE
+            ),
+            amounts
+        );
+        assert_eq!(setup.tokens[2].balance_of(&bob), U256::from(9_745));
+        assert_eq!(setup.tokens[1].balance_of(&bob), zero);
+
+        // the exact output costs the input of the reversed computation
+        let amounts = setup.router.get_amounts_in(&5_000.into(), path.clone());
+        assert_eq!(
+            setup.router.try_swap_tokens_for_exact_tokens(
+                &5_000.into(),
+                &(amounts[0] - 1),
+                path.clone(),
+                &bob,
+                DEADLINE
+            ),
+            Err(Error::ExcessiveInputAmount.into())
+        );
+        let alice_balance = setup.tokens[0].balance_of(&alice);
+        setup
+            .router
+            .swap_tokens_for_exact_tokens(&5_000.into(), &amounts[0], path, &bob, DEADLINE);
+        assert_eq!(setup.tokens[2].balance_of(&bob), U256::from(14_745));
+        assert_eq!(
+            setup.tokens[0].balance_of(&alice),
+            alice_balance - amounts[0]
+        );
+
+        assert_eq!(
+            setup.router.try_get_amounts_out(&10_000.into(), vec![a]),
+            Err(Error::InvalidPath.into())
+        );
+    }
+
+    #[test]
+    fn fee_must_be_below_denominator() {
+        let setup = setup();
+        let (amount, reserve) = (U256::from(1_000), U256::from(1_000_000));
+        for fee in [FEE_DENOMINATOR, FEE_DENOMINATOR + 1] {
+            assert_eq!(
+                setup
+                    .router
+                    .try_get_amount_out(&amount, &reserve, &reserve, fee),
+                Err(Error::InvalidFee.into())
+            );
+            assert_eq!(
+                setup
+                    .router
+                    .try_get_amount_in(&amount, &reserve, &reserve, fee),
+                Err(Error::InvalidFee.into())
+            );
+        }
+        assert_eq!(
+            setup.router.get_amount_out(&amount, &reserve, &reserve, 0),
+            U256::from(999)
+        );
+    }
+}
@@ -44,6 +44,14 @@ pub enum Error {
     /// The package hash for the upgrade is missing.
     /// This error is not used by Odra implementation yet.
     _MissingPackageHashForUpgrade = 60019,
+    /// The permit deadline has passed.
+    PermitExpired = 60020,
+    /// The permit nonce does not match the nonce of the owner.
+    InvalidNonce = 60021,
+    /// The permit is not signed by the owner.
+    InvalidSignature = 60022,
+    /// The voting power lookup is not in the past.
+    FutureLookup = 60023,
     /// The contract is in an invalid state. This error should never happen.
     InvalidState = 60100
 }
@@ -90,3 +90,25 @@ pub struct ChangeSecurity {
     /// The map of changes made to the security rules.
     pub sec_change_map: BTreeMap<Address, SecurityBadge>
 }
+
+/// An event emitted when an account changes its delegate.
+#[odra::event]
+pub struct DelegateChanged {
+    /// The account delegating its votes.
+    pub delegator: Address,
+    /// The previous delegate.
+    pub from_delegate: Option<Address>,
+    /// The new delegate.
+    pub to_delegate: Address
+}
+
+/// An event emitted when the voting power of a delegate changes.
+#[odra::event]
+pub struct DelegateVotesChanged {
+    /// The delegate.
+    pub delegate: Address,
+    /// The voting power before the change.
+    pub previous_votes: U256,
+    /// The voting power after the change.
+    pub new_votes: U256
+}
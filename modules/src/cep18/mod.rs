@@ -4,8 +4,12 @@ mod cep18_client_contract;
 pub mod errors;
 /// Cep18 module events.
 pub mod events;
+/// Cep18 signed allowances.
+pub mod permit;
 /// Cep18 module storage.
 pub mod storage;
 mod tests;
 /// Cep18 module utils.
 pub mod utils;
+/// Cep18 voting power.
+pub mod votes;
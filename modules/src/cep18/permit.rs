@@ -0,0 +1,58 @@
+use odra::casper_types::bytesrepr::Bytes;
+use odra::casper_types::U256;
+use odra::prelude::*;
+
+/// The prefix the Casper Wallet adds to every signed message.
+pub const MESSAGE_PREFIX: &str = "Casper Message:\n";
+
+/// An allowance signed by the owner of the tokens, submitted with `Cep18::permit`.
+///
+/// The owner signs the bytes returned by [Permit::message], a text in the following format:
+///
+/// ```text
+/// Casper Message:
+/// CEP-18 permit
+/// token: hash-2c4a6ce0...
+/// owner: account-hash-6b0e5a3d...
+/// spender: account-hash-9f6e0c5a...
+/// amount: 1000
+/// deadline: 1718000000000
+/// nonce: 0
+/// ```
+///
+/// The addresses are formatted keys, the amount, deadline and nonce are decimal numbers.
+/// The deadline is a block time in milliseconds, the nonce is the current nonce of the owner.
+/// The message starts with the prefix added by the Casper Wallet, so the wallet users
+/// can sign it as a plain text message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Permit {
+    /// The address of the token contract.
+    pub token: Address,
+    /// The owner of the tokens.
+    pub owner: Address,
+    /// The spender allowed to spend the tokens.
+    pub spender: Address,
+    /// The allowance amount.
+    pub amount: U256,
+    /// The block time after which the permit cannot be used.
+    pub deadline: u64,
+    /// The nonce of the owner, every permit can be used only once.
+    pub nonce: u64
+}
+
+impl Permit {
+    /// Returns the message to be signed by the owner of the tokens.
+    pub fn message(&self) -> Bytes {
+        let message = format!(
+            "{}CEP-18 permit\ntoken: {}\nowner: {}\nspender: {}\namount: {}\ndeadline: {}\nnonce: {}",
+            MESSAGE_PREFIX,
+            self.token.to_string(),
+            self.owner.to_string(),
+            self.spender.to_string(),
+            self.amount,
+            self.deadline,
+            self.nonce
+        );
+        Bytes::from(message.as_bytes())
+    }
+}
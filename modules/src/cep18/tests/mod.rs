@@ -1,3 +1,5 @@
 mod allowance;
 mod mint_and_burn;
+mod permit;
 mod transfer;
+mod votes;
@@ -0,0 +1,138 @@
+#[cfg(test)]
+mod permit_tests {
+    use odra::casper_types::bytesrepr::Bytes;
+    use odra::casper_types::U256;
+    use odra::host::HostRef;
+    use odra::prelude::*;
+
+    use crate::cep18::errors::Error::{InvalidNonce, InvalidSignature, PermitExpired};
+    use crate::cep18::permit::Permit;
+    use crate::cep18_token::tests::{setup, ALLOWANCE_AMOUNT_1};
+    use crate::cep18_token::Cep18HostRef;
+
+    const DEADLINE_OFFSET: u64 = 60_000;
+
+    fn sign_permit(
+        cep18_token: &Cep18HostRef,
+        signer: Address,
+        owner: Address,
+        spender: Address,
+        deadline: u64
+    ) -> Bytes {
+        let permit = Permit {
+            token: *cep18_token.address(),
+            owner,
+            spender,
+            amount: ALLOWANCE_AMOUNT_1.into(),
+            deadline,
+            nonce: cep18_token.nonce_of(&owner)
+        };
+        cep18_token.env().sign_message(&permit.message(), &signer)
+    }
+
+    #[test]
+    fn permit_message_has_documented_format() {
+        let env = odra_test::env();
+        let (owner, spender) = (env.get_account(0), env.get_account(1));
+        let token = env.get_account(2);
+        let permit = Permit {
+            token,
+            owner,
+            spender,
+            amount: 1_000.into(),
+            deadline: 1_718_000_000_000,
+            nonce: 7
+        };
+
+        let expected = format!(
+            "Casper Message:\nCEP-18 permit\ntoken: {}\nowner: {}\nspender: {}\namount: 1000\ndeadline: 1718000000000\nnonce: 7",
+            token.to_string(),
+            owner.to_string(),
+            spender.to_string()
+        );
+        assert_eq!(permit.message(), Bytes::from(expected.as_bytes()));
+    }
+
+    #[test]
+    fn should_approve_with_permit() {
+        // given a token and a permit signed by the owner
+        let mut cep18_token = setup(false);
+        let env = cep18_token.env().clone();
+        let (owner, spender, relayer) =
+            (env.get_account(0), env.get_account(1), env.get_account(2));
+        let deadline = env.block_time() + DEADLINE_OFFSET;
+        let signature = sign_permit(&cep18_token, owner, owner, spender, deadline);
+        let amount = U256::from(ALLOWANCE_AMOUNT_1);
+
+        // when the relayer submits the permit
+        env.set_caller(relayer);
+        cep18_token.permit(
+            &env.public_key(&owner),
+            &spender,
+            &amount,
+            deadline,
+            0,
+            &signature
+        );
+
+        // then the allowance is set and the nonce is used
+        assert_eq!(cep18_token.allowance(&owner, &spender), amount);
+        assert_eq!(cep18_token.nonce_of(&owner), 1);
+
+        // and the spender can transfer the tokens
+        env.set_caller(spender);
+        cep18_token.transfer_from(&owner, &spender, &amount);
+        assert_eq!(cep18_token.balance_of(&spender), amount);
+
+        // but the permit cannot be used again
+        let result = cep18_token.try_permit(
+            &env.public_key(&owner),
+            &spender,
+            &amount,
+            deadline,
+            0,
+            &signature
+        );
+        assert_eq!(result.err().unwrap(), InvalidNonce.into());
+    }
+
+    #[test]
+    fn should_not_approve_with_expired_permit() {
+        let mut cep18_token = setup(false);
+        let env = cep18_token.env().clone();
+        let (owner, spender) = (env.get_account(0), env.get_account(1));
+        let deadline = env.block_time() + DEADLINE_OFFSET;
+        let signature = sign_permit(&cep18_token, owner, owner, spender, deadline);
+
+        env.advance_block_time(DEADLINE_OFFSET + 1);
+        let result = cep18_token.try_permit(
+            &env.public_key(&owner),
+            &spender,
+            &ALLOWANCE_AMOUNT_1.into(),
+            deadline,
+            0,
+            &signature
+        );
+        assert_eq!(result.err().unwrap(), PermitExpired.into());
+    }
+
+    #[test]
+    fn should_not_approve_with_permit_signed_by_another_account() {
+        let mut cep18_token = setup(false);
+        let env = cep18_token.env().clone();
+        let (owner, spender) = (env.get_account(0), env.get_account(1));
+        let deadline = env.block_time() + DEADLINE_OFFSET;
+        let signature = sign_permit(&cep18_token, spender, owner, spender, deadline);
+
+        let result = cep18_token.try_permit(
+            &env.public_key(&owner),
+            &spender,
+            &ALLOWANCE_AMOUNT_1.into(),
+            deadline,
+            0,
+            &signature
+        );
+        assert_eq!(result.err().unwrap(), InvalidSignature.into());
+        assert_eq!(cep18_token.allowance(&owner, &spender), U256::zero());
+    }
+}
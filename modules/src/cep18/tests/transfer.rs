@@ -5,6 +5,7 @@ mod transfer_tests {
 
     use crate::cep18::cep18_client_contract::Cep18ClientContract;
     use crate::cep18::errors::Error::{CannotTargetSelfUser, InsufficientBalance};
+    use crate::cep18::events::{Mint, Transfer};
     use crate::cep18_token::tests::{
         setup, ALLOWANCE_AMOUNT_1, TOKEN_TOTAL_SUPPLY, TRANSFER_AMOUNT_1
     };
@@ -28,6 +29,41 @@ mod transfer_tests {
         assert_eq!(cep18_token.total_supply(), amount);
     }
 
+    #[test]
+    fn should_emit_transfer_events_in_order() {
+        // given a token
+        let mut cep18_token = setup(false);
+        let owner = cep18_token.env().get_account(0);
+        let alice = cep18_token.env().get_account(1);
+        let bob = cep18_token.env().get_account(2);
+        let amount = TRANSFER_AMOUNT_1.into();
+
+        // when the owner transfers tokens to alice and then to bob
+        cep18_token.transfer(&alice, &amount);
+        cep18_token.transfer(&bob, &amount);
+
+        // then the events are emitted in order
+        odra_test::assert_events_in_order!(
+            cep18_token,
+            [
+                Mint {
+                    recipient: owner,
+                    amount: TOKEN_TOTAL_SUPPLY.into()
+                },
+                Transfer {
+                    sender: owner,
+                    recipient: alice,
+                    amount
+                },
+                Transfer {
+                    sender: owner,
+                    recipient: bob,
+                    amount
+                }
+            ]
+        );
+    }
+
     #[test]
     fn should_not_transfer_more_than_owned_balance() {
         // given a token
@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod votes_tests {
+    use odra::casper_types::U256;
+    use odra::host::HostRef;
+
+    use crate::cep18::errors::Error::FutureLookup;
+    use crate::cep18::events::{DelegateChanged, DelegateVotesChanged};
+    use crate::cep18_token::tests::{setup, TOKEN_TOTAL_SUPPLY, TRANSFER_AMOUNT_1};
+
+    const BLOCK_TIME: u64 = 1_000;
+
+    #[test]
+    fn should_count_votes_of_delegated_tokens() {
+        let mut cep18_token = setup(false);
+        let env = cep18_token.env().clone();
+        let (owner, alice, bob) = (env.get_account(0), env.get_account(1), env.get_account(2));
+        let supply = U256::from(TOKEN_TOTAL_SUPPLY);
+        let amount = U256::from(TRANSFER_AMOUNT_1);
+
+        // the tokens do not count as votes until delegated
+        assert_eq!(cep18_token.get_votes(&owner), U256::zero());
+
+        cep18_token.delegate(&owner);
+        assert_eq!(cep18_token.delegates(&owner), Some(owner));
+        assert_eq!(cep18_token.get_votes(&owner), supply);
+        assert!(env.emitted_event(
+            &cep18_token,
+            &DelegateChanged {
+                delegator: owner,
+                from_delegate: None,
+                to_delegate: owner
+            }
+        ));
+
+        // a transfer to an account without a delegate removes the votes
+        cep18_token.transfer(&alice, &amount);
+        assert_eq!(cep18_token.get_votes(&owner), supply - amount);
+        assert_eq!(cep18_token.get_votes(&alice), U256::zero());
+
+        // the votes follow the delegated tokens
+        env.set_caller(alice);
+        cep18_token.delegate(&bob);
+        assert_eq!(cep18_token.get_votes(&bob), amount);
+        assert!(env.emitted_event(
+            &cep18_token,
+            &DelegateVotesChanged {
+                delegate: bob,
+                previous_votes: U256::zero(),
+                new_votes: amount
+            }
+        ));
+
+        // a transfer between the delegated accounts moves the votes
+        cep18_token.transfer(&owner, &amount);
+        assert_eq!(cep18_token.get_votes(&bob), U256::zero());
+        assert_eq!(cep18_token.get_votes(&owner), supply);
+    }
+
+    #[test]
+    fn should_return_past_votes() {
+        let mut cep18_token = setup(true);
+        let env = cep18_token.env().clone();
+        let (owner, alice) = (env.get_account(0), env.get_account(1));
+        let supply = U256::from(TOKEN_TOTAL_SUPPLY);
+        let amount = U256::from(TRANSFER_AMOUNT_1);
+
+        env.advance_block_time(BLOCK_TIME);
+        let delegated_at = env.block_time();
+        cep18_token.delegate(&owner);
+        env.advance_block_time(BLOCK_TIME);
+
+        let transferred_at = env.block_time();
+        cep18_token.transfer(&alice, &amount);
+        // the second change in the same block overwrites the checkpoint
+        cep18_token.transfer(&alice, &amount);
+        env.advance_block_time(BLOCK_TIME);
+
+        let minted_at = env.block_time();
+        cep18_token.mint(&owner, &amount);
+        env.advance_block_time(BLOCK_TIME);
+
+        let burnt_at = env.block_time();
+        cep18_token.burn(&owner, &(supply - amount));
+        env.advance_block_time(BLOCK_TIME);
+
+        assert_eq!(
+            cep18_token.get_past_votes(&owner, delegated_at - 1),
+            U256::zero()
+        );
+        assert_eq!(cep18_token.get_past_votes(&owner, delegated_at), supply);
+        assert_eq!(
+            cep18_token.get_past_votes(&owner, transferred_at - 1),
+            supply
+        );
+        assert_eq!(
+            cep18_token.get_past_votes(&owner, transferred_at),
+            supply - amount - amount
+        );
+        assert_eq!(
+            cep18_token.get_past_votes(&owner, minted_at + 1),
+            supply - amount
+        );
+        assert_eq!(cep18_token.get_past_votes(&owner, burnt_at), U256::zero());
+        assert_eq!(cep18_token.get_votes(&owner), U256::zero());
+
+        assert_eq!(cep18_token.get_past_total_supply(delegated_at), supply);
+        assert_eq!(cep18_token.get_past_total_supply(transferred_at), supply);
+        assert_eq!(
+            cep18_token.get_past_total_supply(minted_at),
+            supply + amount
+        );
+        assert_eq!(cep18_token.get_past_total_supply(burnt_at), amount + amount);
+    }
+
+    #[test]
+    fn should_not_return_votes_in_the_future() {
+        let cep18_token = setup(false);
+        let env = cep18_token.env().clone();
+        let owner = env.get_account(0);
+
+        let result = cep18_token.try_get_past_votes(&owner, env.block_time());
+        assert_eq!(result.err().unwrap(), FutureLookup.into());
+        let result = cep18_token.try_get_past_total_supply(env.block_time() + 1);
+        assert_eq!(result.err().unwrap(), FutureLookup.into());
+    }
+}
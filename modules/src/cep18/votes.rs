@@ -0,0 +1,162 @@
+use odra::casper_types::U256;
+use odra::prelude::*;
+
+use crate::cep18::errors::Error::{FutureLookup, Overflow};
+use crate::cep18::events::{DelegateChanged, DelegateVotesChanged};
+
+/// The voting power at the given block time.
+#[odra::odra_type]
+pub struct Checkpoint {
+    /// The block time in milliseconds.
+    pub timestamp: u64,
+    /// The voting power.
+    pub votes: U256
+}
+
+/// Delegations and the voting power checkpoints of the token holders.
+///
+/// The tokens count as votes once the holder delegates them, to themselves or another account.
+/// Every balance change moves the votes between the delegates and writes a checkpoint
+/// at the current block time, the total supply is checkpointed on every mint and burn.
+#[odra::module(events = [DelegateChanged, DelegateVotesChanged])]
+pub struct Cep18Votes {
+    delegates: Mapping<Address, Address>,
+    // the checkpoints of the total supply are stored under `None`
+    checkpoints: Mapping<(Option<Address>, u32), Checkpoint>,
+    checkpoints_len: Mapping<Option<Address>, u32>
+}
+
+impl Cep18Votes {
+    /// Returns the delegate of the account.
+    pub fn delegates(&self, account: &Address) -> Option<Address> {
+        self.delegates.get(account)
+    }
+
+    /// Delegates the votes of the account with the given balance to the delegatee.
+    pub fn delegate(&mut self, account: &Address, delegatee: &Address, balance: U256) {
+        let from_delegate = self.delegates(account);
+        self.delegates.set(account, *delegatee);
+        self.env().emit_event(DelegateChanged {
+            delegator: *account,
+            from_delegate,
+            to_delegate: *delegatee
+        });
+        self.move_votes(from_delegate, Some(*delegatee), balance);
+    }
+
+    /// Updates the checkpoints after the amount of tokens was moved between the accounts.
+    ///
+    /// The sender is `None` for a mint, the recipient is `None` for a burn.
+    pub fn transfer_votes(&mut self, from: Option<&Address>, to: Option<&Address>, amount: U256) {
+        if from.is_none() {
+            self.push(None, |votes| votes.checked_add(amount));
+        }
+        if to.is_none() {
+            self.push(None, |votes| votes.checked_sub(amount));
+        }
+        let from_delegate = from.and_then(|account| self.delegates(account));
+        let to_delegate = to.and_then(|account| self.delegates(account));
+        self.move_votes(from_delegate, to_delegate, amount);
+    }
+
+    /// Returns the current voting power of the account.
+    pub fn get_votes(&self, account: &Address) -> U256 {
+        self.latest(Some(*account))
+    }
+
+    /// Returns the voting power of the account at the given block time in the past.
+    pub fn get_past_votes(&self, account: &Address, timestamp: u64) -> U256 {
+        self.assert_past(timestamp);
+        self.lookup(Some(*account), timestamp)
+    }
+
+    /// Returns the total supply at the given block time in the past.
+    pub fn get_past_total_supply(&self, timestamp: u64) -> U256 {
+        self.assert_past(timestamp);
+        self.lookup(None, timestamp)
+    }
+
+    fn move_votes(&mut self, from: Option<Address>, to: Option<Address>, amount: U256) {
+        if from == to || amount.is_zero() {
+            return;
+        }
+        if let Some(delegate) = from {
+            let (previous_votes, new_votes) =
+                self.push(Some(delegate), |votes| votes.checked_sub(amount));
+            self.env().emit_event(DelegateVotesChanged {
+                delegate,
+                previous_votes,
+                new_votes
+            });
+        }
+        if let Some(delegate) = to {
+            let (previous_votes, new_votes) =
+                self.push(Some(delegate), |votes| votes.checked_add(amount));
+            self.env().emit_event(DelegateVotesChanged {
+                delegate,
+                previous_votes,
+                new_votes
+            });
+        }
+    }
+
+    /// Writes a checkpoint at the current block time, returns the previous and the new votes.
+    fn push(&mut self, key: Option<Address>, op: impl Fn(U256) -> Option<U256>) -> (U256, U256) {
+        let timestamp = self.env().get_block_time();
+        let len = self.checkpoints_len.get_or_default(&key);
+        let last = len
+            .checked_sub(1)
+            .and_then(|pos| self.checkpoints.get(&(key, pos)));
+        let previous_votes = last.as_ref().map(|c| c.votes).unwrap_or_default();
+        let votes = op(previous_votes).unwrap_or_revert_with(self, Overflow);
+
+        match last {
+            // a checkpoint at the current block time is overwritten
+            Some(last) if last.timestamp == timestamp => {
+                self.checkpoints
+                    .set(&(key, len - 1), Checkpoint { timestamp, votes });
+            }
+            _ => {
+                self.checkpoints
+                    .set(&(key, len), Checkpoint { timestamp, votes });
+                self.checkpoints_len.set(&key, len + 1);
+            }
+        }
+        (previous_votes, votes)
+    }
+
+    fn latest(&self, key: Option<Address>) -> U256 {
+        self.checkpoints_len
+            .get_or_default(&key)
+            .checked_sub(1)
+            .and_then(|pos| self.checkpoints.get(&(key, pos)))
+            .map(|c| c.votes)
+            .unwrap_or_default()
+    }
+
+    /// Finds the votes of the last checkpoint written at or before the given block time.
+    fn lookup(&self, key: Option<Address>, timestamp: u64) -> U256 {
+        let (mut low, mut high) = (0, self.checkpoints_len.get_or_default(&key));
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match self.checkpoints.get(&(key, mid)) {
+                Some(checkpoint) if checkpoint.timestamp > timestamp => high = mid,
+                _ => low = mid + 1
+            }
+        }
+        match high.checked_sub(1) {
+            Some(pos) => self
+                .checkpoints
+                .get(&(key, pos))
+                .map(|c| c.votes)
+                .unwrap_or_default(),
+            None => U256::zero()
+        }
+    }
+
+    fn assert_past(&self, timestamp: u64) {
+        if timestamp >= self.env().get_block_time() {
+            self.env().revert(FutureLookup);
+        }
+    }
+}
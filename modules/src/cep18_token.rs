@@ -1,5 +1,6 @@
 //! CEP-18 Casper Fungible Token standard implementation.
-use odra::casper_types::U256;
+use odra::casper_types::bytesrepr::Bytes;
+use odra::casper_types::{PublicKey, U256};
 use odra::prelude::*;
 
 use crate::cep18::errors::Error;
@@ -8,11 +9,13 @@ use crate::cep18::events::{
     Burn, ChangeSecurity, DecreaseAllowance, IncreaseAllowance, Mint, SetAllowance, Transfer,
     TransferFrom
 };
+use crate::cep18::permit::Permit;
 use crate::cep18::storage::{
     Cep18AllowancesStorage, Cep18BalancesStorage, Cep18DecimalsStorage, Cep18NameStorage,
     Cep18SymbolStorage, Cep18TotalSupplyStorage
 };
 use crate::cep18::utils::{Cep18Modality, SecurityBadge};
+use crate::cep18::votes::Cep18Votes;
 
 /// CEP-18 token module
 #[odra::module(
@@ -29,7 +32,9 @@ pub struct Cep18 {
     balances: SubModule<Cep18BalancesStorage>,
     allowances: SubModule<Cep18AllowancesStorage>,
     security_badges: Mapping<Address, SecurityBadge>,
-    modality: Var<Cep18Modality>
+    modality: Var<Cep18Modality>,
+    nonces: Mapping<Address, u64>,
+    votes: SubModule<Cep18Votes>
 }
 
 #[odra::module]
@@ -55,6 +60,8 @@ impl Cep18 {
 
         // mint the initial supply for the caller
         self.balances.set(&caller, initial_supply);
+        self.votes
+            .transfer_votes(None, Some(&caller), initial_supply);
         self.env().emit_event(Mint {
             recipient: caller,
             amount: initial_supply
@@ -173,6 +180,89 @@ impl Cep18 {
         });
     }
 
+    /// Approves the spender to spend the given amount of tokens on behalf of the owner,
+    /// who signed the [Permit] message.
+    ///
+    /// Anyone can submit the permit, so the owner does not need to pay for the transaction.
+    /// The permit is valid until the deadline and can be used only once.
+    pub fn permit(
+        &mut self,
+        owner_public_key: &PublicKey,
+        spender: &Address,
+        amount: &U256,
+        deadline: u64,
+        nonce: u64,
+        signature: &Bytes
+    ) {
+        let owner = Address::from(owner_public_key.clone());
+        if owner == *spender {
+            self.env().revert(Error::CannotTargetSelfUser);
+        }
+        if self.env().get_block_time() > deadline {
+            self.env().revert(Error::PermitExpired);
+        }
+        if self.nonce_of(&owner) != nonce {
+            self.env().revert(Error::InvalidNonce);
+        }
+
+        let permit = Permit {
+            token: self.env().self_address(),
+            owner,
+            spender: *spender,
+            amount: *amount,
+            deadline,
+            nonce
+        };
+        if !self
+            .env()
+            .verify_signature(&permit.message(), signature, owner_public_key)
+        {
+            self.env().revert(Error::InvalidSignature);
+        }
+
+        self.nonces.set(&owner, nonce + 1);
+        self.allowances.set(&owner, spender, *amount);
+        self.env().emit_event(SetAllowance {
+            owner,
+            spender: *spender,
+            allowance: *amount
+        });
+    }
+
+    /// Returns the nonce of the next permit signed by the owner.
+    pub fn nonce_of(&self, owner: &Address) -> u64 {
+        self.nonces.get_or_default(owner)
+    }
+
+    /// Delegates the votes of the caller to the delegatee.
+    ///
+    /// The tokens count as votes only once delegated, a holder can delegate to themselves.
+    pub fn delegate(&mut self, delegatee: &Address) {
+        let delegator = self.env().caller();
+        let balance = self.balance_of(&delegator);
+        self.votes.delegate(&delegator, delegatee, balance);
+    }
+
+    /// Returns the delegate of the account.
+    pub fn delegates(&self, account: &Address) -> Option<Address> {
+        self.votes.delegates(account)
+    }
+
+    /// Returns the current voting power of the account.
+    pub fn get_votes(&self, account: &Address) -> U256 {
+        self.votes.get_votes(account)
+    }
+
+    /// Returns the voting power of the account at the given block time in the past.
+    pub fn get_past_votes(&self, account: &Address, timestamp: u64) -> U256 {
+        self.votes.get_past_votes(account, timestamp)
+    }
+
+    /// Returns the total supply at the given block time in the past.
+    pub fn get_past_total_supply(&self, timestamp: u64) -> U256 {
+        self.votes.get_past_total_supply(timestamp)
+    }
+
     /// Decreases the allowance of the spender by the given amount.
     pub fn decrease_allowance(&mut self, spender: &Address, decr_by: &U256) {
         let owner = self.env().caller();
@@ -288,6 +378,8 @@ impl Cep18 {
         if amount > &U256::zero() {
             self.balances.subtract(sender, *amount);
             self.balances.add(recipient, *amount);
+            self.votes
+                .transfer_votes(Some(sender), Some(recipient), *amount);
         }
 
         self.env().emit_event(Transfer {
@@ -301,6 +393,7 @@ impl Cep18 {
     pub fn raw_mint(&mut self, owner: &Address, amount: &U256) {
         self.total_supply.add(*amount);
         self.balances.add(owner, *amount);
+        self.votes.transfer_votes(None, Some(owner), *amount);
 
         self.env().emit_event(Mint {
             recipient: *owner,
@@ -312,6 +405,7 @@ impl Cep18 {
     pub fn raw_burn(&mut self, owner: &Address, amount: &U256) {
         self.total_supply.subtract(*amount);
         self.balances.subtract(owner, *amount);
+        self.votes.transfer_votes(Some(owner), None, *amount);
 
         self.env().emit_event(Burn {
             owner: *owner,
@@ -319,6 +413,18 @@ impl Cep18 {
         });
     }
 
+    /// Spends the given amount of the spender's allowance without checking the permissions.
+    pub fn raw_spend_allowance(&mut self, owner: &Address, spender: &Address, amount: &U256) {
+        let allowance = self.allowance(owner, spender);
+        self.allowances.set(
+            owner,
+            spender,
+            allowance
+                .checked_sub(*amount)
+                .unwrap_or_revert_with(self, Error::InsufficientAllowance)
+        );
+    }
+
     /// Changes the security access granted to users without checking the permissions.
     pub fn raw_change_security(
         &mut self,
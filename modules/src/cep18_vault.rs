@@ -0,0 +1,509 @@
+//! Tokenized vault implementation
+use crate::cep18_token::{Cep18, Cep18ContractRef};
+use crate::cep18_vault::errors::Error;
+use crate::cep18_vault::events::{Deposit, Withdraw};
+use odra::casper_types::{U256, U512};
+use odra::prelude::*;
+use odra::uints::{ToU256, ToU512};
+use odra::ContractRef;
+
+/// The maximum number of decimals the shares add to the decimals of the asset.
+pub const MAX_DECIMALS_OFFSET: u8 = 18;
+
+/// The Cep18Vault module.
+///
+/// Holds tokens of an underlying [Cep18](crate::cep18_token::Cep18) contract, the asset,
+/// and issues shares of the vault, a Cep18 token itself. The shares represent a proportional
+/// claim on the assets held by the vault, so any assets transferred to the vault directly
+/// increase the value of all the shares.
+///
+/// The conversions round in favor of the vault: the shares issued for deposited assets and the assets
+/// paid for redeemed shares round down, the assets required to mint shares and the shares burnt
+/// to withdraw assets round up.
+///
+/// The vault mitigates the inflation attack, where the first depositor donates assets to make
+/// the deposits of the others round down to zero shares. The conversions use virtual shares and assets,
+/// the shares have `decimals_offset` more decimals than the asset, which makes the attack
+/// unprofitable and the rounding losses of the depositors negligible.
+#[odra::module(events = [Deposit, Withdraw], errors = Error)]
+pub struct Cep18Vault {
+    token: SubModule<Cep18>,
+    asset: External<Cep18ContractRef>,
+    decimals_offset: Var<u8>
+}
+
+/// The Cep18Vault module implementation.
+#[odra::module]
+impl Cep18Vault {
+    /// Initializes the vault of the given asset with the metadata of the shares.
+    ///
+    /// The shares have the decimals of the asset increased by `decimals_offset`.
+    pub fn init(&mut self, asset: Address, symbol: String, name: String, decimals_offset: u8) {
+        if decimals_offset > MAX_DECIMALS_OFFSET {
+            self.env().revert(Error::InvalidDecimalsOffset);
+        }
+        self.asset.set(asset);
+        self.decimals_offset.set(decimals_offset);
+        let decimals = self
+            .asset
+            .decimals()
+            .checked_add(decimals_offset)
+            .unwrap_or_revert_with(self, Error::InvalidDecimalsOffset);
+        self.token
+            .init(symbol, name, decimals, U256::zero(), vec![], vec![], None);
+    }
+
+    /// Returns the address of the underlying asset.
+    pub fn asset(&self) -> Address {
+        *self.asset.address()
+    }
+
+    /// Returns the amount of the asset held by the vault.
+    pub fn total_assets(&self) -> U256 {
+        self.asset.balance_of(&self.env().self_address())
+    }
+
+    /// Returns the amount of shares worth the given amount of assets, rounded down.
+    pub fn convert_to_shares(&self, assets: &U256) -> U256 {
+        self.to_shares(assets, Rounding::Down)
+    }
+
+    /// Returns the amount of assets worth the given amount of shares, rounded down.
+    pub fn convert_to_assets(&self, shares: &U256) -> U256 {
+        self.to_assets(shares, Rounding::Down)
+    }
+
+    /// Returns the maximum amount of assets the receiver can deposit.
+    pub fn max_deposit(&self, _receiver: &Address) -> U256 {
+        U256::MAX
+    }
+
+    /// Returns the maximum amount of shares the receiver can mint.
+    pub fn max_mint(&self, _receiver: &Address) -> U256 {
+        U256::MAX
+    }
+
+    /// Returns the maximum amount of assets the owner can withdraw.
+    pub fn max_withdraw(&self, owner: &Address) -> U256 {
+        self.to_assets(&self.balance_of(owner), Rounding::Down)
+    }
+
+    /// Returns the maximum amount of shares the owner can redeem.
+    pub fn max_redeem(&self, owner: &Address) -> U256 {
+        self.balance_of(owner)
+    }
+
+    /// Returns the amount of shares a deposit of the given assets would mint.
+    pub fn preview_deposit(&self, assets: &U256) -> U256 {
+        self.to_shares(assets, Rounding::Down)
+    }
+
+    /// Returns the amount of assets required to mint the given shares.
+    pub fn preview_mint(&self, shares: &U256) -> U256 {
+        self.to_assets(shares, Rounding::Up)
+    }
+
+    /// Returns the amount of shares a withdrawal of the given assets would burn.
+    pub fn preview_withdraw(&self, assets: &U256) -> U256 {
+        self.to_shares(assets, Rounding::Up)
+    }
+
+    /// Returns the amount of assets a redemption of the given shares would pay.
+    pub fn preview_redeem(&self, shares: &U256) -> U256 {
+        self.to_assets(shares, Rounding::Down)
+    }
+
+    /// Deposits the assets of the caller and mints the shares to the receiver.
+    ///
+    /// The caller must approve the transfer of the assets first. Returns the amount of shares minted.
+    pub fn deposit(&mut self, assets: &U256, receiver: &Address) -> U256 {
+        if *assets > self.max_deposit(receiver) {
+            self.env().revert(Error::ExceededMaxDeposit);
+        }
+        let shares = self.preview_deposit(assets);
+        self.raw_deposit(receiver, assets, &shares);
+        shares
+    }
+
+    /// Mints the shares to the receiver depositing the assets of the caller.
+    ///
+    /// The caller must approve the transfer of the assets first. Returns the amount of assets deposited.
+    pub fn mint(&mut self, shares: &U256, receiver: &Address) -> U256 {
+        if *shares > self.max_mint(receiver) {
+            self.env().revert(Error::ExceededMaxMint);
+        }
+        let assets = self.preview_mint(shares);
+        self.raw_deposit(receiver, &assets, shares);
+        assets
+    }
+
+    /// Burns the shares of the owner and transfers the assets to the receiver.
+    ///
+    /// If the caller is not the owner, the shares are spent from the caller's allowance.
+    /// Returns the amount of shares burnt.
+    pub fn withdraw(&mut self, assets: &U256, receiver: &Address, owner: &Address) -> U256 {
+        if *assets > self.max_withdraw(owner) {
+            self.env().revert(Error::ExceededMaxWithdraw);
+        }
+        let shares = self.preview_withdraw(assets);
+        self.raw_withdraw(receiver, owner, assets, &shares);
+        shares
+    }
+
+    /// Redeems the shares of the owner and transfers the assets to the receiver.
+    ///
+    /// If the caller is not the owner, the shares are spent from the caller's allowance.
+    /// Returns the amount of assets transferred.
+    pub fn redeem(&mut self, shares: &U256, receiver: &Address, owner: &Address) -> U256 {
+        if *shares > self.max_redeem(owner) {
+            self.env().revert(Error::ExceededMaxRedeem);
+        }
+        let assets = self.preview_redeem(shares);
+        self.raw_withdraw(receiver, owner, &assets, shares);
+        assets
+    }
+
+    /// Returns the number of decimals the shares add to the decimals of the asset.
+    pub fn decimals_offset(&self) -> u8 {
+        self.decimals_offset.get_or_default()
+    }
+
+    delegate! {
+        to self.token {
+            fn name(&self) -> String;
+            fn symbol(&self) -> String;
+            fn decimals(&self) -> u8;
+            fn total_supply(&self) -> U256;
+            fn balance_of(&self, address: &Address) -> U256;
+            fn allowance(&self, owner: &Address, spender: &Address) -> U256;
+            fn approve(&mut self, spender: &Address, amount: &U256);
+            fn decrease_allowance(&mut self, spender: &Address, decr_by: &U256);
+            fn increase_allowance(&mut self, spender: &Address, inc_by: &U256);
+            fn transfer(&mut self, recipient: &Address, amount: &U256);
+            fn transfer_from(&mut self, owner: &Address, recipient: &Address, amount: &U256);
+        }
+    }
+}
+
+enum Rounding {
+    Down,
+    Up
+}
+
+impl Cep18Vault {
+    fn to_shares(&self, assets: &U256, rounding: Rounding) -> U256 {
+        let virtual_shares = self.total_supply() + self.virtual_shares();
+        let virtual_assets = self.total_assets() + 1;
+        self.mul_div(assets, &virtual_shares, &virtual_assets, rounding)
+    }
+
+    fn to_assets(&self, shares: &U256, rounding: Rounding) -> U256 {
+        let virtual_shares = self.total_supply() + self.virtual_shares();
+        let virtual_assets = self.total_assets() + 1;
+        self.mul_div(shares, &virtual_assets, &virtual_shares, rounding)
+    }
+
+    fn virtual_shares(&self) -> U256 {
+        U256::from(10).pow(self.decimals_offset().into())
+    }
+
+    fn mul_div(&self, x: &U256, y: &U256, denominator: &U256, rounding: Rounding) -> U256 {
+        // the product may not fit in U256
+        let product = x.to_u512() * y.to_u512();
+        let denominator = denominator.to_u512();
+        let mut result = product / denominator;
+        if matches!(rounding, Rounding::Up) && !(product % denominator).is_zero() {
+            result += U512::one();
+        }
+        result.to_u256().unwrap_or_revert(self)
+    }
+
+    fn raw_deposit(&mut self, receiver: &Address, assets: &U256, shares: &U256) {
+        let sender = self.env().caller();
+        let this = self.env().self_address();
+        self.asset.transfer_from(&sender, &this, assets);
+        self.token.raw_mint(receiver, shares);
+        self.env().emit_event(Deposit {
+            sender,
+            owner: *receiver,
+            assets: *assets,
+            shares: *shares
+        });
+    }
+
+    fn raw_withdraw(&mut self, receiver: &Address, owner: &Address, assets: &U256, shares: &U256) {
+        let sender = self.env().caller();
+        if sender != *owner {
+            self.token.raw_spend_allowance(owner, &sender, shares);
+        }
+        self.token.raw_burn(owner, shares);
+        self.asset.transfer(receiver, assets);
+        self.env().emit_event(Withdraw {
+            sender,
+            receiver: *receiver,
+            owner: *owner,
+            assets: *assets,
+            shares: *shares
+        });
+    }
+}
+
+/// Cep18Vault Events
+pub mod events {
+    use odra::casper_event_standard;
+    use odra::casper_types::U256;
+    use odra::prelude::*;
+
+    /// Event emitted when assets are deposited to the vault.
+    #[odra::event]
+    pub struct Deposit {
+        /// The account that deposited the assets.
+        pub sender: Address,
+        /// The account that received the shares.
+        pub owner: Address,
+        /// The amount of assets deposited.
+        pub assets: U256,
+        /// The amount of shares minted.
+        pub shares: U256
+    }
+
+    /// Event emitted when assets are withdrawn from the vault.
+    #[odra::event]
+    pub struct Withdraw {
+        /// The account that withdrew the assets.
+        pub sender: Address,
+        /// The account that received the assets.
+        pub receiver: Address,
+        /// The account whose shares were burnt.
+        pub owner: Address,
+        /// The amount of assets withdrawn.
+        pub assets: U256,
+        /// The amount of shares burnt.
+        pub shares: U256
+    }
+}
+
+/// Cep18Vault Errors
+pub mod errors {
+    use odra::prelude::*;
+
+    /// Cep18Vault errors
+    #[odra::odra_error]
+    pub enum Error {
+        /// The decimals offset is too large
+        InvalidDecimalsOffset = 53_000,
+        /// The deposit exceeds the maximum deposit of the receiver
+        ExceededMaxDeposit = 53_001,
+        /// The minted shares exceed the maximum mint of the receiver
+        ExceededMaxMint = 53_002,
+        /// The withdrawal exceeds the maximum withdrawal of the owner
+        ExceededMaxWithdraw = 53_003,
+        /// The redeemed shares exceed the maximum redemption of the owner
+        ExceededMaxRedeem = 53_004
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cep18::errors::Error as Cep18Error;
+    use crate::cep18::utils::Cep18Modality;
+    use crate::cep18_token::tests::setup_with_args;
+    use crate::cep18_token::{Cep18HostRef, Cep18InitArgs};
+    use odra::host::{Deployer, HostEnv, HostRef};
+
+    const SUPPLY: u64 = 1_000_000_000;
+    const ASSET_DECIMALS: u8 = 9;
+
+    fn setup(decimals_offset: u8) -> (Cep18VaultHostRef, Cep18HostRef) {
+        let env = odra_test::env();
+        let asset = setup_with_args(
+            &env,
+            Cep18InitArgs {
+                symbol: "AST".to_string(),
+                name: "Asset".to_string(),
+                decimals: ASSET_DECIMALS,
+                initial_supply: SUPPLY.into(),
+                admin_list: vec![],
+                minter_list: vec![],
+                modality: Some(Cep18Modality::None)
+            }
+        );
+        let vault = Cep18Vault::deploy(
+            &env,
+            Cep18VaultInitArgs {
+                asset: *asset.address(),
+                symbol: "vAST".to_string(),
+                name: "Asset Vault".to_string(),
+                decimals_offset
+            }
+        );
+        (vault, asset)
+    }
+
+    // Funds the account with assets and approves the vault to spend them.
+    fn fund(
+        env: &HostEnv,
+        vault: &Cep18VaultHostRef,
+        asset: &mut Cep18HostRef,
+        account: Address,
+        amount: u64
+    ) {
+        env.set_caller(env.get_account(0));
+        asset.transfer(&account, &amount.into());
+        env.set_caller(account);
+        asset.approve(vault.address(), &amount.into());
+    }
+
+    #[test]
+    fn shares_track_assets() {
+        let (mut vault, mut asset) = setup(0);
+        let env = vault.env().clone();
+        let (alice, bob) = (env.get_account(1), env.get_account(2));
+        assert_eq!(vault.asset(), *asset.address());
+        assert_eq!(vault.decimals(), ASSET_DECIMALS);
+        assert_eq!(vault.total_supply(), U256::zero());
+
+        // shares are minted one to one until the vault earns assets
+        fund(&env, &vault, &mut asset, alice, 1_000);
+        assert_eq!(vault.deposit(&100.into(), &alice), U256::from(100));
+        assert_eq!(vault.mint(&50.into(), &bob), U256::from(50));
+        assert_eq!(vault.balance_of(&alice), U256::from(100));
+        assert_eq!(vault.balance_of(&bob), U256::from(50));
+        assert_eq!(vault.total_assets(), U256::from(150));
+        assert!(env.emitted_event(
+            &vault,
+            &Deposit {
+                sender: alice,
+                owner: bob,
+                assets: 50.into(),
+                shares: 50.into()
+            }
+        ));
+
+        // the assets transferred to the vault are shared by the holders
+        env.set_caller(env.get_account(0));
+        asset.transfer(vault.address(), &150.into());
+        assert_eq!(vault.convert_to_assets(&100.into()), U256::from(199));
+        assert_eq!(vault.max_withdraw(&alice), U256::from(199));
+
+        env.set_caller(alice);
+        assert_eq!(vault.redeem(&100.into(), &alice, &alice), U256::from(199));
+        assert_eq!(asset.balance_of(&alice), U256::from(1_049));
+        assert_eq!(vault.balance_of(&alice), U256::zero());
+        assert!(env.emitted_event(
+            &vault,
+            &Withdraw {
+                sender: alice,
+                receiver: alice,
+                owner: alice,
+                assets: 199.into(),
+                shares: 100.into()
+            }
+        ));
+        env.set_caller(bob);
+        assert_eq!(vault.withdraw(&100.into(), &bob, &bob), U256::from(50));
+        assert_eq!(asset.balance_of(&bob), U256::from(100));
+        assert_eq!(vault.total_supply(), U256::zero());
+        assert_eq!(vault.total_assets(), U256::one());
+    }
+
+    #[test]
+    fn conversions_round_in_favor_of_vault() {
+        let (mut vault, mut asset) = setup(0);
+        let env = vault.env().clone();
+        let alice = env.get_account(1);
+        fund(&env, &vault, &mut asset, alice, 1_000);
+        vault.deposit(&100.into(), &alice);
+        env.set_caller(env.get_account(0));
+        asset.transfer(vault.address(), &50.into());
+
+        // a share is worth about 1.49 assets
+        assert_eq!(vault.preview_deposit(&10.into()), U256::from(6));
+        assert_eq!(vault.preview_withdraw(&10.into()), U256::from(7));
+        assert_eq!(vault.preview_mint(&10.into()), U256::from(15));
+        assert_eq!(vault.preview_redeem(&10.into()), U256::from(14));
+        assert_eq!(vault.convert_to_shares(&10.into()), U256::from(6));
+        assert_eq!(vault.convert_to_assets(&10.into()), U256::from(14));
+
+        env.set_caller(alice);
+        assert_eq!(vault.mint(&10.into(), &alice), U256::from(15));
+        assert_eq!(vault.withdraw(&30.into(), &alice, &alice), U256::from(21));
+        assert_eq!(vault.redeem(&20.into(), &alice, &alice), U256::from(30));
+        assert_eq!(vault.balance_of(&alice), U256::from(69));
+        assert_eq!(vault.total_assets(), U256::from(105));
+        assert_eq!(vault.max_withdraw(&alice), U256::from(104));
+    }
+
+    #[test]
+    fn spender_withdraws_with_allowance() {
+        let (mut vault, mut asset) = setup(0);
+        let env = vault.env().clone();
+        let (alice, bob) = (env.get_account(1), env.get_account(2));
+        fund(&env, &vault, &mut asset, alice, 1_000);
+        vault.deposit(&1_000.into(), &alice);
+
+        assert_eq!(
+            vault.try_withdraw(&1_001.into(), &alice, &alice),
+            Err(Error::ExceededMaxWithdraw.into())
+        );
+        assert_eq!(
+            vault.try_redeem(&1_001.into(), &alice, &alice),
+            Err(Error::ExceededMaxRedeem.into())
+        );
+
+        env.set_caller(bob);
+        assert_eq!(
+            vault.try_redeem(&100.into(), &bob, &alice),
+            Err(Cep18Error::InsufficientAllowance.into())
+        );
+        env.set_caller(alice);
+        vault.approve(&bob, &300.into());
+        env.set_caller(bob);
+        vault.redeem(&100.into(), &bob, &alice);
+        vault.withdraw(&200.into(), &bob, &alice);
+        assert_eq!(asset.balance_of(&bob), U256::from(300));
+        assert_eq!(vault.balance_of(&alice), U256::from(700));
+        assert_eq!(vault.allowance(&alice, &bob), U256::zero());
+        assert_eq!(
+            vault.try_withdraw(&1.into(), &bob, &alice),
+            Err(Cep18Error::InsufficientAllowance.into())
+        );
+    }
+
+    #[test]
+    fn inflation_attack_is_unprofitable() {
+        let (mut vault, mut asset) = setup(6);
+        let env = vault.env().clone();
+        let (attacker, victim) = (env.get_account(1), env.get_account(2));
+        assert_eq!(vault.decimals(), ASSET_DECIMALS + 6);
+
+        // the attacker deposits first and donates to inflate the value of a share
+        fund(&env, &vault, &mut asset, attacker, 1_000_001);
+        assert_eq!(vault.deposit(&1.into(), &attacker), U256::from(1_000_000));
+        asset.transfer(vault.address(), &1_000_000.into());
+
+        // the victim's deposit still mints shares, the victim loses a rounding unit
+        fund(&env, &vault, &mut asset, victim, 1_000_000);
+        vault.deposit(&1_000_000.into(), &victim);
+        assert_eq!(vault.max_withdraw(&victim), U256::from(999_999));
+
+        // the attacker loses about half of the donation
+        assert_eq!(vault.max_withdraw(&attacker), U256::from(500_001));
+    }
+
+    #[test]
+    fn decimals_offset_is_limited() {
+        let (vault, asset) = setup(0);
+        let init_args = Cep18VaultInitArgs {
+            asset: *asset.address(),
+            symbol: "vAST".to_string(),
+            name: "Asset Vault".to_string(),
+            decimals_offset: MAX_DECIMALS_OFFSET + 1
+        };
+        assert_eq!(
+            Cep18Vault::try_deploy(vault.env(), init_args).err(),
+            Some(Error::InvalidDecimalsOffset.into())
+        );
+    }
+}
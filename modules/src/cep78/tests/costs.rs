@@ -55,6 +55,30 @@ fn transfer_costs_should_remain_stable() {
     assert_eq!(costs.get(1), costs.get(2));
 }
 
+#[test]
+fn mint_gas_profile_should_match_gas_report() {
+    let env = odra_test::env();
+    let args = default_args_builder()
+        .owner_reverse_lookup_mode(OwnerReverseLookupMode::Complete)
+        .nft_metadata_kind(NFTMetadataKind::Raw)
+        .ownership_mode(OwnershipMode::Transferable)
+        .build();
+
+    let mut contract = TestCep78::deploy(&env, args);
+    let token_owner = env.get_account(0);
+    contract.register_owner(Maybe::Some(token_owner));
+    contract.mint(token_owner, "".to_string(), Maybe::None);
+    contract.mint(token_owner, "".to_string(), Maybe::None);
+
+    let costs = utils::get_gas_cost_of(&env, "mint");
+    let total_cost = costs.iter().fold(0u64, |acc, cost| acc + cost.as_u64());
+    let profile = env.gas_profile();
+    assert_eq!(
+        profile.entry_points().get("TestCep78::mint"),
+        Some(&total_cost)
+    );
+}
+
 fn should_cost_less_when_installing_without_reverse_lookup(reporting: OwnerReverseLookupMode) {
     let env = odra_test::env();
 
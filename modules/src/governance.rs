@@ -0,0 +1,10 @@
+//! Governance modules.
+mod contract_call;
+pub mod errors;
+pub mod events;
+mod governor;
+mod timelock;
+
+pub use contract_call::ContractCall;
+pub use governor::*;
+pub use timelock::*;
@@ -0,0 +1,54 @@
+//! A call of a contract executed by the governance.
+use super::errors::Error;
+use odra::casper_types::bytesrepr::{self, Bytes, FromBytes, ToBytes};
+use odra::casper_types::{RuntimeArgs, U512};
+use odra::prelude::*;
+use odra::CallDef;
+
+/// A [CallDef] along with the called contract, stored until executed by the governance.
+///
+/// The arguments of the call are kept serialized, so a call of any entry point can be stored.
+#[odra::odra_type]
+pub struct ContractCall {
+    /// The called contract.
+    pub target: Address,
+    /// The name of the called entry point.
+    pub entry_point: String,
+    /// The serialized arguments of the call.
+    pub args: Bytes,
+    /// The value attached to the call.
+    pub amount: U512
+}
+
+impl ContractCall {
+    /// Creates a call of the given contract.
+    pub fn new(target: Address, call: CallDef) -> Self {
+        let args = call
+            .args()
+            .to_bytes()
+            .expect("Runtime args should serialize to bytes");
+        Self {
+            target,
+            entry_point: call.entry_point().to_string(),
+            args: Bytes::from(args),
+            amount: call.amount()
+        }
+    }
+
+    /// Executes the call, the result of the call is ignored.
+    pub(crate) fn execute(&self, env: &ContractEnv) {
+        let (args, _) =
+            RuntimeArgs::from_bytes(&self.args).unwrap_or_revert_with(env, Error::InvalidCallArgs);
+        let call = CallDef::new(&self.entry_point, true, args).with_amount(self.amount);
+        env.call_contract::<Ignored>(self.target, call);
+    }
+}
+
+/// The result of a call, whatever the called entry point returns.
+struct Ignored;
+
+impl FromBytes for Ignored {
+    fn from_bytes(_bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        Ok((Self, &[]))
+    }
+}
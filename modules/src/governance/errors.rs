@@ -0,0 +1,41 @@
+//! Errors for Governance modules.
+use odra::prelude::OdraError;
+
+/// Governance-related errors.
+#[odra::odra_error]
+pub enum Error {
+    /// The quorum exceeds 100 percent.
+    InvalidQuorum = 40_000,
+    /// The voting period is zero.
+    InvalidVotingPeriod = 40_001,
+    /// The proposal has no calls.
+    EmptyProposal = 40_002,
+    /// The proposal does not exist.
+    UnknownProposal = 40_003,
+    /// The proposal is not open for voting.
+    ProposalNotActive = 40_004,
+    /// The account has already voted on the proposal.
+    AlreadyVoted = 40_005,
+    /// The proposal has not succeeded.
+    ProposalNotSuccessful = 40_006,
+    /// The voting on the proposal has already started.
+    ProposalNotPending = 40_007,
+    /// The caller is not the proposer.
+    CallerNotTheProposer = 40_008,
+    /// The arguments of a call cannot be deserialized.
+    InvalidCallArgs = 40_009,
+    /// The delay is shorter than the minimum delay.
+    InsufficientDelay = 41_000,
+    /// The operation is already scheduled.
+    OperationAlreadyScheduled = 41_001,
+    /// The operation is not ready to be executed.
+    OperationNotReady = 41_002,
+    /// The operation is not pending.
+    OperationNotPending = 41_003,
+    /// The predecessor of the operation has not been executed.
+    PredecessorNotDone = 41_004,
+    /// The caller is not the timelock itself.
+    CallerNotTheTimelock = 41_005,
+    /// The batch has no calls.
+    EmptyBatch = 41_006
+}
@@ -0,0 +1,111 @@
+//! Events emitted by the Governance modules.
+use super::governor::VoteType;
+use super::timelock::OperationId;
+use odra::casper_types::bytesrepr::Bytes;
+use odra::casper_types::{U256, U512};
+use odra::prelude::*;
+
+/// Emitted when a proposal is created, the calls of the proposal are returned by
+/// [Governor::proposal()](super::Governor::proposal).
+#[odra::event]
+pub struct ProposalCreated {
+    /// The id of the proposal.
+    pub proposal_id: u32,
+    /// The account that created the proposal.
+    pub proposer: Address,
+    /// The description of the proposal.
+    pub description: String,
+    /// The time after which the voting starts.
+    pub vote_start: u64,
+    /// The time at which the voting ends.
+    pub vote_end: u64
+}
+
+/// Emitted when a vote is cast.
+#[odra::event]
+pub struct VoteCast {
+    /// The account that voted.
+    pub voter: Address,
+    /// The id of the proposal.
+    pub proposal_id: u32,
+    /// The direction of the vote.
+    pub support: VoteType,
+    /// The voting power of the voter.
+    pub weight: U256
+}
+
+/// Emitted when a proposal is executed.
+#[odra::event]
+pub struct ProposalExecuted {
+    /// The id of the proposal.
+    pub proposal_id: u32
+}
+
+/// Emitted when a proposal is canceled.
+#[odra::event]
+pub struct ProposalCanceled {
+    /// The id of the proposal.
+    pub proposal_id: u32
+}
+
+/// Emitted when the operation `id` is scheduled, the calls of the operation
+/// are described by the [CallScheduled] events.
+#[odra::event]
+pub struct OperationScheduled {
+    /// The id of the operation.
+    pub id: OperationId,
+    /// The operation which must be executed before.
+    pub predecessor: Option<OperationId>,
+    /// The delay after which the operation can be executed.
+    pub delay: u64
+}
+
+/// Emitted when a call is scheduled as a part of the operation `id`.
+#[odra::event]
+pub struct CallScheduled {
+    /// The id of the operation.
+    pub id: OperationId,
+    /// The index of the call in the operation.
+    pub index: u32,
+    /// The called contract.
+    pub target: Address,
+    /// The name of the called entry point.
+    pub entry_point: String,
+    /// The serialized arguments of the call.
+    pub args: Bytes,
+    /// The value attached to the call.
+    pub amount: U512
+}
+
+/// Emitted when a call is executed as a part of the operation `id`.
+#[odra::event]
+pub struct CallExecuted {
+    /// The id of the operation.
+    pub id: OperationId,
+    /// The index of the call in the operation.
+    pub index: u32,
+    /// The called contract.
+    pub target: Address,
+    /// The name of the called entry point.
+    pub entry_point: String,
+    /// The serialized arguments of the call.
+    pub args: Bytes,
+    /// The value attached to the call.
+    pub amount: U512
+}
+
+/// Emitted when the operation `id` is canceled.
+#[odra::event]
+pub struct OperationCanceled {
+    /// The id of the operation.
+    pub id: OperationId
+}
+
+/// Emitted when the minimum delay of the operations is changed.
+#[odra::event]
+pub struct MinDelayChanged {
+    /// The previous minimum delay.
+    pub old_delay: u64,
+    /// The new minimum delay.
+    pub new_delay: u64
+}
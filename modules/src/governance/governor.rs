@@ -0,0 +1,524 @@
+//! Governor module.
+use super::contract_call::ContractCall;
+use super::errors::Error;
+use super::events::*;
+use odra::casper_types::U256;
+use odra::prelude::*;
+
+/// The denominator of the quorum, the quorum is a percentage of the total supply.
+pub const QUORUM_DENOMINATOR: u32 = 100;
+
+/// The direction of a vote.
+#[odra::odra_type]
+#[derive(Copy)]
+pub enum VoteType {
+    /// The voter is against the proposal.
+    Against = 0,
+    /// The voter supports the proposal.
+    For = 1,
+    /// The voter abstains, the vote counts towards the quorum only.
+    Abstain = 2
+}
+
+/// The state of a proposal.
+#[odra::odra_type]
+#[derive(Copy)]
+pub enum ProposalState {
+    /// The voting has not started yet.
+    Pending = 0,
+    /// The voting is open.
+    Active = 1,
+    /// The proposal has been canceled by the proposer.
+    Canceled = 2,
+    /// The voting has ended without a quorum or a majority of votes for the proposal.
+    Defeated = 3,
+    /// The voting has ended with a quorum and a majority of votes for the proposal.
+    Succeeded = 4,
+    /// The calls of the proposal have been executed.
+    Executed = 5
+}
+
+/// A proposal, the calls executed if the voting succeeds.
+#[odra::odra_type]
+pub struct Proposal {
+    /// The account that created the proposal.
+    pub proposer: Address,
+    /// The calls executed if the proposal succeeds.
+    pub calls: Vec<ContractCall>,
+    /// The description of the proposal.
+    pub description: String,
+    /// The time after which the voting starts, the voting power is taken at this time.
+    pub vote_start: u64,
+    /// The time at which the voting ends.
+    pub vote_end: u64,
+    /// Whether the proposal has been executed.
+    pub executed: bool,
+    /// Whether the proposal has been canceled.
+    pub canceled: bool
+}
+
+/// The votes cast on a proposal.
+#[odra::odra_type]
+#[derive(Default)]
+pub struct ProposalVotes {
+    /// The votes against the proposal.
+    pub against_votes: U256,
+    /// The votes for the proposal.
+    pub for_votes: U256,
+    /// The abstaining votes.
+    pub abstain_votes: U256
+}
+
+/// A token keeping track of the voting power of the accounts, like [Cep18](crate::cep18_token::Cep18).
+#[odra::external_contract]
+pub trait VotesToken {
+    /// Returns the voting power of the account at the given time.
+    fn get_past_votes(&self, account: &Address, timestamp: u64) -> U256;
+    /// Returns the total supply at the given time.
+    fn get_past_total_supply(&self, timestamp: u64) -> U256;
+}
+
+/// This contract module implements on-chain governance based on the voting power of a token.
+///
+/// Any account can create a proposal, a list of calls of other contracts. After the voting delay,
+/// the holders of the delegated votes of the token can vote for, against or abstain during
+/// the voting period. The voting power is taken at the start of the voting.
+///
+/// A proposal succeeds if the votes for and the abstaining votes reach the quorum,
+/// a percentage of the total supply, and there are more votes for the proposal than against it.
+/// Then anyone can [execute()](Governor::execute) the proposal, the calls are made by the governor.
+#[odra::module(
+    events = [ProposalCreated, VoteCast, ProposalExecuted, ProposalCanceled],
+    errors = Error
+)]
+pub struct Governor {
+    token: External<VotesTokenContractRef>,
+    voting_delay: Var<u64>,
+    voting_period: Var<u64>,
+    quorum_numerator: Var<u32>,
+    proposal_ids: Sequence<u32>,
+    proposals: Mapping<u32, Proposal>,
+    votes: Mapping<u32, ProposalVotes>,
+    has_voted: Mapping<(u32, Address), bool>
+}
+
+#[odra::module]
+impl Governor {
+    /// Initializes the governor with the token and the voting parameters.
+    ///
+    /// The voting delay and the voting period are given in milliseconds,
+    /// the quorum is a percentage of the total supply of the token.
+    pub fn init(
+        &mut self,
+        token: Address,
+        voting_delay: u64,
+        voting_period: u64,
+        quorum_numerator: u32
+    ) {
+        if voting_period == 0 {
+            self.env().revert(Error::InvalidVotingPeriod);
+        }
+        if quorum_numerator > QUORUM_DENOMINATOR {
+            self.env().revert(Error::InvalidQuorum);
+        }
+        self.token.set(token);
+        self.voting_delay.set(voting_delay);
+        self.voting_period.set(voting_period);
+        self.quorum_numerator.set(quorum_numerator);
+    }
+
+    /// Creates a proposal executing the given calls, returns the id of the proposal.
+    ///
+    /// Emits a [ProposalCreated] event.
+    pub fn propose(&mut self, calls: Vec<ContractCall>, description: String) -> u32 {
+        if calls.is_empty() {
+            self.env().revert(Error::EmptyProposal);
+        }
+        let proposer = self.env().caller();
+        let vote_start = self.env().get_block_time() + self.voting_delay();
+        let vote_end = vote_start + self.voting_period();
+        let proposal_id = self.proposal_ids.next_value();
+
+        self.proposals.set(
+            &proposal_id,
+            Proposal {
+                proposer,
+                calls,
+                description: description.clone(),
+                vote_start,
+                vote_end,
+                executed: false,
+                canceled: false
+            }
+        );
+        self.env().emit_event(ProposalCreated {
+            proposal_id,
+            proposer,
+            description,
+            vote_start,
+            vote_end
+        });
+        proposal_id
+    }
+
+    /// Casts the caller's vote on an active proposal, returns the voting power of the caller.
+    ///
+    /// Emits a [VoteCast] event.
+    pub fn cast_vote(&mut self, proposal_id: u32, support: VoteType) -> U256 {
+        if self.state(proposal_id) != ProposalState::Active {
+            self.env().revert(Error::ProposalNotActive);
+        }
+        let voter = self.env().caller();
+        if self.has_voted(proposal_id, voter) {
+            self.env().revert(Error::AlreadyVoted);
+        }
+
+        let weight = self
+            .token
+            .get_past_votes(&voter, self.proposal_snapshot(proposal_id));
+        let mut votes = self.proposal_votes(proposal_id);
+        match support {
+            VoteType::Against => votes.against_votes += weight,
+            VoteType::For => votes.for_votes += weight,
+            VoteType::Abstain => votes.abstain_votes += weight
+        }
+        self.votes.set(&proposal_id, votes);
+        self.has_voted.set(&(proposal_id, voter), true);
+
+        self.env().emit_event(VoteCast {
+            voter,
+            proposal_id,
+            support,
+            weight
+        });
+        weight
+    }
+
+    /// Executes the calls of a succeeded proposal.
+    ///
+    /// Emits a [ProposalExecuted] event.
+    pub fn execute(&mut self, proposal_id: u32) {
+        if self.state(proposal_id) != ProposalState::Succeeded {
+            self.env().revert(Error::ProposalNotSuccessful);
+        }
+        let mut proposal = self.proposal(proposal_id);
+        proposal.executed = true;
+        self.proposals.set(&proposal_id, proposal.clone());
+
+        let env = self.env();
+        for call in proposal.calls.iter() {
+            call.execute(&env);
+        }
+        self.env().emit_event(ProposalExecuted { proposal_id });
+    }
+
+    /// Cancels a proposal before the voting starts. Only the proposer can cancel the proposal.
+    ///
+    /// Emits a [ProposalCanceled] event.
+    pub fn cancel(&mut self, proposal_id: u32) {
+        let mut proposal = self.proposal(proposal_id);
+        if proposal.proposer != self.env().caller() {
+            self.env().revert(Error::CallerNotTheProposer);
+        }
+        if self.state(proposal_id) != ProposalState::Pending {
+            self.env().revert(Error::ProposalNotPending);
+        }
+        proposal.canceled = true;
+        self.proposals.set(&proposal_id, proposal);
+        self.env().emit_event(ProposalCanceled { proposal_id });
+    }
+
+    /// Returns the current state of the proposal.
+    pub fn state(&self, proposal_id: u32) -> ProposalState {
+        let proposal = self.proposal(proposal_id);
+        if proposal.executed {
+            return ProposalState::Executed;
+        }
+        if proposal.canceled {
+            return ProposalState::Canceled;
+        }
+
+        let now = self.env().get_block_time();
+        if now <= proposal.vote_start {
+            ProposalState::Pending
+        } else if now <= proposal.vote_end {
+            ProposalState::Active
+        } else if self.quorum_reached(proposal_id) && self.vote_succeeded(proposal_id) {
+            ProposalState::Succeeded
+        } else {
+            ProposalState::Defeated
+        }
+    }
+
+    /// Returns the proposal with the given id.
+    pub fn proposal(&self, proposal_id: u32) -> Proposal {
+        self.proposals
+            .get(&proposal_id)
+            .unwrap_or_revert_with(self, Error::UnknownProposal)
+    }
+
+    /// Returns the time at which the voting power of the voters is taken.
+    pub fn proposal_snapshot(&self, proposal_id: u32) -> u64 {
+        self.proposal(proposal_id).vote_start
+    }
+
+    /// Returns the time at which the voting ends.
+    pub fn proposal_deadline(&self, proposal_id: u32) -> u64 {
+        self.proposal(proposal_id).vote_end
+    }
+
+    /// Returns the votes cast on the proposal.
+    pub fn proposal_votes(&self, proposal_id: u32) -> ProposalVotes {
+        self.votes.get_or_default(&proposal_id)
+    }
+
+    /// Returns true if the account has voted on the proposal.
+    pub fn has_voted(&self, proposal_id: u32, account: Address) -> bool {
+        self.has_voted.get_or_default(&(proposal_id, account))
+    }
+
+    /// Returns the number of votes required to reach the quorum at the given time.
+    pub fn quorum(&self, timestamp: u64) -> U256 {
+        self.token.get_past_total_supply(timestamp) * self.quorum_numerator.get_or_default()
+            / QUORUM_DENOMINATOR
+    }
+
+    /// Returns the delay between the creation of a proposal and the start of the voting.
+    pub fn voting_delay(&self) -> u64 {
+        self.voting_delay.get_or_default()
+    }
+
+    /// Returns the duration of the voting.
+    pub fn voting_period(&self) -> u64 {
+        self.voting_period.get_or_default()
+    }
+}
+
+impl Governor {
+    fn quorum_reached(&self, proposal_id: u32) -> bool {
+        let votes = self.proposal_votes(proposal_id);
+        self.quorum(self.proposal_snapshot(proposal_id)) <= votes.for_votes + votes.abstain_votes
+    }
+
+    fn vote_succeeded(&self, proposal_id: u32) -> bool {
+        let votes = self.proposal_votes(proposal_id);
+        votes.for_votes > votes.against_votes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cep18_token::tests::{setup, TOKEN_TOTAL_SUPPLY, TRANSFER_AMOUNT_1};
+    use crate::cep18_token::Cep18HostRef;
+    use odra::casper_types::{runtime_args, RuntimeArgs};
+    use odra::host::{Deployer, HostEnv, HostRef};
+    use odra::CallDef;
+
+    const VOTING_DELAY: u64 = 1_000;
+    const VOTING_PERIOD: u64 = 10_000;
+    const QUORUM: u32 = 4;
+
+    fn setup_governor() -> (HostEnv, Cep18HostRef, GovernorHostRef) {
+        let mut token = setup(false);
+        let env = token.env().clone();
+        let governor = Governor::deploy(
+            &env,
+            GovernorInitArgs {
+                token: *token.address(),
+                voting_delay: VOTING_DELAY,
+                voting_period: VOTING_PERIOD,
+                quorum_numerator: QUORUM
+            }
+        );
+
+        // the owner and alice hold the votes, the governor holds the treasury
+        let (owner, alice) = (env.get_account(0), env.get_account(1));
+        token.transfer(governor.address(), &U256::from(TRANSFER_AMOUNT_1));
+        token.transfer(&alice, &(U256::from(TOKEN_TOTAL_SUPPLY) / 4));
+        token.delegate(&owner);
+        env.set_caller(alice);
+        token.delegate(&alice);
+        env.set_caller(owner);
+        (env, token, governor)
+    }
+
+    fn transfer_proposal(token: &Cep18HostRef, recipient: Address) -> Vec<ContractCall> {
+        let args = runtime_args! {
+            "recipient" => recipient,
+            "amount" => U256::from(TRANSFER_AMOUNT_1)
+        };
+        vec![ContractCall::new(
+            *token.address(),
+            CallDef::new("transfer", true, args)
+        )]
+    }
+
+    #[test]
+    fn init_validates_parameters() {
+        let token = setup(false);
+        let env = token.env().clone();
+        let args = |voting_period, quorum_numerator| GovernorInitArgs {
+            token: *token.address(),
+            voting_delay: VOTING_DELAY,
+            voting_period,
+            quorum_numerator
+        };
+
+        assert_eq!(
+            Governor::try_deploy(&env, args(VOTING_PERIOD, QUORUM_DENOMINATOR + 1)).err(),
+            Some(Error::InvalidQuorum.into())
+        );
+        assert_eq!(
+            Governor::try_deploy(&env, args(0, QUORUM)).err(),
+            Some(Error::InvalidVotingPeriod.into())
+        );
+    }
+
+    #[test]
+    fn succeeded_proposal_is_executed() {
+        let (env, token, mut governor) = setup_governor();
+        let (owner, alice, bob) = (env.get_account(0), env.get_account(1), env.get_account(2));
+        let calls = transfer_proposal(&token, bob);
+
+        // when a proposal is created
+        let proposal_id = governor.propose(calls.clone(), String::from("Pay bob"));
+        let vote_start = env.block_time() + VOTING_DELAY;
+        assert_eq!(governor.state(proposal_id), ProposalState::Pending);
+        assert_eq!(governor.proposal(proposal_id).calls, calls);
+        assert!(env.emitted_event(
+            &governor,
+            &ProposalCreated {
+                proposal_id,
+                proposer: owner,
+                description: String::from("Pay bob"),
+                vote_start,
+                vote_end: vote_start + VOTING_PERIOD
+            }
+        ));
+
+        // then the voting starts after the voting delay
+        assert_eq!(
+            governor.try_cast_vote(proposal_id, VoteType::For),
+            Err(Error::ProposalNotActive.into())
+        );
+        env.advance_block_time(VOTING_DELAY + 1);
+        assert_eq!(governor.state(proposal_id), ProposalState::Active);
+
+        // when the accounts vote
+        let weight = governor.cast_vote(proposal_id, VoteType::For);
+        assert_eq!(weight, token.get_votes(&owner));
+        assert!(env.emitted_event(
+            &governor,
+            &VoteCast {
+                voter: owner,
+                proposal_id,
+                support: VoteType::For,
+                weight
+            }
+        ));
+        assert_eq!(
+            governor.try_cast_vote(proposal_id, VoteType::Against),
+            Err(Error::AlreadyVoted.into())
+        );
+        env.set_caller(alice);
+        governor.cast_vote(proposal_id, VoteType::Against);
+        env.set_caller(bob);
+        assert_eq!(
+            governor.cast_vote(proposal_id, VoteType::Abstain),
+            U256::zero()
+        );
+
+        let votes = governor.proposal_votes(proposal_id);
+        assert_eq!(votes.for_votes, weight);
+        assert_eq!(votes.against_votes, token.get_votes(&alice));
+        assert!(governor.has_voted(proposal_id, bob));
+
+        // then the proposal cannot be executed until the voting ends
+        assert_eq!(
+            governor.try_execute(proposal_id),
+            Err(Error::ProposalNotSuccessful.into())
+        );
+        env.advance_block_time(VOTING_PERIOD);
+        assert_eq!(governor.state(proposal_id), ProposalState::Succeeded);
+
+        // when the proposal is executed
+        governor.execute(proposal_id);
+
+        // then the calls are made by the governor
+        assert_eq!(token.balance_of(&bob), U256::from(TRANSFER_AMOUNT_1));
+        assert_eq!(token.balance_of(governor.address()), U256::zero());
+        assert_eq!(governor.state(proposal_id), ProposalState::Executed);
+        assert!(env.emitted_event(&governor, &ProposalExecuted { proposal_id }));
+        assert_eq!(
+            governor.try_execute(proposal_id),
+            Err(Error::ProposalNotSuccessful.into())
+        );
+    }
+
+    #[test]
+    fn proposal_without_quorum_or_majority_is_defeated() {
+        let (env, mut token, mut governor) = setup_governor();
+        let (owner, alice, bob) = (env.get_account(0), env.get_account(1), env.get_account(2));
+
+        // bob holds too few votes to reach the quorum alone
+        token.transfer(&bob, &U256::from(TRANSFER_AMOUNT_1));
+        env.set_caller(bob);
+        token.delegate(&bob);
+        env.set_caller(owner);
+
+        let without_quorum = governor.propose(transfer_proposal(&token, bob), String::new());
+        let outvoted = governor.propose(transfer_proposal(&token, bob), String::new());
+        env.advance_block_time(VOTING_DELAY + 1);
+
+        env.set_caller(bob);
+        governor.cast_vote(without_quorum, VoteType::For);
+        governor.cast_vote(outvoted, VoteType::For);
+        env.set_caller(alice);
+        governor.cast_vote(outvoted, VoteType::Against);
+        env.advance_block_time(VOTING_PERIOD);
+
+        assert!(governor.quorum(governor.proposal_snapshot(without_quorum)) > U256::zero());
+        for proposal_id in [without_quorum, outvoted] {
+            assert_eq!(governor.state(proposal_id), ProposalState::Defeated);
+            assert_eq!(
+                governor.try_execute(proposal_id),
+                Err(Error::ProposalNotSuccessful.into())
+            );
+        }
+    }
+
+    #[test]
+    fn proposer_cancels_pending_proposal() {
+        let (env, token, mut governor) = setup_governor();
+        let (owner, bob) = (env.get_account(0), env.get_account(2));
+        let proposal_id = governor.propose(transfer_proposal(&token, bob), String::new());
+
+        // only the proposer can cancel the proposal
+        env.set_caller(bob);
+        assert_eq!(
+            governor.try_cancel(proposal_id),
+            Err(Error::CallerNotTheProposer.into())
+        );
+        env.set_caller(owner);
+        governor.cancel(proposal_id);
+        assert_eq!(governor.state(proposal_id), ProposalState::Canceled);
+        assert!(env.emitted_event(&governor, &ProposalCanceled { proposal_id }));
+
+        // once the voting starts the proposal cannot be canceled
+        let proposal_id = governor.propose(transfer_proposal(&token, bob), String::new());
+        env.advance_block_time(VOTING_DELAY + 1);
+        assert_eq!(
+            governor.try_cancel(proposal_id),
+            Err(Error::ProposalNotPending.into())
+        );
+        assert_eq!(
+            governor.try_propose(vec![], String::new()),
+            Err(Error::EmptyProposal.into())
+        );
+        assert_eq!(
+            governor.try_state(proposal_id + 1),
+            Err(Error::UnknownProposal.into())
+        );
+    }
+}
@@ -0,0 +1,646 @@
+//! Timelock module.
+use super::contract_call::ContractCall;
+use super::errors::Error;
+use super::events::*;
+use crate::access::{role_id, AccessControl, Role, DEFAULT_ADMIN_ROLE};
+use odra::casper_types::bytesrepr::ToBytes;
+use odra::prelude::*;
+
+/// The identifier of an operation, the hash of its calls, predecessor and salt.
+pub type OperationId = [u8; 32];
+
+/// The role allowed to schedule operations.
+pub const PROPOSER_ROLE: Role = role_id("PROPOSER");
+/// The role allowed to execute ready operations.
+pub const EXECUTOR_ROLE: Role = role_id("EXECUTOR");
+/// The role allowed to cancel pending operations.
+pub const CANCELLER_ROLE: Role = role_id("CANCELLER");
+
+/// The timestamp of an executed operation.
+const DONE_TIMESTAMP: u64 = 1;
+
+/// This contract module delays the execution of operations, so the affected parties
+/// can react before an operation takes effect.
+///
+/// An operation is a call or a batch of calls of other contracts, identified by the hash
+/// of the calls, the predecessor and the salt. A proposer schedules an operation with
+/// a delay not shorter than the minimum delay. Once the delay passes, an executor can execute
+/// the operation, until then a canceller can cancel it.
+///
+/// The roles are managed by [AccessControl]. The timelock administers itself,
+/// so the roles and the minimum delay can be changed by operations calling the timelock.
+#[odra::module(
+    events = [
+        OperationScheduled,
+        CallScheduled,
+        CallExecuted,
+        OperationCanceled,
+        MinDelayChanged
+    ],
+    errors = Error
+)]
+pub struct Timelock {
+    access_control: SubModule<AccessControl>,
+    min_delay: Var<u64>,
+    timestamps: Mapping<OperationId, u64>
+}
+
+#[odra::module]
+impl Timelock {
+    /// Initializes the timelock with the minimum delay and the accounts granted the roles.
+    ///
+    /// The proposers are granted the canceller role as well. The optional admin can manage the roles
+    /// without a delay, it is supposed to renounce the admin role once the timelock is set up.
+    pub fn init(
+        &mut self,
+        min_delay: u64,
+        proposers: Vec<Address>,
+        executors: Vec<Address>,
+        admin: Option<Address>
+    ) {
+        let timelock = self.env().self_address();
+        self.access_control
+            .unchecked_grant_role(&DEFAULT_ADMIN_ROLE, &timelock);
+        if let Some(admin) = admin {
+            self.access_control
+                .unchecked_grant_role(&DEFAULT_ADMIN_ROLE, &admin);
+        }
+        for proposer in proposers.iter() {
+            self.access_control
+                .unchecked_grant_role(&PROPOSER_ROLE, proposer);
+            self.access_control
+                .unchecked_grant_role(&CANCELLER_ROLE, proposer);
+        }
+        for executor in executors.iter() {
+            self.access_control
+                .unchecked_grant_role(&EXECUTOR_ROLE, executor);
+        }
+
+        self.min_delay.set(min_delay);
+        self.env().emit_event(MinDelayChanged {
+            old_delay: 0,
+            new_delay: min_delay
+        });
+    }
+
+    /// Schedules an operation containing a single call.
+    ///
+    /// Emits an [OperationScheduled] and a [CallScheduled] event.
+    #[odra(only_role(access_control, "PROPOSER"))]
+    pub fn schedule(
+        &mut self,
+        call: ContractCall,
+        predecessor: Option<OperationId>,
+        salt: [u8; 32],
+        delay: u64
+    ) {
+        let id = self.hash_operation(call.clone(), predecessor, salt);
+        self.schedule_operation(&id, predecessor, delay);
+        self.emit_scheduled(&id, 0, call);
+    }
+
+    /// Schedules an operation containing a batch of calls.
+    ///
+    /// Emits an [OperationScheduled] event and a [CallScheduled] event for each call of the batch.
+    #[odra(only_role(access_control, "PROPOSER"))]
+    pub fn schedule_batch(
+        &mut self,
+        calls: Vec<ContractCall>,
+        predecessor: Option<OperationId>,
+        salt: [u8; 32],
+        delay: u64
+    ) {
+        if calls.is_empty() {
+            self.env().revert(Error::EmptyBatch);
+        }
+        let id = self.hash_operation_batch(calls.clone(), predecessor, salt);
+        self.schedule_operation(&id, predecessor, delay);
+        for (index, call) in calls.into_iter().enumerate() {
+            self.emit_scheduled(&id, index as u32, call);
+        }
+    }
+
+    /// Cancels a pending operation.
+    ///
+    /// Emits an [OperationCanceled] event.
+    #[odra(only_role(access_control, "CANCELLER"))]
+    pub fn cancel(&mut self, id: &OperationId) {
+        if !self.is_operation_pending(id) {
+            self.env().revert(Error::OperationNotPending);
+        }
+        self.timestamps.set(id, 0);
+        self.env().emit_event(OperationCanceled { id: *id });
+    }
+
+    /// Executes a ready operation containing a single call.
+    ///
+    /// Emits a [CallExecuted] event.
+    #[odra(only_role(access_control, "EXECUTOR"))]
+    pub fn execute(
+        &mut self,
+        call: ContractCall,
+        predecessor: Option<OperationId>,
+        salt: [u8; 32]
+    ) {
+        let id = self.hash_operation(call.clone(), predecessor, salt);
+        self.before_call(&id, predecessor);
+        self.execute_call(&id, 0, call);
+        self.after_call(&id);
+    }
+
+    /// Executes a ready operation containing a batch of calls.
+    ///
+    /// Emits a [CallExecuted] event for each call of the batch.
+    #[odra(only_role(access_control, "EXECUTOR"))]
+    pub fn execute_batch(
+        &mut self,
+        calls: Vec<ContractCall>,
+        predecessor: Option<OperationId>,
+        salt: [u8; 32]
+    ) {
+        let id = self.hash_operation_batch(calls.clone(), predecessor, salt);
+        self.before_call(&id, predecessor);
+        for (index, call) in calls.into_iter().enumerate() {
+            self.execute_call(&id, index as u32, call);
+        }
+        self.after_call(&id);
+    }
+
+    /// Changes the minimum delay of the operations.
+    ///
+    /// Can be called by the timelock only, so the change must be scheduled as an operation.
+    ///
+    /// Emits a [MinDelayChanged] event.
+    pub fn update_delay(&mut self, new_delay: u64) {
+        if self.env().caller() != self.env().self_address() {
+            self.env().revert(Error::CallerNotTheTimelock);
+        }
+        self.env().emit_event(MinDelayChanged {
+            old_delay: self.get_min_delay(),
+            new_delay
+        });
+        self.min_delay.set(new_delay);
+    }
+
+    /// Returns the id of an operation containing a single call.
+    pub fn hash_operation(
+        &self,
+        call: ContractCall,
+        predecessor: Option<OperationId>,
+        salt: [u8; 32]
+    ) -> OperationId {
+        self.hash((call, predecessor, salt))
+    }
+
+    /// Returns the id of an operation containing a batch of calls.
+    pub fn hash_operation_batch(
+        &self,
+        calls: Vec<ContractCall>,
+        predecessor: Option<OperationId>,
+        salt: [u8; 32]
+    ) -> OperationId {
+        self.hash((calls, predecessor, salt))
+    }
+
+    /// Returns true if the operation is scheduled, pending or executed.
+    pub fn is_operation(&self, id: &OperationId) -> bool {
+        self.get_timestamp(id) > 0
+    }
+
+    /// Returns true if the operation is scheduled and not executed yet.
+    pub fn is_operation_pending(&self, id: &OperationId) -> bool {
+        self.get_timestamp(id) > DONE_TIMESTAMP
+    }
+
+    /// Returns true if the operation is pending and its delay has passed.
+    pub fn is_operation_ready(&self, id: &OperationId) -> bool {
+        self.is_operation_pending(id) && self.get_timestamp(id) <= self.env().get_block_time()
+    }
+
+    /// Returns true if the operation has been executed.
+    pub fn is_operation_done(&self, id: &OperationId) -> bool {
+        self.get_timestamp(id) == DONE_TIMESTAMP
+    }
+
+    /// Returns the time at which the operation becomes ready, 0 for unknown operations
+    /// and 1 for executed operations.
+    pub fn get_timestamp(&self, id: &OperationId) -> u64 {
+        self.timestamps.get_or_default(id)
+    }
+
+    /// Returns the minimum delay of the operations.
+    pub fn get_min_delay(&self) -> u64 {
+        self.min_delay.get_or_default()
+    }
+
+    delegate! {
+        to self.access_control {
+            fn has_role(&self, role: &Role, address: &Address) -> bool;
+            fn get_role_admin(&self, role: &Role) -> Role;
+            fn grant_role(&mut self, role: &Role, address: &Address);
+            fn revoke_role(&mut self, role: &Role, address: &Address);
+            fn renounce_role(&mut self, role: &Role, address: &Address);
+        }
+    }
+}
+
+impl Timelock {
+    fn hash<T: ToBytes>(&self, operation: T) -> OperationId {
+        let bytes = operation
+            .to_bytes()
+            .map_err(ExecutionError::from)
+            .unwrap_or_revert(self);
+        self.env().hash(bytes)
+    }
+
+    fn schedule_operation(
+        &mut self,
+        id: &OperationId,
+        predecessor: Option<OperationId>,
+        delay: u64
+    ) {
+        if self.is_operation(id) {
+            self.env().revert(Error::OperationAlreadyScheduled);
+        }
+        if delay < self.get_min_delay() {
+            self.env().revert(Error::InsufficientDelay);
+        }
+        self.timestamps.set(id, self.env().get_block_time() + delay);
+        self.env().emit_event(OperationScheduled {
+            id: *id,
+            predecessor,
+            delay
+        });
+    }
+
+    fn emit_scheduled(&self, id: &OperationId, index: u32, call: ContractCall) {
+        self.env().emit_event(CallScheduled {
+            id: *id,
+            index,
+            target: call.target,
+            entry_point: call.entry_point,
+            args: call.args,
+            amount: call.amount
+        });
+    }
+
+    fn before_call(&self, id: &OperationId, predecessor: Option<OperationId>) {
+        if !self.is_operation_ready(id) {
+            self.env().revert(Error::OperationNotReady);
+        }
+        if let Some(predecessor) = predecessor {
+            if !self.is_operation_done(&predecessor) {
+                self.env().revert(Error::PredecessorNotDone);
+            }
+        }
+    }
+
+    fn execute_call(&self, id: &OperationId, index: u32, call: ContractCall) {
+        call.execute(&self.env());
+        self.env().emit_event(CallExecuted {
+            id: *id,
+            index,
+            target: call.target,
+            entry_point: call.entry_point,
+            args: call.args,
+            amount: call.amount
+        });
+    }
+
+    fn after_call(&mut self, id: &OperationId) {
+        // The operation could have been canceled or executed by a reentrant call.
+        if !self.is_operation_ready(id) {
+            self.env().revert(Error::OperationNotReady);
+        }
+        self.timestamps.set(id, DONE_TIMESTAMP);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::access::errors::Error as AccessError;
+    use crate::cep18_token::tests::{setup, TRANSFER_AMOUNT_1};
+    use crate::cep18_token::Cep18HostRef;
+    use odra::casper_types::{runtime_args, RuntimeArgs, U256};
+    use odra::host::{Deployer, HostEnv, HostRef};
+    use odra::CallDef;
+
+    const MIN_DELAY: u64 = 1_000;
+    const SALT: [u8; 32] = [7u8; 32];
+
+    struct TestEnv {
+        env: HostEnv,
+        token: Cep18HostRef,
+        timelock: TimelockHostRef,
+        proposer: Address,
+        executor: Address,
+        recipient: Address
+    }
+
+    fn setup_timelock() -> TestEnv {
+        let mut token = setup(false);
+        let env = token.env().clone();
+        let (proposer, executor, recipient) =
+            (env.get_account(1), env.get_account(2), env.get_account(3));
+        let timelock = Timelock::deploy(
+            &env,
+            TimelockInitArgs {
+                min_delay: MIN_DELAY,
+                proposers: vec![proposer],
+                executors: vec![executor],
+                admin: None
+            }
+        );
+        token.transfer(timelock.address(), &U256::from(TRANSFER_AMOUNT_1 * 3));
+        env.advance_block_time(MIN_DELAY);
+
+        TestEnv {
+            env,
+            token,
+            timelock,
+            proposer,
+            executor,
+            recipient
+        }
+    }
+
+    fn transfer_call(token: &Cep18HostRef, recipient: Address) -> ContractCall {
+        let args = runtime_args! {
+            "recipient" => recipient,
+            "amount" => U256::from(TRANSFER_AMOUNT_1)
+        };
+        ContractCall::new(*token.address(), CallDef::new("transfer", true, args))
+    }
+
+    #[test]
+    fn init_grants_roles() {
+        let TestEnv {
+            env,
+            timelock,
+            proposer,
+            executor,
+            ..
+        } = setup_timelock();
+
+        assert!(timelock.has_role(&DEFAULT_ADMIN_ROLE, timelock.address()));
+        assert!(!timelock.has_role(&DEFAULT_ADMIN_ROLE, &env.get_account(0)));
+        assert!(timelock.has_role(&PROPOSER_ROLE, &proposer));
+        assert!(timelock.has_role(&CANCELLER_ROLE, &proposer));
+        assert!(!timelock.has_role(&EXECUTOR_ROLE, &proposer));
+        assert!(timelock.has_role(&EXECUTOR_ROLE, &executor));
+        assert_eq!(timelock.get_min_delay(), MIN_DELAY);
+        assert!(env.emitted_event(
+            &timelock,
+            &MinDelayChanged {
+                old_delay: 0,
+                new_delay: MIN_DELAY
+            }
+        ));
+    }
+
+    #[test]
+    fn scheduled_call_is_executed_after_delay() {
+        let TestEnv {
+            env,
+            token,
+            mut timelock,
+            proposer,
+            executor,
+            recipient
+        } = setup_timelock();
+        let call = transfer_call(&token, recipient);
+        let id = timelock.hash_operation(call.clone(), None, SALT);
+
+        // only a proposer can schedule an operation, with a delay not shorter than the minimum
+        assert_eq!(
+            timelock.try_schedule(call.clone(), None, SALT, MIN_DELAY),
+            Err(AccessError::MissingRole.into())
+        );
+        env.set_caller(proposer);
+        assert_eq!(
+            timelock.try_schedule(call.clone(), None, SALT, MIN_DELAY - 1),
+            Err(Error::InsufficientDelay.into())
+        );
+        timelock.schedule(call.clone(), None, SALT, MIN_DELAY);
+        assert_eq!(
+            timelock.try_schedule(call.clone(), None, SALT, MIN_DELAY),
+            Err(Error::OperationAlreadyScheduled.into())
+        );
+        assert!(env.emitted_event(
+            &timelock,
+            &OperationScheduled {
+                id,
+                predecessor: None,
+                delay: MIN_DELAY
+            }
+        ));
+        assert!(env.emitted_event(
+            &timelock,
+            &CallScheduled {
+                id,
+                index: 0,
+                target: call.target,
+                entry_point: call.entry_point.clone(),
+                args: call.args.clone(),
+                amount: call.amount
+            }
+        ));
+        assert!(timelock.is_operation_pending(&id));
+        assert_eq!(timelock.get_timestamp(&id), env.block_time() + MIN_DELAY);
+
+        // only an executor can execute the operation, once the delay passes
+        env.set_caller(executor);
+        assert_eq!(
+            timelock.try_execute(call.clone(), None, SALT),
+            Err(Error::OperationNotReady.into())
+        );
+        env.advance_block_time(MIN_DELAY);
+        assert!(timelock.is_operation_ready(&id));
+        env.set_caller(proposer);
+        assert_eq!(
+            timelock.try_execute(call.clone(), None, SALT),
+            Err(AccessError::MissingRole.into())
+        );
+        env.set_caller(executor);
+        timelock.execute(call.clone(), None, SALT);
+
+        assert_eq!(token.balance_of(&recipient), U256::from(TRANSFER_AMOUNT_1));
+        assert!(timelock.is_operation_done(&id));
+        assert!(!timelock.is_operation_pending(&id));
+        assert!(env.emitted_event(
+            &timelock,
+            &CallExecuted {
+                id,
+                index: 0,
+                target: call.target,
+                entry_point: call.entry_point.clone(),
+                args: call.args.clone(),
+                amount: call.amount
+            }
+        ));
+        assert_eq!(
+            timelock.try_execute(call, None, SALT),
+            Err(Error::OperationNotReady.into())
+        );
+    }
+
+    #[test]
+    fn scheduled_batch_is_executed_after_predecessor() {
+        let TestEnv {
+            env,
+            token,
+            mut timelock,
+            proposer,
+            executor,
+            recipient
+        } = setup_timelock();
+        let call = transfer_call(&token, recipient);
+        let calls = vec![call.clone(), call.clone()];
+        let predecessor = timelock.hash_operation(call.clone(), None, SALT);
+        let id = timelock.hash_operation_batch(calls.clone(), Some(predecessor), SALT);
+        assert_ne!(id, predecessor);
+
+        env.set_caller(proposer);
+        assert_eq!(
+            timelock.try_schedule_batch(vec![], None, SALT, MIN_DELAY),
+            Err(Error::EmptyBatch.into())
+        );
+        timelock.schedule_batch(calls.clone(), Some(predecessor), SALT, MIN_DELAY);
+        assert!(env.emitted_event(
+            &timelock,
+            &OperationScheduled {
+                id,
+                predecessor: Some(predecessor),
+                delay: MIN_DELAY
+            }
+        ));
+        assert!(env.emitted_event(
+            &timelock,
+            &CallScheduled {
+                id,
+                index: 1,
+                target: call.target,
+                entry_point: call.entry_point.clone(),
+                args: call.args.clone(),
+                amount: call.amount
+            }
+        ));
+        env.advance_block_time(MIN_DELAY);
+
+        // the batch cannot be executed before its predecessor
+        env.set_caller(executor);
+        assert_eq!(
+            timelock.try_execute_batch(calls.clone(), Some(predecessor), SALT),
+            Err(Error::PredecessorNotDone.into())
+        );
+        env.set_caller(proposer);
+        timelock.schedule(call.clone(), None, SALT, MIN_DELAY);
+        env.advance_block_time(MIN_DELAY);
+        env.set_caller(executor);
+        timelock.execute(call, None, SALT);
+        timelock.execute_batch(calls, Some(predecessor), SALT);
+
+        assert_eq!(
+            token.balance_of(&recipient),
+            U256::from(TRANSFER_AMOUNT_1 * 3)
+        );
+        assert!(timelock.is_operation_done(&id));
+    }
+
+    #[test]
+    fn canceller_cancels_pending_operation() {
+        let TestEnv {
+            env,
+            token,
+            mut timelock,
+            proposer,
+            executor,
+            recipient
+        } = setup_timelock();
+        let call = transfer_call(&token, recipient);
+        let id = timelock.hash_operation(call.clone(), None, SALT);
+
+        env.set_caller(proposer);
+        assert_eq!(
+            timelock.try_cancel(&id),
+            Err(Error::OperationNotPending.into())
+        );
+        timelock.schedule(call.clone(), None, SALT, MIN_DELAY);
+
+        env.set_caller(executor);
+        assert_eq!(
+            timelock.try_cancel(&id),
+            Err(AccessError::MissingRole.into())
+        );
+        env.set_caller(proposer);
+        timelock.cancel(&id);
+
+        assert!(!timelock.is_operation(&id));
+        assert!(env.emitted_event(&timelock, &OperationCanceled { id }));
+        env.advance_block_time(MIN_DELAY);
+        env.set_caller(executor);
+        assert_eq!(
+            timelock.try_execute(call, None, SALT),
+            Err(Error::OperationNotReady.into())
+        );
+    }
+
+    #[test]
+    fn timelock_administers_itself() {
+        let TestEnv {
+            env,
+            mut timelock,
+            proposer,
+            executor,
+            recipient,
+            ..
+        } = setup_timelock();
+        let new_delay = MIN_DELAY * 2;
+
+        // the delay and the roles cannot be changed directly
+        assert_eq!(
+            timelock.try_update_delay(new_delay),
+            Err(Error::CallerNotTheTimelock.into())
+        );
+        assert_eq!(
+            timelock.try_grant_role(&EXECUTOR_ROLE, &recipient),
+            Err(AccessError::MissingRole.into())
+        );
+
+        // the changes are scheduled as calls of the timelock
+        let calls = vec![
+            ContractCall::new(
+                *timelock.address(),
+                CallDef::new(
+                    "update_delay",
+                    true,
+                    runtime_args! { "new_delay" => new_delay }
+                )
+            ),
+            ContractCall::new(
+                *timelock.address(),
+                CallDef::new(
+                    "grant_role",
+                    true,
+                    runtime_args! { "role" => EXECUTOR_ROLE, "address" => recipient }
+                )
+            ),
+        ];
+        env.set_caller(proposer);
+        timelock.schedule_batch(calls.clone(), None, SALT, MIN_DELAY);
+        env.advance_block_time(MIN_DELAY);
+        env.set_caller(executor);
+        timelock.execute_batch(calls, None, SALT);
+
+        assert_eq!(timelock.get_min_delay(), new_delay);
+        assert!(timelock.has_role(&EXECUTOR_ROLE, &recipient));
+        assert!(env.emitted_event(
+            &timelock,
+            &MinDelayChanged {
+                old_delay: MIN_DELAY,
+                new_delay
+            }
+        ));
+    }
+}
@@ -5,8 +5,10 @@
 extern crate alloc;
 
 pub mod access;
+pub mod amm;
 pub mod cep18;
 pub mod cep18_token;
+pub mod cep18_vault;
 pub mod cep78;
 pub mod erc1155;
 pub mod erc1155_receiver;
@@ -15,5 +17,10 @@ pub mod erc20;
 pub mod erc721;
 pub mod erc721_receiver;
 pub mod erc721_token;
+pub mod governance;
+pub mod merkle_distributor;
+pub mod multisig;
 pub mod security;
+pub mod staking_rewards;
+pub mod vesting_wallet;
 pub mod wrapped_native;
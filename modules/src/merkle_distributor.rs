@@ -0,0 +1,412 @@
+//! Merkle distributor implementation
+use crate::access::Ownable;
+use crate::cep18_token::Cep18ContractRef;
+use crate::merkle_distributor::errors::Error;
+use crate::merkle_distributor::events::{Claimed, MerkleRootUpdated, Swept};
+use odra::casper_types::bytesrepr::ToBytes;
+use odra::casper_types::U256;
+use odra::prelude::*;
+use odra::uints::{ToU256, ToU512};
+use odra::ContractRef;
+
+/// The MerkleDistributor module.
+///
+/// Distributes tokens of a [Cep18](crate::cep18_token::Cep18) contract, or native tokens if no token is set,
+/// to the accounts listed in a Merkle tree. Only the root of the tree is stored, an account claims its amount
+/// proving the leaf `(index, account, amount)` belongs to the tree. Anyone can submit a claim,
+/// the tokens are always transferred to the account.
+///
+/// A leaf is the hash of the hash of the serialized `(index, account, amount)` tuple, hashing twice prevents
+/// a leaf from being mistaken for an inner node. The inner nodes are hashes of the concatenated children,
+/// sorted in ascending order, so the proofs do not need to record the side of the siblings.
+///
+/// The claimed indexes are tracked in a bitmap of 256-bit words. The owner can update the root,
+/// the new tree must not reuse the claimed indexes. After the expiry time, the claims are closed
+/// and the owner can sweep the remaining tokens.
+#[odra::module(events = [Claimed, MerkleRootUpdated, Swept], errors = Error)]
+pub struct MerkleDistributor {
+    ownable: SubModule<Ownable>,
+    token: Var<Address>,
+    merkle_root: Var<[u8; 32]>,
+    expiry: Var<u64>,
+    claimed_bitmap: Mapping<u64, U256>
+}
+
+/// The MerkleDistributor module implementation.
+#[odra::module]
+impl MerkleDistributor {
+    /// Initializes the distributor of the Cep18 token, or native tokens if `token` is `None`,
+    /// with the root of the Merkle tree and the expiry time of the claims.
+    ///
+    /// The caller becomes the owner.
+    pub fn init(&mut self, token: Option<Address>, merkle_root: [u8; 32], expiry: u64) {
+        self.ownable.init();
+        if let Some(token) = token {
+            self.token.set(token);
+        }
+        self.expiry.set(expiry);
+        self.raw_set_merkle_root(merkle_root);
+    }
+
+    /// Accepts native tokens transferred to the distributor.
+    #[odra(receive)]
+    pub fn deposit(&mut self) {}
+
+    /// Transfers the amount to the account, if the leaf `(index, account, amount)` is proven
+    /// to belong to the Merkle tree.
+    pub fn claim(&mut self, index: u64, account: &Address, amount: &U256, proof: Vec<[u8; 32]>) {
+        if self.is_expired() {
+            self.env().revert(Error::ClaimExpired);
+        }
+        if self.is_claimed(index) {
+            self.env().revert(Error::AlreadyClaimed);
+        }
+        let leaf = self.leaf(index, account, amount);
+        if !self.verify(&proof, leaf) {
+            self.env().revert(Error::InvalidProof);
+        }
+
+        self.set_claimed(index);
+        self.transfer(account, amount);
+        self.env().emit_event(Claimed {
+            index,
+            account: *account,
+            amount: *amount
+        });
+    }
+
+    /// Replaces the root of the Merkle tree.
+    #[odra(only_owner = ownable)]
+    pub fn set_merkle_root(&mut self, merkle_root: [u8; 32]) {
+        self.raw_set_merkle_root(merkle_root);
+    }
+
+    /// Transfers the tokens left after the expiry time to the recipient.
+    #[odra(only_owner = ownable)]
+    pub fn sweep(&mut self, to: &Address) {
+        if !self.is_expired() {
+            self.env().revert(Error::NotExpired);
+        }
+        let amount = match self.token() {
+            Some(token) => self.token_ref(token).balance_of(&self.env().self_address()),
+            None => self.env().self_balance().to_u256().unwrap_or_revert(self)
+        };
+        self.transfer(to, &amount);
+        self.env().emit_event(Swept { to: *to, amount });
+    }
+
+    /// Returns true if the index has been claimed.
+    pub fn is_claimed(&self, index: u64) -> bool {
+        let word = self.claimed_bitmap.get_or_default(&(index / 256));
+        word.bit((index % 256) as usize)
+    }
+
+    /// Returns the distributed token, `None` if the distributor holds native tokens.
+    pub fn token(&self) -> Option<Address> {
+        self.token.get()
+    }
+
+    /// Returns the root of the Merkle tree.
+    pub fn merkle_root(&self) -> [u8; 32] {
+        self.merkle_root.get_or_default()
+    }
+
+    /// Returns the time after which the claims are closed.
+    pub fn expiry(&self) -> u64 {
+        self.expiry.get_or_default()
+    }
+
+    delegate! {
+        to self.ownable {
+            fn get_owner(&self) -> Address;
+            fn transfer_ownership(&mut self, new_owner: &Address);
+        }
+    }
+}
+
+impl MerkleDistributor {
+    fn is_expired(&self) -> bool {
+        self.env().get_block_time() > self.expiry()
+    }
+
+    fn set_claimed(&mut self, index: u64) {
+        let key = index / 256;
+        let word = self.claimed_bitmap.get_or_default(&key);
+        self.claimed_bitmap
+            .set(&key, word | (U256::one() << (index % 256)));
+    }
+
+    fn leaf(&self, index: u64, account: &Address, amount: &U256) -> [u8; 32] {
+        let bytes = (index, *account, *amount).to_bytes().unwrap_or_revert(self);
+        self.env().hash(self.env().hash(bytes))
+    }
+
+    fn verify(&self, proof: &[[u8; 32]], leaf: [u8; 32]) -> bool {
+        let root = proof.iter().fold(leaf, |node, sibling| {
+            let (left, right) = if node <= *sibling {
+                (node, *sibling)
+            } else {
+                (*sibling, node)
+            };
+            self.env().hash([left, right].concat())
+        });
+        root == self.merkle_root()
+    }
+
+    fn transfer(&self, to: &Address, amount: &U256) {
+        match self.token() {
+            Some(token) => self.token_ref(token).transfer(to, amount),
+            None => self.env().transfer_tokens(to, &amount.to_u512())
+        }
+    }
+
+    fn token_ref(&self, token: Address) -> Cep18ContractRef {
+        Cep18ContractRef::new(self.env(), token)
+    }
+
+    fn raw_set_merkle_root(&mut self, merkle_root: [u8; 32]) {
+        self.merkle_root.set(merkle_root);
+        self.env().emit_event(MerkleRootUpdated { merkle_root });
+    }
+}
+
+/// MerkleDistributor Events
+pub mod events {
+    use odra::casper_event_standard;
+    use odra::casper_types::U256;
+    use odra::prelude::*;
+
+    /// Event emitted when an amount is claimed.
+    #[odra::event]
+    pub struct Claimed {
+        /// The index of the claimed leaf.
+        pub index: u64,
+        /// The account the tokens are transferred to.
+        pub account: Address,
+        /// The amount of tokens claimed.
+        pub amount: U256
+    }
+
+    /// Event emitted when the root of the Merkle tree is set.
+    #[odra::event]
+    pub struct MerkleRootUpdated {
+        /// The new root.
+        pub merkle_root: [u8; 32]
+    }
+
+    /// Event emitted when the remaining tokens are swept.
+    #[odra::event]
+    pub struct Swept {
+        /// The recipient of the tokens.
+        pub to: Address,
+        /// The amount of tokens swept.
+        pub amount: U256
+    }
+}
+
+/// MerkleDistributor Errors
+pub mod errors {
+    use odra::prelude::*;
+
+    /// MerkleDistributor errors
+    #[odra::odra_error]
+    pub enum Error {
+        /// The index has already been claimed
+        AlreadyClaimed = 56_000,
+        /// The proof does not match the Merkle root
+        InvalidProof = 56_001,
+        /// The claims have expired
+        ClaimExpired = 56_002,
+        /// The claims have not expired yet
+        NotExpired = 56_003
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::access::errors::Error as AccessError;
+    use crate::cep18_token::tests::setup;
+    use odra::casper_types::crypto::blake2b;
+    use odra::casper_types::U512;
+    use odra::host::{Deployer, HostEnv, HostRef};
+
+    const EXPIRY: u64 = 100_000;
+    const INDEXES: [u64; 5] = [0, 1, 255, 256, 1_000];
+
+    struct Tree {
+        root: [u8; 32],
+        proofs: Vec<Vec<[u8; 32]>>
+    }
+
+    fn leaf(index: u64, account: Address, amount: U256) -> [u8; 32] {
+        blake2b(blake2b((index, account, amount).to_bytes().unwrap()))
+    }
+
+    // Builds a tree of the leaves, promoting the odd nodes to the next level.
+    fn tree(leaves: Vec<[u8; 32]>) -> Tree {
+        let mut proofs = vec![vec![]; leaves.len()];
+        let mut positions: Vec<usize> = (0..leaves.len()).collect();
+        let mut level = leaves;
+        while level.len() > 1 {
+            for (proof, position) in proofs.iter_mut().zip(positions.iter_mut()) {
+                if let Some(sibling) = level.get(*position ^ 1) {
+                    proof.push(*sibling);
+                }
+                *position /= 2;
+            }
+            level = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => {
+                        let (left, right) = if left <= right {
+                            (left, right)
+                        } else {
+                            (right, left)
+                        };
+                        blake2b([*left, *right].concat())
+                    }
+                    [node] => *node,
+                    _ => unreachable!()
+                })
+                .collect();
+        }
+        Tree {
+            root: level[0],
+            proofs
+        }
+    }
+
+    // Allocates 1_000 tokens times the account number to the accounts 1 to 5.
+    fn allocations(env: &HostEnv) -> Vec<(u64, Address, U256)> {
+        INDEXES
+            .iter()
+            .zip(1..)
+            .map(|(index, i)| (*index, env.get_account(i), U256::from(1_000 * i)))
+            .collect()
+    }
+
+    fn allocation_tree(env: &HostEnv) -> Tree {
+        tree(
+            allocations(env)
+                .into_iter()
+                .map(|(index, account, amount)| leaf(index, account, amount))
+                .collect()
+        )
+    }
+
+    #[test]
+    fn cep18_tokens_are_claimed_with_proof() {
+        let mut token = setup(false);
+        let env = token.env().clone();
+        let tree = allocation_tree(&env);
+        let mut distributor = MerkleDistributor::deploy(
+            &env,
+            MerkleDistributorInitArgs {
+                token: Some(*token.address()),
+                merkle_root: tree.root,
+                expiry: EXPIRY
+            }
+        );
+        token.transfer(distributor.address(), &15_000.into());
+        assert_eq!(distributor.token(), Some(*token.address()));
+        assert_eq!(distributor.merkle_root(), tree.root);
+
+        for ((index, account, amount), proof) in allocations(&env).into_iter().zip(tree.proofs) {
+            // the proof must match the leaf
+            assert_eq!(
+                distributor.try_claim(index, &account, &(amount + 1), proof.clone()),
+                Err(Error::InvalidProof.into())
+            );
+            assert!(!distributor.is_claimed(index));
+
+            // the account claims itself
+            env.set_caller(account);
+            distributor.claim(index, &account, &amount, proof.clone());
+            assert!(distributor.is_claimed(index));
+            assert_eq!(token.balance_of(&account), amount);
+            assert!(env.emitted_event(
+                &distributor,
+                &Claimed {
+                    index,
+                    account,
+                    amount
+                }
+            ));
+            assert_eq!(
+                distributor.try_claim(index, &account, &amount, proof),
+                Err(Error::AlreadyClaimed.into())
+            );
+        }
+        assert_eq!(token.balance_of(distributor.address()), U256::zero());
+
+        // the indexes share the words of the bitmap
+        assert!(!distributor.is_claimed(2));
+        assert!(!distributor.is_claimed(257));
+    }
+
+    #[test]
+    fn native_tokens_are_swept_after_expiry() {
+        let env = odra_test::env();
+        let (owner, recipient) = (env.get_account(0), env.get_account(6));
+        let tree = allocation_tree(&env);
+        let mut distributor = MerkleDistributor::deploy(
+            &env,
+            MerkleDistributorInitArgs {
+                token: None,
+                merkle_root: [0u8; 32],
+                expiry: env.block_time() + EXPIRY
+            }
+        );
+        env.transfer(*distributor.address(), U512::from(15_000))
+            .unwrap();
+        assert_eq!(distributor.token(), None);
+
+        // the owner updates the root
+        env.set_caller(recipient);
+        assert_eq!(
+            distributor.try_set_merkle_root(tree.root),
+            Err(AccessError::CallerNotTheOwner.into())
+        );
+        env.set_caller(owner);
+        distributor.set_merkle_root(tree.root);
+        assert!(env.emitted_event(
+            &distributor,
+            &MerkleRootUpdated {
+                merkle_root: tree.root
+            }
+        ));
+
+        // anyone claims on behalf of the account
+        let (index, account, amount) = allocations(&env)[1];
+        let balance = env.balance_of(&account);
+        distributor.claim(index, &account, &amount, tree.proofs[1].clone());
+        assert_eq!(
+            env.balance_of(&account),
+            balance + U512::from(amount.as_u64())
+        );
+        assert_eq!(
+            distributor.try_sweep(&recipient),
+            Err(Error::NotExpired.into())
+        );
+
+        // after the expiry, the claims are closed and the rest is swept
+        env.advance_block_time(EXPIRY + 1);
+        let (index, account, amount) = allocations(&env)[0];
+        assert_eq!(
+            distributor.try_claim(index, &account, &amount, tree.proofs[0].clone()),
+            Err(Error::ClaimExpired.into())
+        );
+        let balance = env.balance_of(&recipient);
+        distributor.sweep(&recipient);
+        assert_eq!(env.balance_of(&recipient), balance + U512::from(13_000));
+        assert_eq!(env.balance_of(&distributor), U512::zero());
+        assert!(env.emitted_event(
+            &distributor,
+            &Swept {
+                to: recipient,
+                amount: 13_000.into()
+            }
+        ));
+    }
+}
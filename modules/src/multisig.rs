@@ -0,0 +1,586 @@
+//! Multisig wallet implementation
+use crate::governance::ContractCall;
+use crate::multisig::errors::Error;
+use crate::multisig::events::*;
+use odra::casper_types::U512;
+use odra::prelude::*;
+
+/// An operation executed by the multisig once confirmed by enough owners.
+#[odra::odra_type]
+pub enum Operation {
+    /// A call of a contract.
+    Call {
+        /// The call executed by the multisig.
+        call: ContractCall
+    },
+    /// A transfer of native tokens.
+    Transfer {
+        /// The recipient of the tokens.
+        recipient: Address,
+        /// The amount of tokens.
+        amount: U512
+    }
+}
+
+/// A transaction submitted to the multisig.
+#[odra::odra_type]
+pub struct Transaction {
+    /// The operation executed by the transaction.
+    pub operation: Operation,
+    /// Whether the transaction has been executed.
+    pub executed: bool
+}
+
+/// The Multisig module.
+///
+/// A wallet controlled by a set of owners. Any owner can submit a transaction - a contract call
+/// or a transfer of native tokens, the other owners confirm it. Once the number of confirmations
+/// reaches the threshold, any owner can execute the transaction.
+///
+/// The owners and the threshold can be changed only by transactions calling the multisig itself.
+#[odra::module(
+    events = [
+        Deposit, Submission, Confirmation, Revocation, Execution,
+        OwnerAdded, OwnerRemoved, ThresholdChanged
+    ],
+    errors = Error
+)]
+pub struct Multisig {
+    owners: Var<Vec<Address>>,
+    threshold: Var<u32>,
+    transaction_ids: Sequence<u32>,
+    transactions: Mapping<u32, Transaction>,
+    confirmations: Mapping<(u32, Address), bool>
+}
+
+/// The Multisig module implementation.
+#[odra::module]
+impl Multisig {
+    /// Initializes the multisig with the owners and the number of required confirmations.
+    pub fn init(&mut self, owners: Vec<Address>, threshold: u32) {
+        for (index, owner) in owners.iter().enumerate() {
+            if owners[..index].contains(owner) {
+                self.env().revert(Error::AlreadyAnOwner);
+            }
+            self.env().emit_event(OwnerAdded { owner: *owner });
+        }
+        self.owners.set(owners);
+        self.set_threshold(threshold);
+    }
+
+    /// Accepts native tokens transferred to the multisig.
+    #[odra(receive)]
+    pub fn deposit(&mut self) {
+        self.env().emit_event(Deposit {
+            sender: self.env().caller(),
+            amount: self.env().attached_value()
+        });
+    }
+
+    /// Submits a contract call and confirms it on behalf of the caller,
+    /// returns the id of the transaction.
+    pub fn submit_call(&mut self, call: ContractCall) -> u32 {
+        self.submit(Operation::Call { call })
+    }
+
+    /// Submits a transfer of native tokens and confirms it on behalf of the caller,
+    /// returns the id of the transaction.
+    pub fn submit_transfer(&mut self, recipient: Address, amount: U512) -> u32 {
+        self.submit(Operation::Transfer { recipient, amount })
+    }
+
+    /// Confirms the transaction on behalf of the caller.
+    pub fn confirm(&mut self, transaction_id: u32) {
+        let owner = self.env().caller();
+        self.assert_owner(&owner);
+        self.assert_not_executed(transaction_id);
+        if self.is_confirmed(transaction_id, owner) {
+            self.env().revert(Error::AlreadyConfirmed);
+        }
+        self.confirmations.set(&(transaction_id, owner), true);
+        self.env().emit_event(Confirmation {
+            transaction_id,
+            owner
+        });
+    }
+
+    /// Revokes the confirmation of the transaction given by the caller.
+    pub fn revoke(&mut self, transaction_id: u32) {
+        let owner = self.env().caller();
+        self.assert_owner(&owner);
+        self.assert_not_executed(transaction_id);
+        if !self.is_confirmed(transaction_id, owner) {
+            self.env().revert(Error::NotConfirmed);
+        }
+        self.confirmations.set(&(transaction_id, owner), false);
+        self.env().emit_event(Revocation {
+            transaction_id,
+            owner
+        });
+    }
+
+    /// Executes the transaction confirmed by the required number of owners.
+    pub fn execute(&mut self, transaction_id: u32) {
+        self.assert_owner(&self.env().caller());
+        self.assert_not_executed(transaction_id);
+        if self.confirmation_count(transaction_id) < self.get_threshold() {
+            self.env().revert(Error::NotEnoughConfirmations);
+        }
+
+        let mut transaction = self.get_transaction(transaction_id);
+        transaction.executed = true;
+        self.transactions.set(&transaction_id, transaction.clone());
+
+        match transaction.operation {
+            Operation::Call { call } => call.execute(&self.env()),
+            Operation::Transfer { recipient, amount } => {
+                self.env().transfer_tokens(&recipient, &amount)
+            }
+        }
+        self.env().emit_event(Execution { transaction_id });
+    }
+
+    /// Adds a new owner. Can be called by the multisig only.
+    pub fn add_owner(&mut self, owner: Address) {
+        self.assert_multisig();
+        if self.is_owner(owner) {
+            self.env().revert(Error::AlreadyAnOwner);
+        }
+        let mut owners = self.get_owners();
+        owners.push(owner);
+        self.owners.set(owners);
+        self.env().emit_event(OwnerAdded { owner });
+    }
+
+    /// Removes an owner. Can be called by the multisig only.
+    ///
+    /// The remaining owners must be enough to reach the threshold.
+    pub fn remove_owner(&mut self, owner: Address) {
+        self.assert_multisig();
+        self.assert_owner(&owner);
+        let mut owners = self.get_owners();
+        owners.retain(|o| o != &owner);
+        if (owners.len() as u32) < self.get_threshold() {
+            self.env().revert(Error::InvalidThreshold);
+        }
+        self.owners.set(owners);
+        self.env().emit_event(OwnerRemoved { owner });
+    }
+
+    /// Changes the number of required confirmations. Can be called by the multisig only.
+    pub fn change_threshold(&mut self, threshold: u32) {
+        self.assert_multisig();
+        self.set_threshold(threshold);
+    }
+
+    /// Returns the owners of the multisig.
+    pub fn get_owners(&self) -> Vec<Address> {
+        self.owners.get_or_default()
+    }
+
+    /// Returns true if the address is an owner of the multisig.
+    pub fn is_owner(&self, address: Address) -> bool {
+        self.get_owners().contains(&address)
+    }
+
+    /// Returns the number of confirmations required to execute a transaction.
+    pub fn get_threshold(&self) -> u32 {
+        self.threshold.get_or_default()
+    }
+
+    /// Returns the transaction with the given id.
+    pub fn get_transaction(&self, transaction_id: u32) -> Transaction {
+        self.transactions
+            .get(&transaction_id)
+            .unwrap_or_revert_with(self, Error::UnknownTransaction)
+    }
+
+    /// Returns true if the owner has confirmed the transaction.
+    pub fn is_confirmed(&self, transaction_id: u32, owner: Address) -> bool {
+        self.confirmations.get_or_default(&(transaction_id, owner))
+    }
+
+    /// Returns the number of confirmations of the transaction given by the current owners.
+    pub fn confirmation_count(&self, transaction_id: u32) -> u32 {
+        self.get_owners()
+            .into_iter()
+            .filter(|owner| self.is_confirmed(transaction_id, *owner))
+            .count() as u32
+    }
+}
+
+impl Multisig {
+    fn submit(&mut self, operation: Operation) -> u32 {
+        let owner = self.env().caller();
+        self.assert_owner(&owner);
+        let transaction_id = self.transaction_ids.next_value();
+        self.transactions.set(
+            &transaction_id,
+            Transaction {
+                operation,
+                executed: false
+            }
+        );
+        self.env().emit_event(Submission {
+            transaction_id,
+            owner
+        });
+        self.confirm(transaction_id);
+        transaction_id
+    }
+
+    fn set_threshold(&mut self, threshold: u32) {
+        if threshold == 0 || threshold > self.get_owners().len() as u32 {
+            self.env().revert(Error::InvalidThreshold);
+        }
+        self.threshold.set(threshold);
+        self.env().emit_event(ThresholdChanged { threshold });
+    }
+
+    fn assert_owner(&self, address: &Address) {
+        if !self.is_owner(*address) {
+            self.env().revert(Error::NotAnOwner);
+        }
+    }
+
+    fn assert_not_executed(&self, transaction_id: u32) {
+        if self.get_transaction(transaction_id).executed {
+            self.env().revert(Error::AlreadyExecuted);
+        }
+    }
+
+    fn assert_multisig(&self) {
+        if self.env().caller() != self.env().self_address() {
+            self.env().revert(Error::CallerNotTheMultisig);
+        }
+    }
+}
+
+/// Multisig Events
+pub mod events {
+    use odra::casper_event_standard;
+    use odra::casper_types::U512;
+    use odra::prelude::*;
+
+    /// Event emitted when native tokens are transferred to the multisig.
+    #[odra::event]
+    pub struct Deposit {
+        /// The sender of the tokens.
+        pub sender: Address,
+        /// The amount of tokens.
+        pub amount: U512
+    }
+
+    /// Event emitted when an owner submits a transaction.
+    #[odra::event]
+    pub struct Submission {
+        /// The id of the transaction.
+        pub transaction_id: u32,
+        /// The owner submitting the transaction.
+        pub owner: Address
+    }
+
+    /// Event emitted when an owner confirms a transaction.
+    #[odra::event]
+    pub struct Confirmation {
+        /// The id of the transaction.
+        pub transaction_id: u32,
+        /// The owner confirming the transaction.
+        pub owner: Address
+    }
+
+    /// Event emitted when an owner revokes the confirmation of a transaction.
+    #[odra::event]
+    pub struct Revocation {
+        /// The id of the transaction.
+        pub transaction_id: u32,
+        /// The owner revoking the confirmation.
+        pub owner: Address
+    }
+
+    /// Event emitted when a transaction is executed.
+    #[odra::event]
+    pub struct Execution {
+        /// The id of the transaction.
+        pub transaction_id: u32
+    }
+
+    /// Event emitted when an owner is added.
+    #[odra::event]
+    pub struct OwnerAdded {
+        /// The added owner.
+        pub owner: Address
+    }
+
+    /// Event emitted when an owner is removed.
+    #[odra::event]
+    pub struct OwnerRemoved {
+        /// The removed owner.
+        pub owner: Address
+    }
+
+    /// Event emitted when the number of required confirmations is changed.
+    #[odra::event]
+    pub struct ThresholdChanged {
+        /// The new number of required confirmations.
+        pub threshold: u32
+    }
+}
+
+/// Multisig Errors
+pub mod errors {
+    use odra::prelude::*;
+
+    /// Multisig errors
+    #[odra::odra_error]
+    pub enum Error {
+        /// The caller is not an owner
+        NotAnOwner = 50_000,
+        /// The address is already an owner
+        AlreadyAnOwner = 50_001,
+        /// The threshold is zero or exceeds the number of owners
+        InvalidThreshold = 50_002,
+        /// The transaction does not exist
+        UnknownTransaction = 50_003,
+        /// The transaction has already been executed
+        AlreadyExecuted = 50_004,
+        /// The owner has already confirmed the transaction
+        AlreadyConfirmed = 50_005,
+        /// The owner has not confirmed the transaction
+        NotConfirmed = 50_006,
+        /// The transaction has not enough confirmations
+        NotEnoughConfirmations = 50_007,
+        /// The caller is not the multisig itself
+        CallerNotTheMultisig = 50_008
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cep18_token::tests::{setup, TRANSFER_AMOUNT_1};
+    use odra::casper_types::{runtime_args, RuntimeArgs, U256};
+    use odra::host::{Deployer, HostEnv, HostRef};
+    use odra::CallDef;
+
+    const THRESHOLD: u32 = 2;
+
+    fn setup_multisig(env: &HostEnv) -> MultisigHostRef {
+        let owners = vec![env.get_account(0), env.get_account(1), env.get_account(2)];
+        Multisig::deploy(
+            env,
+            MultisigInitArgs {
+                owners,
+                threshold: THRESHOLD
+            }
+        )
+    }
+
+    fn self_call(multisig: &MultisigHostRef, entry_point: &str, args: RuntimeArgs) -> ContractCall {
+        ContractCall::new(*multisig.address(), CallDef::new(entry_point, true, args))
+    }
+
+    #[test]
+    fn init_validates_owners_and_threshold() {
+        let env = odra_test::env();
+        let (owner, other) = (env.get_account(0), env.get_account(1));
+        let args = |owners, threshold| MultisigInitArgs { owners, threshold };
+
+        assert_eq!(
+            Multisig::try_deploy(&env, args(vec![owner, owner], 1)).err(),
+            Some(Error::AlreadyAnOwner.into())
+        );
+        assert_eq!(
+            Multisig::try_deploy(&env, args(vec![owner, other], 0)).err(),
+            Some(Error::InvalidThreshold.into())
+        );
+        assert_eq!(
+            Multisig::try_deploy(&env, args(vec![owner, other], 3)).err(),
+            Some(Error::InvalidThreshold.into())
+        );
+
+        let multisig = setup_multisig(&env);
+        assert_eq!(multisig.get_owners().len(), 3);
+        assert!(multisig.is_owner(owner));
+        assert!(!multisig.is_owner(env.get_account(3)));
+        assert_eq!(multisig.get_threshold(), THRESHOLD);
+        assert!(env.emitted_event(
+            &multisig,
+            &ThresholdChanged {
+                threshold: THRESHOLD
+            }
+        ));
+    }
+
+    #[test]
+    fn confirmed_transfer_is_executed() {
+        let env = odra_test::env();
+        let mut multisig = setup_multisig(&env);
+        let (owner, other_owner, stranger) =
+            (env.get_account(0), env.get_account(1), env.get_account(3));
+        let recipient = env.get_account(4);
+        let recipient_balance = env.balance_of(&recipient);
+        let (deposit, amount) = (U512::from(1_000), U512::from(600));
+
+        // the multisig receives native tokens
+        env.transfer(*multisig.address(), deposit).unwrap();
+        assert_eq!(env.balance_of(&multisig), deposit);
+        assert!(env.emitted_event(
+            &multisig,
+            &Deposit {
+                sender: owner,
+                amount: deposit
+            }
+        ));
+
+        // a submitted transaction is confirmed by the submitter
+        let transaction_id = multisig.submit_transfer(recipient, amount);
+        assert!(env.emitted_event(
+            &multisig,
+            &Submission {
+                transaction_id,
+                owner
+            }
+        ));
+        assert!(multisig.is_confirmed(transaction_id, owner));
+        assert_eq!(
+            multisig.try_execute(transaction_id),
+            Err(Error::NotEnoughConfirmations.into())
+        );
+
+        // only owners confirm a transaction, at most once
+        env.set_caller(stranger);
+        assert_eq!(
+            multisig.try_confirm(transaction_id),
+            Err(Error::NotAnOwner.into())
+        );
+        env.set_caller(other_owner);
+        multisig.confirm(transaction_id);
+        assert_eq!(
+            multisig.try_confirm(transaction_id),
+            Err(Error::AlreadyConfirmed.into())
+        );
+
+        // a confirmation can be revoked
+        multisig.revoke(transaction_id);
+        assert_eq!(multisig.confirmation_count(transaction_id), 1);
+        assert!(env.emitted_event(
+            &multisig,
+            &Revocation {
+                transaction_id,
+                owner: other_owner
+            }
+        ));
+        assert_eq!(
+            multisig.try_revoke(transaction_id),
+            Err(Error::NotConfirmed.into())
+        );
+        multisig.confirm(transaction_id);
+        assert_eq!(multisig.confirmation_count(transaction_id), THRESHOLD);
+
+        // once confirmed, the transaction is executed once
+        multisig.execute(transaction_id);
+        assert_eq!(env.balance_of(&recipient), recipient_balance + amount);
+        assert_eq!(env.balance_of(&multisig), deposit - amount);
+        assert!(multisig.get_transaction(transaction_id).executed);
+        assert!(env.emitted_event(&multisig, &Execution { transaction_id }));
+        assert_eq!(
+            multisig.try_execute(transaction_id),
+            Err(Error::AlreadyExecuted.into())
+        );
+        assert_eq!(
+            multisig.try_confirm(transaction_id + 1),
+            Err(Error::UnknownTransaction.into())
+        );
+    }
+
+    #[test]
+    fn confirmed_call_is_executed() {
+        let mut token = setup(false);
+        let env = token.env().clone();
+        let mut multisig = setup_multisig(&env);
+        let recipient = env.get_account(4);
+        let amount = U256::from(TRANSFER_AMOUNT_1);
+        token.transfer(multisig.address(), &amount);
+
+        let call = ContractCall::new(
+            *token.address(),
+            CallDef::new(
+                "transfer",
+                true,
+                runtime_args! { "recipient" => recipient, "amount" => amount }
+            )
+        );
+        let transaction_id = multisig.submit_call(call.clone());
+        assert_eq!(
+            multisig.get_transaction(transaction_id).operation,
+            Operation::Call { call }
+        );
+        env.set_caller(env.get_account(2));
+        multisig.confirm(transaction_id);
+        multisig.execute(transaction_id);
+
+        assert_eq!(token.balance_of(&recipient), amount);
+        assert_eq!(token.balance_of(multisig.address()), U256::zero());
+    }
+
+    #[test]
+    fn owners_are_changed_by_multisig() {
+        let env = odra_test::env();
+        let mut multisig = setup_multisig(&env);
+        let (owner, other_owner, new_owner) =
+            (env.get_account(0), env.get_account(1), env.get_account(3));
+
+        // the owners cannot be changed directly
+        assert_eq!(
+            multisig.try_add_owner(new_owner),
+            Err(Error::CallerNotTheMultisig.into())
+        );
+        assert_eq!(
+            multisig.try_change_threshold(1),
+            Err(Error::CallerNotTheMultisig.into())
+        );
+
+        let add_owner = self_call(
+            &multisig,
+            "add_owner",
+            runtime_args! { "owner" => new_owner }
+        );
+        let change_threshold = self_call(
+            &multisig,
+            "change_threshold",
+            runtime_args! { "threshold" => 4u32 }
+        );
+        let remove_owner = self_call(
+            &multisig,
+            "remove_owner",
+            runtime_args! { "owner" => owner }
+        );
+        let mut submit_and_execute = |call: ContractCall| {
+            env.set_caller(owner);
+            let transaction_id = multisig.submit_call(call);
+            env.set_caller(other_owner);
+            multisig.confirm(transaction_id);
+            multisig.try_execute(transaction_id)
+        };
+
+        assert_eq!(submit_and_execute(add_owner), Ok(()));
+        assert_eq!(submit_and_execute(change_threshold), Ok(()));
+
+        assert!(multisig.is_owner(new_owner));
+        assert_eq!(multisig.get_threshold(), 4);
+        assert!(env.emitted_event(&multisig, &OwnerAdded { owner: new_owner }));
+
+        // the remaining owners must reach the threshold
+        let transaction_id = multisig.submit_call(remove_owner);
+        for account in [owner, new_owner, env.get_account(2)] {
+            env.set_caller(account);
+            multisig.confirm(transaction_id);
+        }
+        assert_eq!(
+            multisig.try_execute(transaction_id),
+            Err(Error::InvalidThreshold.into())
+        );
+        assert!(multisig.is_owner(owner));
+    }
+}
@@ -0,0 +1,576 @@
+//! Staking rewards implementation
+use crate::access::Ownable;
+use crate::cep18_token::Cep18ContractRef;
+use crate::staking_rewards::errors::Error;
+use crate::staking_rewards::events::*;
+use odra::casper_types::U256;
+use odra::prelude::*;
+
+/// The precision of the reward per token, the rewards of tiny stakes do not round down to zero.
+pub const PRECISION: u128 = 1_000_000_000_000_000_000;
+
+/// The StakingRewards module.
+///
+/// Accounts stake tokens of one [Cep18](crate::cep18_token::Cep18) contract and earn tokens of another one.
+/// The owner funds a reward period with [notify_reward_amount()](StakingRewards::notify_reward_amount),
+/// the reward is distributed evenly over the rewards duration, at a constant rate per millisecond.
+/// The rate is stored multiplied by [PRECISION], so low rewards distributed over long periods
+/// are not rounded down.
+///
+/// The module keeps track of the reward earned by a single staked token since the deployment,
+/// the reward per token. The reward of an account is its stake multiplied by the growth of the reward per token
+/// since the last update of the account, so the rewards are computed in constant time.
+#[odra::module(
+    events = [RewardAdded, Staked, Withdrawn, RewardPaid, RewardsDurationUpdated],
+    errors = Error
+)]
+pub struct StakingRewards {
+    ownable: SubModule<Ownable>,
+    staking_token: External<Cep18ContractRef>,
+    rewards_token: External<Cep18ContractRef>,
+    rewards_duration: Var<u64>,
+    period_finish: Var<u64>,
+    reward_rate: Var<U256>,
+    last_update_time: Var<u64>,
+    reward_per_token_stored: Var<U256>,
+    user_reward_per_token_paid: Mapping<Address, U256>,
+    rewards: Mapping<Address, U256>,
+    total_supply: Var<U256>,
+    balances: Mapping<Address, U256>
+}
+
+/// The StakingRewards module implementation.
+#[odra::module]
+impl StakingRewards {
+    /// Initializes the module with the staked and the rewards tokens, and the duration of a reward period.
+    ///
+    /// The caller becomes the owner.
+    pub fn init(&mut self, staking_token: Address, rewards_token: Address, rewards_duration: u64) {
+        self.ownable.init();
+        self.staking_token.set(staking_token);
+        self.rewards_token.set(rewards_token);
+        self.set_duration(rewards_duration);
+    }
+
+    /// Stakes the given amount of tokens, the caller must approve the transfer first.
+    pub fn stake(&mut self, amount: &U256) {
+        if amount.is_zero() {
+            self.env().revert(Error::CannotStakeZero);
+        }
+        let account = self.env().caller();
+        self.update_reward(Some(account));
+        self.total_supply.add(*amount);
+        self.balances.add(&account, *amount);
+        let this = self.env().self_address();
+        self.staking_token.transfer_from(&account, &this, amount);
+        self.env().emit_event(Staked {
+            account,
+            amount: *amount
+        });
+    }
+
+    /// Withdraws the given amount of the staked tokens.
+    pub fn withdraw(&mut self, amount: &U256) {
+        if amount.is_zero() {
+            self.env().revert(Error::CannotWithdrawZero);
+        }
+        let account = self.env().caller();
+        if self.balance_of(&account) < *amount {
+            self.env().revert(Error::InsufficientStake);
+        }
+        self.update_reward(Some(account));
+        self.total_supply.subtract(*amount);
+        self.balances.subtract(&account, *amount);
+        self.staking_token.transfer(&account, amount);
+        self.env().emit_event(Withdrawn {
+            account,
+            amount: *amount
+        });
+    }
+
+    /// Transfers the reward earned by the caller.
+    pub fn claim(&mut self) {
+        let account = self.env().caller();
+        self.update_reward(Some(account));
+        let reward = self.rewards.get_or_default(&account);
+        if reward.is_zero() {
+            return;
+        }
+        self.rewards.set(&account, U256::zero());
+        self.rewards_token.transfer(&account, &reward);
+        self.env().emit_event(RewardPaid { account, reward });
+    }
+
+    /// Starts a new reward period distributing the given reward, together with the reward
+    /// not distributed yet if the current period has not finished.
+    ///
+    /// The reward must be transferred to the module beforehand.
+    #[odra(only_owner = ownable)]
+    pub fn notify_reward_amount(&mut self, reward: &U256) {
+        self.update_reward(None);
+        let now = self.env().get_block_time();
+        let duration = U256::from(self.rewards_duration());
+        let period_finish = self.period_finish();
+        let reward = *reward * U256::from(PRECISION);
+        let reward_rate = if now >= period_finish {
+            reward / duration
+        } else {
+            let leftover = U256::from(period_finish - now) * self.reward_rate();
+            (reward + leftover) / duration
+        };
+        if reward_rate.is_zero() {
+            self.env().revert(Error::ZeroRewardRate);
+        }
+
+        // The rewards token balance must cover the whole period.
+        let balance = self.rewards_token.balance_of(&self.env().self_address());
+        if reward_rate * duration > balance * U256::from(PRECISION) {
+            self.env().revert(Error::RewardTooHigh);
+        }
+
+        self.reward_rate.set(reward_rate);
+        self.last_update_time.set(now);
+        self.period_finish.set(now + self.rewards_duration());
+        self.env().emit_event(RewardAdded {
+            reward: reward / U256::from(PRECISION)
+        });
+    }
+
+    /// Changes the duration of the reward periods, once the current period has finished.
+    #[odra(only_owner = ownable)]
+    pub fn set_rewards_duration(&mut self, rewards_duration: u64) {
+        if self.env().get_block_time() <= self.period_finish() {
+            self.env().revert(Error::RewardPeriodNotFinished);
+        }
+        self.set_duration(rewards_duration);
+    }
+
+    /// Returns the total amount of the staked tokens.
+    pub fn total_supply(&self) -> U256 {
+        self.total_supply.get_or_default()
+    }
+
+    /// Returns the amount of tokens staked by the account.
+    pub fn balance_of(&self, account: &Address) -> U256 {
+        self.balances.get_or_default(account)
+    }
+
+    /// Returns the current block time, or the end of the reward period if it has finished.
+    pub fn last_time_reward_applicable(&self) -> u64 {
+        self.env().get_block_time().min(self.period_finish())
+    }
+
+    /// Returns the reward earned by a single staked token since the deployment,
+    /// multiplied by [PRECISION].
+    pub fn reward_per_token(&self) -> U256 {
+        let stored = self.reward_per_token_stored.get_or_default();
+        let total_supply = self.total_supply();
+        if total_supply.is_zero() {
+            return stored;
+        }
+        let elapsed = self
+            .last_time_reward_applicable()
+            .saturating_sub(self.last_update_time.get_or_default());
+        stored + U256::from(elapsed) * self.reward_rate() / total_supply
+    }
+
+    /// Returns the reward earned by the account and not claimed yet.
+    pub fn earned(&self, account: &Address) -> U256 {
+        let paid = self.user_reward_per_token_paid.get_or_default(account);
+        self.balance_of(account) * (self.reward_per_token() - paid) / U256::from(PRECISION)
+            + self.rewards.get_or_default(account)
+    }
+
+    /// Returns the reward distributed per millisecond, multiplied by [PRECISION].
+    pub fn reward_rate(&self) -> U256 {
+        self.reward_rate.get_or_default()
+    }
+
+    /// Returns the reward distributed over the whole reward period.
+    pub fn get_reward_for_duration(&self) -> U256 {
+        self.reward_rate() * U256::from(self.rewards_duration()) / U256::from(PRECISION)
+    }
+
+    /// Returns the duration of a reward period.
+    pub fn rewards_duration(&self) -> u64 {
+        self.rewards_duration.get_or_default()
+    }
+
+    /// Returns the end of the current reward period.
+    pub fn period_finish(&self) -> u64 {
+        self.period_finish.get_or_default()
+    }
+
+    delegate! {
+        to self.ownable {
+            fn get_owner(&self) -> Address;
+            fn transfer_ownership(&mut self, new_owner: &Address);
+        }
+    }
+}
+
+impl StakingRewards {
+    fn update_reward(&mut self, account: Option<Address>) {
+        let reward_per_token = self.reward_per_token();
+        self.reward_per_token_stored.set(reward_per_token);
+        self.last_update_time
+            .set(self.last_time_reward_applicable());
+        if let Some(account) = account {
+            let earned = self.earned(&account);
+            self.rewards.set(&account, earned);
+            self.user_reward_per_token_paid
+                .set(&account, reward_per_token);
+        }
+    }
+
+    fn set_duration(&mut self, rewards_duration: u64) {
+        if rewards_duration == 0 {
+            self.env().revert(Error::InvalidRewardsDuration);
+        }
+        self.rewards_duration.set(rewards_duration);
+        self.env()
+            .emit_event(RewardsDurationUpdated { rewards_duration });
+    }
+}
+
+/// StakingRewards Events
+pub mod events {
+    use odra::casper_event_standard;
+    use odra::casper_types::U256;
+    use odra::prelude::*;
+
+    /// Event emitted when a reward period is funded.
+    #[odra::event]
+    pub struct RewardAdded {
+        /// The reward added to the distribution.
+        pub reward: U256
+    }
+
+    /// Event emitted when an account stakes tokens.
+    #[odra::event]
+    pub struct Staked {
+        /// The staking account.
+        pub account: Address,
+        /// The amount of tokens staked.
+        pub amount: U256
+    }
+
+    /// Event emitted when an account withdraws staked tokens.
+    #[odra::event]
+    pub struct Withdrawn {
+        /// The withdrawing account.
+        pub account: Address,
+        /// The amount of tokens withdrawn.
+        pub amount: U256
+    }
+
+    /// Event emitted when an account claims its reward.
+    #[odra::event]
+    pub struct RewardPaid {
+        /// The rewarded account.
+        pub account: Address,
+        /// The amount of reward tokens transferred.
+        pub reward: U256
+    }
+
+    /// Event emitted when the duration of the reward periods is changed.
+    #[odra::event]
+    pub struct RewardsDurationUpdated {
+        /// The new duration of a reward period.
+        pub rewards_duration: u64
+    }
+}
+
+/// StakingRewards Errors
+pub mod errors {
+    use odra::prelude::*;
+
+    /// StakingRewards errors
+    #[odra::odra_error]
+    pub enum Error {
+        /// The staked amount is zero
+        CannotStakeZero = 52_000,
+        /// The withdrawn amount is zero
+        CannotWithdrawZero = 52_001,
+        /// The account has staked less than the withdrawn amount
+        InsufficientStake = 52_002,
+        /// The reward exceeds the balance of the rewards token
+        RewardTooHigh = 52_003,
+        /// The current reward period has not finished
+        RewardPeriodNotFinished = 52_004,
+        /// The rewards duration is zero
+        InvalidRewardsDuration = 52_005,
+        /// The reward is too low to be distributed over the rewards duration
+        ZeroRewardRate = 52_006
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::access::errors::Error as AccessError;
+    use crate::cep18::utils::Cep18Modality;
+    use crate::cep18_token::tests::{setup, setup_with_args};
+    use crate::cep18_token::{Cep18HostRef, Cep18InitArgs};
+    use odra::host::{Deployer, HostEnv, HostRef};
+
+    const DURATION: u64 = 10_000;
+    const REWARD: u64 = 1_000_000;
+    const YEAR: u64 = 31_536_000_000;
+
+    fn setup_staking(
+        duration: u64,
+        rewards_supply: U256
+    ) -> (StakingRewardsHostRef, Cep18HostRef, Cep18HostRef) {
+        let staking_token = setup(false);
+        let env = staking_token.env().clone();
+        let rewards_token = setup_with_args(
+            &env,
+            Cep18InitArgs {
+                symbol: "RWD".to_string(),
+                name: "Reward".to_string(),
+                decimals: 18,
+                initial_supply: rewards_supply,
+                admin_list: vec![],
+                minter_list: vec![],
+                modality: Some(Cep18Modality::None)
+            }
+        );
+        let staking = StakingRewards::deploy(
+            &env,
+            StakingRewardsInitArgs {
+                staking_token: *staking_token.address(),
+                rewards_token: *rewards_token.address(),
+                rewards_duration: duration
+            }
+        );
+        (staking, staking_token, rewards_token)
+    }
+
+    fn stake(
+        env: &HostEnv,
+        staking: &mut StakingRewardsHostRef,
+        token: &mut Cep18HostRef,
+        account: Address,
+        amount: U256
+    ) {
+        env.set_caller(env.get_account(0));
+        token.transfer(&account, &amount);
+        env.set_caller(account);
+        token.approve(staking.address(), &amount);
+        staking.stake(&amount);
+    }
+
+    fn fund(
+        env: &HostEnv,
+        staking: &mut StakingRewardsHostRef,
+        rewards_token: &mut Cep18HostRef,
+        reward: U256
+    ) {
+        env.set_caller(env.get_account(0));
+        rewards_token.transfer(staking.address(), &reward);
+        staking.notify_reward_amount(&reward);
+    }
+
+    #[test]
+    fn single_staker_earns_whole_reward() {
+        let (mut staking, mut staking_token, mut rewards_token) =
+            setup_staking(DURATION, REWARD.into());
+        let env = staking.env().clone();
+        let alice = env.get_account(1);
+        let amount = U256::from(1_000);
+        stake(&env, &mut staking, &mut staking_token, alice, amount);
+        assert_eq!(staking_token.balance_of(staking.address()), amount);
+        assert!(env.emitted_event(
+            &staking,
+            &Staked {
+                account: alice,
+                amount
+            }
+        ));
+
+        // nothing is earned before the reward period starts
+        env.advance_block_time(DURATION);
+        assert_eq!(staking.earned(&alice), U256::zero());
+
+        fund(
+            &env,
+            &mut staking,
+            &mut rewards_token,
+            U256::from(REWARD / 2)
+        );
+        assert_eq!(
+            staking.reward_rate(),
+            U256::from(50) * U256::from(PRECISION)
+        );
+        env.advance_block_time(DURATION / 2);
+        assert_eq!(staking.earned(&alice), U256::from(REWARD / 4));
+
+        // the reward left from the current period rolls over to the next one
+        fund(
+            &env,
+            &mut staking,
+            &mut rewards_token,
+            U256::from(REWARD / 2)
+        );
+        assert_eq!(
+            staking.reward_rate(),
+            U256::from(75) * U256::from(PRECISION)
+        );
+        assert_eq!(
+            staking.get_reward_for_duration(),
+            U256::from(REWARD * 3 / 4)
+        );
+        env.advance_block_time(DURATION * 2);
+        assert_eq!(staking.earned(&alice), U256::from(REWARD));
+
+        env.set_caller(alice);
+        staking.claim();
+        staking.withdraw(&amount);
+        assert_eq!(rewards_token.balance_of(&alice), U256::from(REWARD));
+        assert_eq!(staking_token.balance_of(&alice), amount);
+        assert_eq!(staking.earned(&alice), U256::zero());
+        assert_eq!(staking.total_supply(), U256::zero());
+        assert!(env.emitted_event(
+            &staking,
+            &RewardPaid {
+                account: alice,
+                reward: U256::from(REWARD)
+            }
+        ));
+    }
+
+    #[test]
+    fn rewards_are_proportional_to_stakes() {
+        let (mut staking, mut staking_token, mut rewards_token) =
+            setup_staking(DURATION, REWARD.into());
+        let env = staking.env().clone();
+        let (alice, bob) = (env.get_account(1), env.get_account(2));
+        stake(&env, &mut staking, &mut staking_token, alice, 1_000.into());
+        stake(&env, &mut staking, &mut staking_token, bob, 3_000.into());
+        fund(&env, &mut staking, &mut rewards_token, REWARD.into());
+
+        env.advance_block_time(DURATION / 2);
+        assert_eq!(staking.earned(&alice), U256::from(125_000));
+        assert_eq!(staking.earned(&bob), U256::from(375_000));
+
+        // alice leaves, bob earns the rest of the reward, rounded down
+        env.set_caller(alice);
+        staking.withdraw(&1_000.into());
+        env.advance_block_time(DURATION);
+        assert_eq!(staking.earned(&alice), U256::from(125_000));
+        assert_eq!(staking.earned(&bob), U256::from(874_999));
+
+        staking.claim();
+        env.set_caller(bob);
+        staking.claim();
+        let paid = rewards_token.balance_of(&alice) + rewards_token.balance_of(&bob);
+        assert_eq!(paid, U256::from(REWARD - 1));
+        assert_eq!(rewards_token.balance_of(staking.address()), U256::one());
+    }
+
+    #[test]
+    fn rewards_keep_precision_over_long_period() {
+        // a reward of a million tokens with 18 decimals, distributed over a year
+        let reward = U256::from(1_000_000) * U256::from(PRECISION);
+        let (mut staking, mut staking_token, mut rewards_token) = setup_staking(YEAR, reward);
+        let env = staking.env().clone();
+        let (alice, bob) = (env.get_account(1), env.get_account(2));
+        let whale_stake = U256::from(300_000_000);
+        stake(&env, &mut staking, &mut staking_token, alice, U256::one());
+        stake(&env, &mut staking, &mut staking_token, bob, whale_stake);
+        fund(&env, &mut staking, &mut rewards_token, reward);
+        let distributed = staking.get_reward_for_duration();
+        assert!(reward - distributed <= U256::one());
+
+        // bob claims every month, alice only once the period has finished
+        env.set_caller(bob);
+        for _ in 0..13 {
+            env.advance_block_time(YEAR / 12);
+            staking.claim();
+        }
+        env.set_caller(alice);
+        staking.claim();
+
+        let alice_reward = rewards_token.balance_of(&alice);
+        let bob_reward = rewards_token.balance_of(&bob);
+        let expected_alice_reward = distributed / (whale_stake + 1);
+        assert!(!alice_reward.is_zero());
+        assert!(expected_alice_reward - alice_reward <= U256::one());
+        assert!(alice_reward + bob_reward <= distributed);
+        assert!(distributed - alice_reward - bob_reward < U256::from(20));
+        assert_eq!(staking.earned(&alice), U256::zero());
+        assert_eq!(staking.earned(&bob), U256::zero());
+    }
+
+    #[test]
+    fn low_decimals_reward_is_distributed_over_long_period() {
+        // a reward of a thousand tokens with 6 decimals, distributed over a year
+        let reward = U256::from(1_000_000_000);
+        let (mut staking, mut staking_token, mut rewards_token) = setup_staking(YEAR, reward);
+        let env = staking.env().clone();
+        let alice = env.get_account(1);
+        stake(&env, &mut staking, &mut staking_token, alice, 1_000.into());
+        fund(&env, &mut staking, &mut rewards_token, reward);
+        assert!(!staking.reward_rate().is_zero());
+
+        env.advance_block_time(YEAR / 2);
+        assert!(reward / 2 - staking.earned(&alice) <= U256::one());
+
+        env.advance_block_time(YEAR);
+        env.set_caller(alice);
+        staking.claim();
+        let alice_reward = rewards_token.balance_of(&alice);
+        assert!(reward - alice_reward <= U256::one());
+    }
+
+    #[test]
+    fn invalid_operations_revert() {
+        let (mut staking, mut staking_token, mut rewards_token) =
+            setup_staking(DURATION, REWARD.into());
+        let env = staking.env().clone();
+        let alice = env.get_account(1);
+        stake(&env, &mut staking, &mut staking_token, alice, 1_000.into());
+
+        assert_eq!(
+            staking.try_stake(&U256::zero()),
+            Err(Error::CannotStakeZero.into())
+        );
+        assert_eq!(
+            staking.try_withdraw(&U256::zero()),
+            Err(Error::CannotWithdrawZero.into())
+        );
+        assert_eq!(
+            staking.try_withdraw(&1_001.into()),
+            Err(Error::InsufficientStake.into())
+        );
+        assert_eq!(
+            staking.try_notify_reward_amount(&REWARD.into()),
+            Err(AccessError::CallerNotTheOwner.into())
+        );
+
+        // the reward must be transferred before the period starts
+        env.set_caller(env.get_account(0));
+        assert_eq!(
+            staking.try_notify_reward_amount(&REWARD.into()),
+            Err(Error::RewardTooHigh.into())
+        );
+        assert_eq!(
+            staking.try_notify_reward_amount(&U256::zero()),
+            Err(Error::ZeroRewardRate.into())
+        );
+        fund(&env, &mut staking, &mut rewards_token, REWARD.into());
+        assert_eq!(
+            staking.try_set_rewards_duration(DURATION * 2),
+            Err(Error::RewardPeriodNotFinished.into())
+        );
+
+        env.advance_block_time(DURATION + 1);
+        staking.set_rewards_duration(DURATION * 2);
+        assert_eq!(staking.rewards_duration(), DURATION * 2);
+        assert_eq!(
+            staking.try_set_rewards_duration(0),
+            Err(Error::InvalidRewardsDuration.into())
+        );
+    }
+}
@@ -0,0 +1,319 @@
+//! Vesting wallet implementation
+use crate::cep18_token::Cep18ContractRef;
+use crate::vesting_wallet::errors::Error;
+use crate::vesting_wallet::events::{Cep18Released, NativeReleased};
+use core::ops::{Div, Mul};
+use odra::casper_types::{U256, U512};
+use odra::prelude::*;
+use odra::ContractRef;
+
+/// The VestingWallet module.
+///
+/// Holds native tokens and tokens of a configured [Cep18](crate::cep18_token::Cep18) contract
+/// on behalf of the beneficiary, releasing them according to a vesting schedule.
+///
+/// The tokens vest linearly from `start` until `start + duration`. With a non-zero `cliff`,
+/// nothing vests until `start + cliff`, then the amount vested since `start` becomes releasable at once.
+/// The cliff equal to the duration releases all the tokens at the end of the schedule.
+///
+/// Any tokens transferred to the wallet follow the schedule, as if they had been locked from the start.
+/// Anyone can release the vested tokens, they are always transferred to the beneficiary.
+#[odra::module(events = [NativeReleased, Cep18Released], errors = Error)]
+pub struct VestingWallet {
+    beneficiary: Var<Address>,
+    start: Var<u64>,
+    duration: Var<u64>,
+    cliff: Var<u64>,
+    token: External<Cep18ContractRef>,
+    released: Var<U512>,
+    released_cep18: Var<U256>
+}
+
+/// The VestingWallet module implementation.
+#[odra::module]
+impl VestingWallet {
+    /// Initializes the wallet with the beneficiary, the vesting schedule and the Cep18 token.
+    ///
+    /// The `start` is a block time, the `duration` and the `cliff` are given in milliseconds.
+    pub fn init(
+        &mut self,
+        beneficiary: Address,
+        start: u64,
+        duration: u64,
+        cliff: u64,
+        token: Address
+    ) {
+        if cliff > duration {
+            self.env().revert(Error::InvalidCliff);
+        }
+        self.beneficiary.set(beneficiary);
+        self.start.set(start);
+        self.duration.set(duration);
+        self.cliff.set(cliff);
+        self.token.set(token);
+    }
+
+    /// Accepts native tokens transferred to the wallet.
+    #[odra(receive)]
+    pub fn deposit(&mut self) {}
+
+    /// Transfers the releasable native tokens to the beneficiary.
+    pub fn release(&mut self) {
+        let amount = self.releasable();
+        self.released.add(amount);
+        self.env().transfer_tokens(&self.beneficiary(), &amount);
+        self.env().emit_event(NativeReleased { amount });
+    }
+
+    /// Transfers the releasable Cep18 tokens to the beneficiary.
+    pub fn release_cep18(&mut self) {
+        let amount = self.releasable_cep18();
+        self.released_cep18.add(amount);
+        let beneficiary = self.beneficiary();
+        self.token.transfer(&beneficiary, &amount);
+        self.env().emit_event(Cep18Released {
+            token: self.token(),
+            amount
+        });
+    }
+
+    /// Returns the amount of native tokens vested but not released yet.
+    pub fn releasable(&self) -> U512 {
+        self.vested_amount(self.env().get_block_time()) - self.released()
+    }
+
+    /// Returns the amount of Cep18 tokens vested but not released yet.
+    pub fn releasable_cep18(&self) -> U256 {
+        self.vested_cep18_amount(self.env().get_block_time()) - self.released_cep18()
+    }
+
+    /// Returns the amount of native tokens vested at the given time.
+    pub fn vested_amount(&self, timestamp: u64) -> U512 {
+        let total_allocation = self.env().self_balance() + self.released();
+        self.vesting_schedule(total_allocation, timestamp)
+    }
+
+    /// Returns the amount of Cep18 tokens vested at the given time.
+    pub fn vested_cep18_amount(&self, timestamp: u64) -> U256 {
+        let balance = self.token.balance_of(&self.env().self_address());
+        self.vesting_schedule(balance + self.released_cep18(), timestamp)
+    }
+
+    /// Returns the amount of native tokens already released.
+    pub fn released(&self) -> U512 {
+        self.released.get_or_default()
+    }
+
+    /// Returns the amount of Cep18 tokens already released.
+    pub fn released_cep18(&self) -> U256 {
+        self.released_cep18.get_or_default()
+    }
+
+    /// Returns the beneficiary of the wallet.
+    pub fn beneficiary(&self) -> Address {
+        self.beneficiary
+            .get_or_revert_with(Error::BeneficiaryNotSet)
+    }
+
+    /// Returns the address of the Cep18 contract.
+    pub fn token(&self) -> Address {
+        *self.token.address()
+    }
+
+    /// Returns the start of the vesting.
+    pub fn start(&self) -> u64 {
+        self.start.get_or_default()
+    }
+
+    /// Returns the duration of the vesting.
+    pub fn duration(&self) -> u64 {
+        self.duration.get_or_default()
+    }
+
+    /// Returns the time after the start until which nothing vests.
+    pub fn cliff(&self) -> u64 {
+        self.cliff.get_or_default()
+    }
+}
+
+impl VestingWallet {
+    fn vesting_schedule<T>(&self, total_allocation: T, timestamp: u64) -> T
+    where
+        T: Mul<Output = T> + Div<Output = T> + From<u64> + Default
+    {
+        let start = self.start();
+        let duration = self.duration();
+        if timestamp < start + self.cliff() {
+            T::default()
+        } else if timestamp >= start + duration {
+            total_allocation
+        } else {
+            total_allocation * T::from(timestamp - start) / T::from(duration)
+        }
+    }
+}
+
+/// VestingWallet Events
+pub mod events {
+    use odra::casper_event_standard;
+    use odra::casper_types::{U256, U512};
+    use odra::prelude::*;
+
+    /// Event emitted when native tokens are released to the beneficiary.
+    #[odra::event]
+    pub struct NativeReleased {
+        /// The amount of tokens released.
+        pub amount: U512
+    }
+
+    /// Event emitted when Cep18 tokens are released to the beneficiary.
+    #[odra::event]
+    pub struct Cep18Released {
+        /// The address of the Cep18 contract.
+        pub token: Address,
+        /// The amount of tokens released.
+        pub amount: U256
+    }
+}
+
+/// VestingWallet Errors
+pub mod errors {
+    use odra::prelude::*;
+
+    /// VestingWallet errors
+    #[odra::odra_error]
+    pub enum Error {
+        /// The cliff is longer than the duration
+        InvalidCliff = 51_000,
+        /// The beneficiary is not set
+        BeneficiaryNotSet = 51_001
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cep18_token::tests::setup;
+    use odra::host::{Deployer, HostEnv, HostRef};
+
+    const DELAY: u64 = 1_000;
+    const DURATION: u64 = 10_000;
+    const CLIFF: u64 = 2_000;
+
+    fn setup_wallet(env: &HostEnv, token: Address, cliff: u64) -> (VestingWalletHostRef, u64) {
+        let start = env.block_time() + DELAY;
+        let wallet = VestingWallet::deploy(
+            env,
+            VestingWalletInitArgs {
+                beneficiary: env.get_account(4),
+                start,
+                duration: DURATION,
+                cliff,
+                token
+            }
+        );
+        (wallet, start)
+    }
+
+    #[test]
+    fn cliff_cannot_exceed_duration() {
+        let token = setup(false);
+        let env = token.env().clone();
+        let init_args = VestingWalletInitArgs {
+            beneficiary: env.get_account(4),
+            start: env.block_time(),
+            duration: DURATION,
+            cliff: DURATION + 1,
+            token: *token.address()
+        };
+
+        assert_eq!(
+            VestingWallet::try_deploy(&env, init_args).err(),
+            Some(Error::InvalidCliff.into())
+        );
+    }
+
+    #[test]
+    fn native_tokens_vest_linearly_after_cliff() {
+        let token = setup(false);
+        let env = token.env().clone();
+        let (mut wallet, start) = setup_wallet(&env, *token.address(), CLIFF);
+        let beneficiary = env.get_account(4);
+        let beneficiary_balance = env.balance_of(&beneficiary);
+        let allocation = U512::from(10_000);
+        env.transfer(*wallet.address(), allocation).unwrap();
+
+        // nothing vests before the cliff, then the tokens vest linearly
+        assert_eq!(wallet.vested_amount(start - 1), U512::zero());
+        assert_eq!(wallet.vested_amount(start + CLIFF - 1), U512::zero());
+        assert_eq!(wallet.vested_amount(start + CLIFF), U512::from(2_000));
+        assert_eq!(
+            wallet.vested_amount(start + DURATION / 2),
+            U512::from(5_000)
+        );
+        assert_eq!(wallet.vested_amount(start + DURATION), allocation);
+        assert_eq!(wallet.releasable(), U512::zero());
+
+        // the vested tokens are released to the beneficiary
+        env.advance_block_time(DELAY + DURATION / 2);
+        assert_eq!(wallet.releasable(), U512::from(5_000));
+        wallet.release();
+        assert_eq!(
+            env.balance_of(&beneficiary),
+            beneficiary_balance + U512::from(5_000)
+        );
+        assert_eq!(wallet.released(), U512::from(5_000));
+        assert_eq!(wallet.releasable(), U512::zero());
+        assert!(env.emitted_event(
+            &wallet,
+            &NativeReleased {
+                amount: U512::from(5_000)
+            }
+        ));
+
+        // the tokens deposited later follow the schedule
+        env.transfer(*wallet.address(), allocation).unwrap();
+        assert_eq!(wallet.releasable(), U512::from(5_000));
+        env.advance_block_time(DURATION);
+        assert_eq!(wallet.releasable(), U512::from(15_000));
+        wallet.release();
+        assert_eq!(
+            env.balance_of(&beneficiary),
+            beneficiary_balance + allocation + allocation
+        );
+        assert_eq!(env.balance_of(&wallet), U512::zero());
+    }
+
+    #[test]
+    fn cep18_tokens_vest_at_cliff() {
+        let mut token = setup(false);
+        let env = token.env().clone();
+        let (mut wallet, start) = setup_wallet(&env, *token.address(), DURATION);
+        let beneficiary = env.get_account(4);
+        let allocation = U256::from(10_000);
+        token.transfer(wallet.address(), &allocation);
+
+        // the cliff equal to the duration releases all the tokens at the end
+        assert_eq!(
+            wallet.vested_cep18_amount(start + DURATION - 1),
+            U256::zero()
+        );
+        assert_eq!(wallet.vested_cep18_amount(start + DURATION), allocation);
+        env.advance_block_time(DELAY + DURATION - 1);
+        assert_eq!(wallet.releasable_cep18(), U256::zero());
+
+        env.advance_block_time(1);
+        assert_eq!(wallet.releasable_cep18(), allocation);
+        wallet.release_cep18();
+        assert_eq!(token.balance_of(&beneficiary), allocation);
+        assert_eq!(token.balance_of(wallet.address()), U256::zero());
+        assert_eq!(wallet.released_cep18(), allocation);
+        assert!(env.emitted_event(
+            &wallet,
+            &Cep18Released {
+                token: *token.address(),
+                amount: allocation
+            }
+        ));
+    }
+}
@@ -0,0 +1,98 @@
+#![doc = "A counter contract written without Odra - to be compiled into the WASM"]
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("This binary only supports wasm32 target architecture!");
+
+extern crate alloc;
+
+use alloc::{string::ToString, vec};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert
+};
+use casper_types::{
+    contracts::NamedKeys, CLType, CLValue, EntryPoint, EntryPointAccess, EntryPointType,
+    EntryPoints, Parameter, URef
+};
+
+const COUNT_KEY: &str = "count";
+const PACKAGE_HASH_KEY: &str = "counter_package_hash";
+
+#[no_mangle]
+extern "C" fn increment() {
+    let by: u32 = runtime::get_named_arg("by");
+    let count_uref = count_uref();
+    let count: u32 = storage::read(count_uref)
+        .unwrap_or_revert()
+        .unwrap_or_revert();
+    storage::write(count_uref, count + by);
+}
+
+// Exported under the name the host calls instead of an undefined entry point.
+#[no_mangle]
+extern "C" fn __odra_fallback() {
+    let count_uref = count_uref();
+    let count: u32 = storage::read(count_uref)
+        .unwrap_or_revert()
+        .unwrap_or_revert();
+    storage::write(count_uref, count + 1);
+}
+
+#[no_mangle]
+extern "C" fn get() {
+    let count: u32 = storage::read(count_uref())
+        .unwrap_or_revert()
+        .unwrap_or_revert();
+    runtime::ret(CLValue::from_t(count).unwrap_or_revert());
+}
+
+#[no_mangle]
+extern "C" fn call() {
+    let initial_value: u32 = runtime::get_named_arg("initial_value");
+
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(
+        COUNT_KEY.to_string(),
+        storage::new_uref(initial_value).into()
+    );
+
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        "increment",
+        vec![Parameter::new("by", CLType::U32)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get",
+        vec![],
+        CLType::U32,
+        EntryPointAccess::Public,
+        EntryPointType::Contract
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "__odra_fallback",
+        vec![],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract
+    ));
+
+    let (_, _) = storage::new_contract(
+        entry_points,
+        Some(named_keys),
+        Some(PACKAGE_HASH_KEY.to_string()),
+        None
+    );
+}
+
+fn count_uref() -> URef {
+    runtime::get_key(COUNT_KEY)
+        .unwrap_or_revert()
+        .into_uref()
+        .unwrap_or_revert()
+}
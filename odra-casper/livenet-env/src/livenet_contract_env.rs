@@ -2,9 +2,11 @@
 use blake2::digest::VariableOutput;
 use blake2::Blake2bVar;
 use odra_casper_rpc_client::casper_client::CasperClient;
+use odra_casper_rpc_client::log::info;
 use odra_core::callstack::{Callstack, CallstackElement};
 use odra_core::casper_types::bytesrepr::Bytes;
-use odra_core::casper_types::{CLValue, U512};
+use odra_core::casper_types::{CLValue, RuntimeArgs, U512};
+use odra_core::factory::ContractTemplate;
 use odra_core::prelude::*;
 use odra_core::{CallDef, ContractContext, ContractRegister};
 use std::io::Write;
@@ -95,6 +97,14 @@ impl ContractContext for LivenetContractEnv {
         result.unwrap()
     }
 
+    fn new_contract(
+        &self,
+        _template: &ContractTemplate,
+        _init_args: Option<RuntimeArgs>
+    ) -> Address {
+        panic!("Cannot install a contract in LivenetEnv without a deploy")
+    }
+
     fn get_block_time(&self) -> u64 {
         let client = self.casper_client.borrow();
         self.runtime
@@ -155,6 +165,10 @@ impl ContractContext for LivenetContractEnv {
         // no-op
     }
 
+    fn log(&self, message: &str) {
+        info(message);
+    }
+
     fn hash(&self, bytes: &[u8]) -> [u8; 32] {
         let mut result = [0u8; 32];
         let mut hasher = <Blake2bVar as VariableOutput>::new(32).expect("should create hasher");
@@ -9,9 +9,9 @@ use odra_core::entry_point_callback::EntryPointsCaller;
 use odra_core::{
     casper_types::{bytesrepr::Bytes, PublicKey, RuntimeArgs, U512},
     host::HostContext,
-    CallDef, ContractEnv, GasReport
+    CallDef, ContractEnv, GasProfile, GasReport
 };
-use odra_core::{prelude::*, EventError};
+use odra_core::{prelude::*, EventError, VmError};
 use odra_core::{ContractContainer, ContractRegister};
 use std::sync::RwLock;
 use std::thread::sleep;
@@ -67,12 +67,24 @@ impl HostContext for LivenetHost {
         self.casper_client.borrow().get_account(index)
     }
 
+    fn new_account(&self) -> Address {
+        panic!("new_account is not supported for LivenetHost");
+    }
+
+    fn account(&self, _label: &str) -> Address {
+        panic!("account is not supported for LivenetHost");
+    }
+
     fn balance_of(&self, address: &Address) -> U512 {
         let rt = Runtime::new().unwrap();
         let client = self.casper_client.borrow();
         rt.block_on(async { client.get_balance(address).await })
     }
 
+    fn set_balance(&self, _address: &Address, _amount: U512) {
+        panic!("set_balance is not supported for LivenetHost");
+    }
+
     fn advance_block_time(&self, time_diff: u64) {
         info(format!(
             "advance_block_time called - Waiting for {} ms",
@@ -162,6 +174,17 @@ impl HostContext for LivenetHost {
         Ok(address)
     }
 
+    fn install_wasm(
+        &self,
+        _wasm_path: &str,
+        _args: RuntimeArgs,
+        _package_hash_key: &str
+    ) -> OdraResult<Address> {
+        Err(OdraError::VmError(VmError::UnsupportedOperation(
+            String::from("install_wasm")
+        )))
+    }
+
     fn register_contract(
         &self,
         address: Address,
@@ -186,6 +209,11 @@ impl HostContext for LivenetHost {
         todo!()
     }
 
+    fn gas_profile(&self) -> GasProfile {
+        // The node does not report the gas used by host functions, so the profile is empty.
+        GasProfile::default()
+    }
+
     fn last_call_gas_cost(&self) -> u64 {
         // Todo: implement
         0
@@ -203,6 +231,11 @@ impl HostContext for LivenetHost {
     }
 
     fn transfer(&self, to: Address, amount: U512) -> OdraResult<()> {
+        if to.is_contract() {
+            return Err(OdraError::ExecutionError(
+                ExecutionError::TransferToContract
+            ));
+        }
         let rt = Runtime::new().unwrap();
         let timestamp = Timestamp::now();
         let client = self.casper_client.borrow_mut();
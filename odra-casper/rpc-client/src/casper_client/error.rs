@@ -99,6 +99,8 @@ fn get_internal_error_name(error_num: u16) -> (String, OdraError) {
         ExecutionError::MissingArg,
         ExecutionError::MissingAddress,
         ExecutionError::OutOfGas,
+        ExecutionError::WriteInView,
+        ExecutionError::UnknownEntrypoint,
         ExecutionError::MaxUserError,
         ExecutionError::UserErrorTooHigh
     )
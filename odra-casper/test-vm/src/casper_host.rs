@@ -1,4 +1,4 @@
-use odra_core::{prelude::*, GasReport};
+use odra_core::{prelude::*, GasProfile, GasReport};
 use std::cell::RefCell;
 use std::env;
 use std::path::PathBuf;
@@ -51,10 +51,22 @@ impl HostContext for CasperHost {
         self.vm.borrow().get_account(index)
     }
 
+    fn new_account(&self) -> Address {
+        self.vm.borrow_mut().new_account()
+    }
+
+    fn account(&self, label: &str) -> Address {
+        self.vm.borrow_mut().account(label)
+    }
+
     fn balance_of(&self, address: &Address) -> U512 {
         self.vm.borrow().balance_of(address)
     }
 
+    fn set_balance(&self, address: &Address, amount: U512) {
+        self.vm.borrow_mut().set_balance(address, amount)
+    }
+
     fn advance_block_time(&self, time_diff: u64) {
         self.vm.borrow_mut().advance_block_time(time_diff)
     }
@@ -119,6 +131,17 @@ impl HostContext for CasperHost {
         }
     }
 
+    fn install_wasm(
+        &self,
+        wasm_path: &str,
+        args: RuntimeArgs,
+        package_hash_key: &str
+    ) -> OdraResult<Address> {
+        self.vm
+            .borrow_mut()
+            .install_wasm(wasm_path, args, package_hash_key)
+    }
+
     fn register_contract(
         &self,
         address: Address,
@@ -136,6 +159,10 @@ impl HostContext for CasperHost {
         self.vm.borrow().gas_report().clone()
     }
 
+    fn gas_profile(&self) -> GasProfile {
+        self.vm.borrow().gas_profile().clone()
+    }
+
     fn last_call_gas_cost(&self) -> u64 {
         self.vm.borrow().last_call_gas_cost()
     }
@@ -149,6 +176,11 @@ impl HostContext for CasperHost {
     }
 
     fn transfer(&self, to: Address, amount: U512) -> OdraResult<()> {
+        if to.is_contract() && self.vm.borrow().has_entry_point(&to, RECEIVE_ENTRY_POINT) {
+            let call_def =
+                CallDef::new(RECEIVE_ENTRY_POINT, true, RuntimeArgs::new()).with_amount(amount);
+            return self.call_contract(&to, call_def, true).map(|_| ());
+        }
         self.vm.borrow_mut().transfer(to, amount)
     }
 }
@@ -9,3 +9,12 @@ mod vm;
 
 pub use casper_host::CasperHost;
 pub use vm::casper_vm::CasperVm;
+pub use vm::casper_vm_config::{CasperVmConfig, DEFAULT_MAX_DEPLOY_SIZE};
+
+/// Gas costs configuration types used by [CasperVmConfig].
+pub mod costs {
+    pub use casper_execution_engine::shared::{
+        host_function_costs::HostFunctionCosts, opcode_costs::OpcodeCosts,
+        storage_costs::StorageCosts, system_config::SystemConfig, wasm_config::WasmConfig
+    };
+}
@@ -10,29 +10,41 @@ use casper_engine_test_support::{
     DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PAYMENT
 };
 use casper_event_standard::try_full_name_from_bytes;
-use odra_core::{casper_event_standard, DeployReport, GasReport};
+use casper_execution_engine::core::engine_state::{
+    deploy_item::DeployItem, genesis::ExecConfigBuilder, ExecuteRequest
+};
+use casper_execution_engine::storage::global_state::in_memory::InMemoryGlobalState;
+use odra_core::{casper_event_standard, DeployReport, GasCategory, GasProfile, GasReport};
 use std::rc::Rc;
 
+use super::casper_vm_config::CasperVmConfig;
+
 use casper_execution_engine::core::engine_state::{self, GenesisAccount, RunGenesisRequest};
+use casper_execution_engine::shared::{additive_map::AdditiveMap, transform::Transform};
 use odra_core::casper_types::account::{Account, AccountHash};
 use odra_core::casper_types::bytesrepr::{Bytes, ToBytes};
 use odra_core::casper_types::{bytesrepr::FromBytes, CLTyped, PublicKey, RuntimeArgs, U512};
 use odra_core::casper_types::{
-    runtime_args, ApiError, BlockTime, Contract, ContractHash, ContractPackageHash, Key, Motes,
-    SecretKey, StoredValue, URef
+    runtime_args, ApiError, BlockTime, CLValue, Contract, ContractHash, ContractPackageHash, Key,
+    Motes, ProtocolVersion, SecretKey, StoredValue, URef
 };
 use odra_core::consts;
 use odra_core::consts::*;
-use odra_core::crypto::generate_key_pairs;
+use odra_core::crypto::{
+    generate_indexed_key_pair, generate_key_pairs, generate_labelled_key_pair
+};
 use odra_core::entry_point_callback::EntryPointsCaller;
 use odra_core::prelude::*;
 use odra_core::EventError;
 use odra_core::VmError;
 use odra_core::{
-    host::{HostContext, HostEnv},
+    host::{GenesisBalances, HostContext, HostEnv},
     CallDef, ContractEnv
 };
 
+/// The balance of the genesis account funding the accounts created after genesis.
+const FAUCET_BALANCE: u128 = u128::MAX;
+
 /// Casper virtual machine utilizing [InMemoryWasmTestBuilder].
 pub struct CasperVm {
     accounts: Vec<Address>,
@@ -44,13 +56,34 @@ pub struct CasperVm {
     error: Option<OdraError>,
     attached_value: U512,
     gas_used: BTreeMap<AccountHash, U512>,
-    gas_report: GasReport
+    // Gas paid by an account since its balance was last set, reported as a part of its balance.
+    gas_offsets: BTreeMap<AccountHash, U512>,
+    gas_report: GasReport,
+    gas_profile: GasProfile,
+    contract_names: BTreeMap<Address, String>,
+    foreign_contracts: BTreeSet<Address>,
+    labelled_accounts: BTreeMap<String, Address>,
+    faucet: AccountHash,
+    default_balance: U512,
+    gas_price: u64,
+    max_deploy_size: u32,
+    protocol_version: ProtocolVersion
 }
 
 impl CasperVm {
     /// Creates a new instance with predefined accounts.
     pub fn new() -> Rc<RefCell<Self>> {
-        Rc::new(RefCell::new(Self::new_instance()))
+        Self::new_with_config(CasperVmConfig::default())
+    }
+
+    /// Creates a new instance with predefined accounts having the given balances.
+    pub fn new_with_balances(genesis_balances: GenesisBalances) -> Rc<RefCell<Self>> {
+        Self::new_with_config(CasperVmConfig::default().with_genesis_balances(genesis_balances))
+    }
+
+    /// Creates a new instance configured with the given chainspec, gas costs and genesis balances.
+    pub fn new_with_config(config: CasperVmConfig) -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Self::new_instance(config)))
     }
 
     /// Read a ContractPackageHash of a given name, from the active account.
@@ -78,6 +111,52 @@ impl CasperVm {
         self.accounts[index]
     }
 
+    /// Creates a new account with the default balance.
+    pub fn new_account(&mut self) -> Address {
+        let (address, key_pair) = generate_indexed_key_pair(self.accounts.len());
+        self.add_account(address, key_pair);
+        address
+    }
+
+    /// Gets the address of the account with the given label, creates the account if it does not exist.
+    pub fn account(&mut self, label: &str) -> Address {
+        if let Some(address) = self.labelled_accounts.get(label) {
+            return *address;
+        }
+        let (address, key_pair) = generate_labelled_key_pair(label);
+        self.add_account(address, key_pair);
+        self.labelled_accounts.insert(label.to_string(), address);
+        address
+    }
+
+    /// Overwrites the balance of the given address.
+    ///
+    /// The accepted value can be either an [Address::Account] or [Address::Contract].
+    /// A contract must have a purse, what means it has already received some tokens.
+    pub fn set_balance(&mut self, address: &Address, amount: U512) {
+        let purse = match address {
+            Address::Account(account_hash) => {
+                self.gas_offsets.remove(account_hash);
+                self.context
+                    .get_account(*account_hash)
+                    .unwrap()
+                    .main_purse()
+            }
+            Address::Contract(contract_package_hash) => {
+                let contract_hash = self.get_contract_package_hash(contract_package_hash);
+                *self
+                    .context
+                    .get_contract(contract_hash)
+                    .unwrap()
+                    .named_keys()
+                    .get(consts::CONTRACT_MAIN_PURSE)
+                    .and_then(|key| key.as_uref())
+                    .unwrap_or_else(|| panic!("Contract {:?} has no purse", address))
+            }
+        };
+        self.write_purse_balance(purse, amount);
+    }
+
     /// Advances the block time by the specified time difference.
     pub fn advance_block_time(&mut self, time_diff: u64) {
         self.block_time += time_diff
@@ -129,7 +208,7 @@ impl CasperVm {
         let contract_package_hash = contract_address.as_contract_package_hash().unwrap();
         let contract_hash: ContractHash = self.get_contract_package_hash(contract_package_hash);
 
-        self.events_length(&contract_hash)
+        self.events_length(contract_address, &contract_hash)
     }
 
     /// Attaches a value to the next call.
@@ -140,6 +219,8 @@ impl CasperVm {
     /// Calls a contract with the specified address, call definition, and proxy usage flag.
     ///
     /// If the proxy usage flag is set to true, then the contract will be called via a proxy caller.
+    /// A call of an entry point the contract does not define is redirected to the fallback entry point
+    /// (`#[odra(fallback)]`), if the contract defines one.
     pub fn call_contract(
         &mut self,
         address: &Address,
@@ -150,6 +231,14 @@ impl CasperVm {
         let hash = *address
             .as_contract_package_hash()
             .expect("Contract hash expected");
+        let call_def = if !self.has_entry_point(address, call_def.entry_point())
+            && self.has_entry_point(address, FALLBACK_ENTRY_POINT)
+        {
+            CallDef::new(FALLBACK_ENTRY_POINT, true, call_def.args().clone())
+                .with_amount(call_def.amount())
+        } else {
+            call_def
+        };
 
         let deploy_item = if use_proxy {
             let session_code =
@@ -172,6 +261,7 @@ impl CasperVm {
                 .with_authorization_keys(&[self.active_account_hash()])
                 .with_address(self.active_account_hash())
                 .with_session_bytes(session_code, args)
+                .with_gas_price(self.gas_price)
                 .with_deploy_hash(self.next_hash())
                 .build()
         } else {
@@ -185,13 +275,17 @@ impl CasperVm {
                     call_def.entry_point(),
                     call_def.args().clone()
                 )
+                .with_gas_price(self.gas_price)
                 .with_deploy_hash(self.next_hash())
                 .build()
         };
 
-        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy_item)
-            .with_block_time(self.block_time)
-            .build();
+        if let Err(error) = self.check_deploy_size(&deploy_item) {
+            self.attached_value = U512::zero();
+            self.error = Some(error.clone());
+            self.panic_with_error(error, call_def.entry_point(), hash);
+        }
+        let execute_request = self.execute_request(deploy_item);
         self.context.exec(execute_request).commit();
         self.collect_gas();
         self.gas_report.push(DeployReport::ContractCall {
@@ -199,6 +293,16 @@ impl CasperVm {
             contract_address: *address,
             call_def: call_def.clone()
         });
+        let frame = format!(
+            "{}::{}",
+            self.contract_name(address),
+            call_def.entry_point()
+        );
+        self.gas_profile.record(
+            vec![frame],
+            GasCategory::Execution,
+            self.last_call_gas_cost()
+        );
 
         self.attached_value = U512::zero();
         if let Some(error) = self.context.get_error() {
@@ -226,17 +330,58 @@ impl CasperVm {
             .unwrap();
 
         let result = self.deploy_contract(&wasm_path, &init_args);
-        if let Some(error) = result {
-            let odra_error = parse_error(error);
+        if let Some(odra_error) = result {
             self.error = Some(odra_error.clone());
             panic!("Revert: Contract deploy failed {:?}", odra_error);
         } else {
             let contract_package_hash =
                 self.contract_package_hash_from_name(&package_hash_key_name);
-            contract_package_hash.try_into().unwrap()
+            let address: Address = contract_package_hash.try_into().unwrap();
+            self.contract_names.insert(address, name.to_string());
+            address
         }
     }
 
+    /// Installs a wasm file not built with Odra, with the specified runtime arguments.
+    ///
+    /// The installer is expected to store the contract package hash or the contract hash
+    /// under `package_hash_key` in the named keys of the active account.
+    pub fn install_wasm(
+        &mut self,
+        wasm_path: &str,
+        args: RuntimeArgs,
+        package_hash_key: &str
+    ) -> OdraResult<Address> {
+        if let Some(odra_error) = self.deploy_contract(wasm_path, &args) {
+            self.error = Some(odra_error.clone());
+            return Err(odra_error);
+        }
+
+        let account = self
+            .context
+            .get_account(self.active_account_hash())
+            .unwrap();
+        let hash = account
+            .named_keys()
+            .get(package_hash_key)
+            .and_then(|key| key.into_hash())
+            .ok_or_else(|| {
+                OdraError::VmError(VmError::Other(format!(
+                    "Contract installed from {} has not stored a hash under {}",
+                    wasm_path, package_hash_key
+                )))
+            })?;
+        let package_hash = match self.context.get_contract(ContractHash::new(hash)) {
+            Some(contract) => contract.contract_package_hash(),
+            None => ContractPackageHash::new(hash)
+        };
+        let address = Address::Contract(package_hash);
+        self.contract_names
+            .insert(address, Self::wasm_name(wasm_path));
+        self.foreign_contracts.insert(address);
+        Ok(address)
+    }
+
     /// Create a new instance with predefined accounts.
     pub fn active_account_hash(&self) -> AccountHash {
         *self.active_account.as_account_hash().unwrap()
@@ -256,6 +401,11 @@ impl CasperVm {
     ///
     /// Results an OdraError if the transfer fails.
     pub fn transfer(&mut self, to: Address, amount: U512) -> OdraResult<()> {
+        if to.is_contract() {
+            return Err(OdraError::ExecutionError(
+                ExecutionError::TransferToContract
+            ));
+        }
         let deploy_item = DeployItemBuilder::new()
             .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
             .with_transfer_args(runtime_args! {
@@ -265,12 +415,12 @@ impl CasperVm {
             })
             .with_authorization_keys(&[self.active_account_hash()])
             .with_address(self.active_account_hash())
+            .with_gas_price(self.gas_price)
             .with_deploy_hash(self.next_hash())
             .build();
 
-        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy_item)
-            .with_block_time(self.block_time)
-            .build();
+        self.check_deploy_size(&deploy_item)?;
+        let execute_request = self.execute_request(deploy_item);
         self.context.exec(execute_request).commit();
 
         if let Some(error) = self.context.get_error() {
@@ -315,10 +465,22 @@ impl CasperVm {
     }
 
     /// Returns the report of the gas used during the whole lifetime of the CasperVM.
+    ///
+    /// The gas is measured per deploy, see [CasperVm::gas_profile] for the limitations.
     pub fn gas_report(&self) -> &GasReport {
         &self.gas_report
     }
 
+    /// Returns the gas profile of all the deploys executed so far.
+    ///
+    /// The execution engine does not report the gas used by host functions, so the gas
+    /// of every deploy is attributed as a whole to the called entry point: the profile
+    /// has a single frame per deploy, nested calls are not visible and all the gas of a call
+    /// is in the `Execution` category. Use the OdraVm to profile nested calls and host operations.
+    pub fn gas_profile(&self) -> &GasProfile {
+        &self.gas_profile
+    }
+
     /// Returns the public key that corresponds to the given Account Address.
     pub fn public_key(&self, address: &Address) -> PublicKey {
         let (_, public_key) = self.key_pairs.get(address).unwrap();
@@ -353,12 +515,68 @@ impl CasperVm {
     }
 
     fn collect_gas(&mut self) {
+        let account_hash = *self.active_account.as_account_hash().unwrap();
+        *self.gas_used.entry(account_hash).or_insert_with(U512::zero) += *DEFAULT_PAYMENT;
         *self
-            .gas_used
-            .entry(*self.active_account.as_account_hash().unwrap())
+            .gas_offsets
+            .entry(account_hash)
             .or_insert_with(U512::zero) += *DEFAULT_PAYMENT;
     }
 
+    fn add_account(&mut self, address: Address, key_pair: (SecretKey, PublicKey)) {
+        // An account comes into existence on the first transfer to it, so the account is created
+        // with a transfer of a single mote from the faucet account funded at genesis. The default
+        // balance is written to the purse afterwards, so any balance, including zero, is accepted.
+        let faucet = self.faucet;
+        let deploy_item = DeployItemBuilder::new()
+            .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
+            .with_transfer_args(runtime_args! {
+                "amount" => U512::one(),
+                "target" => address,
+                "id" => Some(0u64),
+            })
+            .with_authorization_keys(&[faucet])
+            .with_address(faucet)
+            .with_gas_price(self.gas_price)
+            .with_deploy_hash(self.next_hash())
+            .build();
+        let execute_request = self.execute_request(deploy_item);
+        self.context.exec(execute_request).expect_success().commit();
+        self.set_balance(&address, self.default_balance);
+
+        self.accounts.push(address);
+        self.key_pairs.insert(address, key_pair);
+    }
+
+    fn write_purse_balance(&mut self, purse: URef, amount: U512) {
+        let mut effects = AdditiveMap::new();
+        effects.insert(
+            Key::Balance(purse.addr()),
+            Transform::Write(StoredValue::CLValue(CLValue::from_t(amount).unwrap()))
+        );
+        let post_state_hash = self.context.get_post_state_hash();
+        self.context.commit_transforms(post_state_hash, effects);
+    }
+
+    fn execute_request(&self, deploy_item: DeployItem) -> ExecuteRequest {
+        ExecuteRequestBuilder::from_deploy_item(deploy_item)
+            .with_block_time(self.block_time)
+            .with_protocol_version(self.protocol_version)
+            .build()
+    }
+
+    fn check_deploy_size(&self, deploy_item: &DeployItem) -> OdraResult<()> {
+        let size =
+            deploy_item.session.serialized_length() + deploy_item.payment.serialized_length();
+        if size > self.max_deploy_size as usize {
+            return Err(OdraError::VmError(VmError::Other(format!(
+                "Deploy size of {} bytes exceeds the limit of {} bytes",
+                size, self.max_deploy_size
+            ))));
+        }
+        Ok(())
+    }
+
     fn next_hash(&mut self) -> [u8; 32] {
         let seed = self.calls_counter;
         self.calls_counter += 1;
@@ -372,7 +590,7 @@ impl CasperVm {
         let account: Account = self.context.get_account(*account_hash).unwrap();
         let purse = account.main_purse();
         let gas_used = self
-            .gas_used
+            .gas_offsets
             .get(account_hash)
             .copied()
             .unwrap_or(U512::zero());
@@ -390,6 +608,17 @@ impl CasperVm {
             .unwrap_or_else(U512::zero)
     }
 
+    /// Returns true if the current version of the contract at the given address defines the entry point.
+    pub fn has_entry_point(&self, address: &Address, entry_point: &str) -> bool {
+        address
+            .as_contract_package_hash()
+            .and_then(|package_hash| self.context.get_contract_package(*package_hash))
+            .and_then(|package| package.current_contract_hash())
+            .and_then(|contract_hash| self.context.get_contract(contract_hash))
+            .map(|contract| contract.entry_points().has_entry_point(entry_point))
+            .unwrap_or_default()
+    }
+
     fn get_contract_package_hash(&self, contract_hash: &ContractPackageHash) -> ContractHash {
         self.context
             .get_contract_package(*contract_hash)
@@ -398,32 +627,60 @@ impl CasperVm {
             .unwrap()
     }
 
-    fn new_instance() -> Self {
-        let mut genesis_config = DEFAULT_GENESIS_CONFIG.clone();
+    fn new_instance(config: CasperVmConfig) -> Self {
+        let genesis_balances = config.genesis_balances();
+        let engine_config = config.engine_config();
+        let protocol_version = config.protocol_version();
+        let mut genesis_accounts = DEFAULT_GENESIS_CONFIG.ee_config().accounts().to_vec();
         let mut accounts: Vec<Address> = Vec::new();
         let key_pairs = generate_key_pairs(20);
         key_pairs
             .iter()
-            .for_each(|(address, (secret_key, public_key))| {
+            .enumerate()
+            .for_each(|(index, (address, (secret_key, public_key)))| {
                 accounts.push(*address);
                 let account = GenesisAccount::account(
                     public_key.clone(),
-                    Motes::new(U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE)),
+                    Motes::new(genesis_balances.balance(index)),
                     None
                 );
-                genesis_config.ee_config_mut().push_account(account);
+                genesis_accounts.push(account);
             });
+        // The faucet key is not derived from a seed shared with the user accounts.
+        let faucet_secret_key = SecretKey::ed25519_from_bytes([u8::MAX; 32]).unwrap();
+        let faucet_public_key = PublicKey::from(&faucet_secret_key);
+        let faucet = faucet_public_key.to_account_hash();
+        genesis_accounts.push(GenesisAccount::account(
+            faucet_public_key,
+            Motes::new(U512::from(FAUCET_BALANCE)),
+            None
+        ));
+
+        // The genesis uses the default settings, except for the costs taken from the chainspec.
+        let default_config = DEFAULT_GENESIS_CONFIG.ee_config();
+        let exec_config = ExecConfigBuilder::new()
+            .with_accounts(genesis_accounts)
+            .with_wasm_config(*engine_config.wasm_config())
+            .with_system_config(*engine_config.system_config())
+            .with_validator_slots(default_config.validator_slots())
+            .with_auction_delay(default_config.auction_delay())
+            .with_locked_funds_period_millis(default_config.locked_funds_period_millis())
+            .with_round_seigniorage_rate(default_config.round_seigniorage_rate())
+            .with_unbonding_delay(default_config.unbonding_delay())
+            .with_genesis_timestamp_millis(default_config.genesis_timestamp_millis())
+            .with_refund_handling(*engine_config.refund_handling())
+            .with_fee_handling(engine_config.fee_handling())
+            .build();
 
         let run_genesis_request = RunGenesisRequest::new(
             *DEFAULT_GENESIS_CONFIG_HASH,
-            genesis_config.protocol_version(),
-            genesis_config.take_ee_config(),
+            protocol_version,
+            exec_config,
             DEFAULT_CHAINSPEC_REGISTRY.clone()
         );
 
-        let chainspec_path =
-            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("resources/chainspec.toml");
-        let mut builder = InMemoryWasmTestBuilder::new_with_chainspec(chainspec_path, None);
+        let global_state = InMemoryGlobalState::empty().expect("Couldn't create global state");
+        let mut builder = InMemoryWasmTestBuilder::new(global_state, engine_config, None);
 
         builder.run_genesis(&run_genesis_request).commit();
 
@@ -436,16 +693,22 @@ impl CasperVm {
             error: None,
             attached_value: U512::zero(),
             gas_used: BTreeMap::new(),
+            gas_offsets: BTreeMap::new(),
             gas_report: GasReport::default(),
-            key_pairs
+            gas_profile: GasProfile::default(),
+            contract_names: BTreeMap::new(),
+            foreign_contracts: BTreeSet::new(),
+            key_pairs,
+            labelled_accounts: BTreeMap::new(),
+            faucet,
+            default_balance: genesis_balances.default_balance(),
+            gas_price: config.gas_price(),
+            max_deploy_size: config.max_deploy_size(),
+            protocol_version
         }
     }
 
-    fn deploy_contract(
-        &mut self,
-        wasm_path: &str,
-        args: &RuntimeArgs
-    ) -> Option<engine_state::Error> {
+    fn deploy_contract(&mut self, wasm_path: &str, args: &RuntimeArgs) -> Option<OdraError> {
         self.error = None;
         let session_code = PathBuf::from(wasm_path);
         let deploy_item = DeployItemBuilder::new()
@@ -453,30 +716,55 @@ impl CasperVm {
             .with_authorization_keys(&[self.active_account_hash()])
             .with_address(self.active_account_hash())
             .with_session_code(session_code, args.clone())
+            .with_gas_price(self.gas_price)
             .with_deploy_hash(self.next_hash())
             .build();
 
-        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy_item)
-            .with_block_time(self.block_time)
-            .build();
+        if let Err(error) = self.check_deploy_size(&deploy_item) {
+            return Some(error);
+        }
+        let execute_request = self.execute_request(deploy_item);
         let result = self.context.exec(execute_request).commit();
         self.collect_gas();
         self.gas_report.push(DeployReport::WasmDeploy {
             gas: self.last_call_contract_gas_cost(),
             file_name: wasm_path.to_string()
         });
-        self.context.get_error()
+        self.gas_profile.record(
+            vec![format!("{}::init", Self::wasm_name(wasm_path))],
+            GasCategory::Deploy,
+            self.last_call_gas_cost()
+        );
+        self.context.get_error().map(parse_error)
     }
 }
 
 impl CasperVm {
-    fn events_length(&self, contract_hash: &ContractHash) -> u32 {
-        self.context
-            .query(
-                None,
-                Key::Hash(contract_hash.value()),
-                &[String::from(consts::EVENTS_LENGTH)]
-            )
+    fn contract_name(&self, address: &Address) -> String {
+        self.contract_names
+            .get(address)
+            .cloned()
+            .unwrap_or_else(|| address.to_string())
+    }
+
+    fn wasm_name(wasm_path: &str) -> String {
+        PathBuf::from(wasm_path)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| wasm_path.to_string())
+    }
+
+    fn events_length(&self, address: &Address, contract_hash: &ContractHash) -> u32 {
+        let result = self.context.query(
+            None,
+            Key::Hash(contract_hash.value()),
+            &[String::from(consts::EVENTS_LENGTH)]
+        );
+        // Contracts not built with Odra may not implement the event standard.
+        if result.is_err() && self.foreign_contracts.contains(address) {
+            return 0;
+        }
+        result
             .unwrap()
             .as_cl_value()
             .unwrap()
@@ -580,6 +868,12 @@ fn parse_error(err: engine_state::Error) -> OdraError {
                 x if x == ExecutionError::MissingArg.code() => {
                     OdraError::ExecutionError(ExecutionError::MissingArg)
                 }
+                x if x == ExecutionError::WriteInView.code() => {
+                    OdraError::ExecutionError(ExecutionError::WriteInView)
+                }
+                x if x == ExecutionError::UnknownEntrypoint.code() => {
+                    OdraError::ExecutionError(ExecutionError::UnknownEntrypoint)
+                }
                 _ => OdraError::ExecutionError(ExecutionError::User(code))
             },
             engine_state::ExecError::InvalidContext => OdraError::VmError(VmError::InvalidContext),
@@ -597,3 +891,195 @@ fn parse_error(err: engine_state::Error) -> OdraError {
         OdraError::VmError(VmError::Other(format!("Casper EngineStateError: {}", err)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_account_management() {
+        // given a vm with a custom balance of the first account
+        let instance = CasperVm::new_with_balances(
+            GenesisBalances::new(U512::from(100_000_000_000u64))
+                .with_balance(0, U512::from(10_000_000_000u64))
+        );
+        let mut instance = instance.borrow_mut();
+        assert_eq!(
+            instance.balance_of(&instance.get_account(0)),
+            U512::from(10_000_000_000u64)
+        );
+        assert_eq!(
+            instance.balance_of(&instance.get_account(1)),
+            U512::from(100_000_000_000u64)
+        );
+
+        // when a new account is created
+        let account = instance.new_account();
+
+        // then it is funded with the default balance and available under the next index
+        assert_eq!(instance.get_account(20), account);
+        assert_eq!(
+            instance.balance_of(&account),
+            U512::from(100_000_000_000u64)
+        );
+        // and the genesis accounts are not charged for it
+        assert_eq!(
+            instance.balance_of(&instance.get_account(0)),
+            U512::from(10_000_000_000u64)
+        );
+
+        // when labelled accounts are requested
+        let alice = instance.account("alice");
+
+        // then the same label yields the same account
+        assert_eq!(instance.account("alice"), alice);
+        assert_ne!(instance.account("bob"), alice);
+        assert_ne!(alice, account);
+
+        // when the new account transfers tokens
+        instance.set_caller(account);
+        instance
+            .transfer(alice, U512::from(5_000_000_000u64))
+            .unwrap();
+
+        // then the tokens are moved by the mint
+        assert_eq!(instance.balance_of(&alice), U512::from(105_000_000_000u64));
+    }
+
+    #[test]
+    fn test_zero_default_balance() {
+        // given a vm with a zero default balance
+        let default_balance = U512::zero();
+        let instance = CasperVm::new_with_balances(GenesisBalances::new(default_balance));
+        let mut instance = instance.borrow_mut();
+
+        // when a new account is created
+        let account = instance.new_account();
+
+        // then its balance is the default balance
+        assert_eq!(instance.balance_of(&account), default_balance);
+        assert_eq!(
+            instance.balance_of(&instance.get_account(0)),
+            default_balance
+        );
+    }
+
+    #[test]
+    fn test_install_foreign_wasm() {
+        // given a counter contract written without Odra
+        let wasm_path = concat!(env!("CARGO_MANIFEST_DIR"), "/resources/counter.wasm");
+        let instance = CasperVm::new();
+        let mut instance = instance.borrow_mut();
+
+        // when it is installed
+        let address = instance
+            .install_wasm(
+                wasm_path,
+                runtime_args! { "initial_value" => 40u32 },
+                "counter_package_hash"
+            )
+            .unwrap();
+
+        // then it can be called like any other contract
+        instance.call_contract(
+            &address,
+            CallDef::new("increment", true, runtime_args! { "by" => 2u32 }),
+            false
+        );
+        let result = instance.call_contract(
+            &address,
+            CallDef::new("get", false, RuntimeArgs::new()),
+            true
+        );
+        assert_eq!(u32::from_bytes(&result).unwrap().0, 42);
+
+        // and it has no events, as it does not implement the event standard
+        assert_eq!(instance.get_events_count(&address), 0);
+
+        // when the installer stores the hash under a different name
+        let result = instance.install_wasm(
+            wasm_path,
+            runtime_args! { "initial_value" => 0u32 },
+            "unknown_key"
+        );
+
+        // then an error is returned
+        assert!(matches!(result, Err(OdraError::VmError(VmError::Other(_)))));
+    }
+
+    #[test]
+    fn test_unknown_entry_point_calls_fallback() {
+        // given a contract exporting a fallback entry point
+        let wasm_path = concat!(env!("CARGO_MANIFEST_DIR"), "/resources/counter.wasm");
+        let instance = CasperVm::new();
+        let mut instance = instance.borrow_mut();
+        let address = instance
+            .install_wasm(
+                wasm_path,
+                runtime_args! { "initial_value" => 40u32 },
+                "counter_package_hash"
+            )
+            .unwrap();
+        assert!(instance.has_entry_point(&address, FALLBACK_ENTRY_POINT));
+        assert!(!instance.has_entry_point(&address, RECEIVE_ENTRY_POINT));
+
+        // when an undefined entry point is called directly and through the proxy
+        let call_def = CallDef::new("unknown", true, RuntimeArgs::new());
+        instance.call_contract(&address, call_def.clone(), false);
+        instance.call_contract(&address, call_def, true);
+
+        // then the fallback entry point is called
+        let result = instance.call_contract(
+            &address,
+            CallDef::new("get", false, RuntimeArgs::new()),
+            true
+        );
+        assert_eq!(u32::from_bytes(&result).unwrap().0, 42);
+
+        // and a transfer to the contract fails, as it does not export a receive entry point
+        assert_eq!(
+            instance.transfer(address, U512::from(100)),
+            Err(OdraError::ExecutionError(
+                ExecutionError::TransferToContract
+            ))
+        );
+    }
+
+    #[test]
+    fn test_gas_profile_is_flat() {
+        // given an installed contract
+        let wasm_path = concat!(env!("CARGO_MANIFEST_DIR"), "/resources/counter.wasm");
+        let instance = CasperVm::new();
+        let mut instance = instance.borrow_mut();
+        let address = instance
+            .install_wasm(
+                wasm_path,
+                runtime_args! { "initial_value" => 0u32 },
+                "counter_package_hash"
+            )
+            .unwrap();
+        let deploy_gas = instance.last_call_gas_cost();
+
+        // when the contract is called
+        instance.call_contract(
+            &address,
+            CallDef::new("increment", true, runtime_args! { "by" => 1u32 }),
+            false
+        );
+        let call_gas = instance.last_call_gas_cost();
+
+        // then the gas of every deploy is attributed as a whole to a single frame
+        let samples = instance
+            .gas_profile()
+            .clone()
+            .into_iter()
+            .collect::<Vec<_>>();
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[0].stack, vec![String::from("counter::init")]);
+        assert_eq!(samples[0].category, GasCategory::Deploy);
+        assert_eq!(samples[0].gas, deploy_gas);
+        assert_eq!(samples[1].stack, vec![String::from("counter::increment")]);
+        assert_eq!(samples[1].category, GasCategory::Execution);
+        assert_eq!(samples[1].gas, call_gas);
+    }
+}
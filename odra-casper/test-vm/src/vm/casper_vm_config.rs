@@ -0,0 +1,271 @@
+//! Configuration of the [CasperVm](crate::CasperVm).
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use casper_engine_test_support::{ChainspecConfig, DEFAULT_GAS_PRICE};
+use casper_execution_engine::core::engine_state::{EngineConfig, EngineConfigBuilder};
+use casper_execution_engine::shared::{system_config::SystemConfig, wasm_config::WasmConfig};
+use odra_core::casper_types::ProtocolVersion;
+use odra_core::host::GenesisBalances;
+
+/// The maximum deploy size used if the chainspec does not define it.
+pub const DEFAULT_MAX_DEPLOY_SIZE: u32 = 1_048_576;
+
+// The same value as used by the test support crate.
+const DEFAULT_MAX_QUERY_DEPTH: u64 = 5;
+
+/// Configuration of the [CasperVm](crate::CasperVm).
+///
+/// By default, the vm uses the chainspec bundled with this crate, the default gas price
+/// and the default balances of the genesis accounts.
+///
+/// # Example
+///
+/// ```ignore
+/// let config = CasperVmConfig::new()
+///     .with_chainspec("resources/chainspec.toml")
+///     .with_gas_price(2)
+///     .with_max_deploy_size(512 * 1024);
+/// let vm = CasperVm::new_with_config(config);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CasperVmConfig {
+    chainspec_path: PathBuf,
+    wasm_config: Option<WasmConfig>,
+    system_config: Option<SystemConfig>,
+    gas_price: u64,
+    max_deploy_size: Option<u32>,
+    genesis_balances: GenesisBalances
+}
+
+impl CasperVmConfig {
+    /// Creates a new default configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the path to the chainspec file the vm is configured with.
+    ///
+    /// The chainspec defines the wasm and system costs, and the maximum deploy size.
+    pub fn with_chainspec<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.chainspec_path = path.as_ref().to_path_buf();
+        self
+    }
+
+    /// Overrides the wasm costs and limits defined in the chainspec.
+    pub fn with_wasm_config(mut self, wasm_config: WasmConfig) -> Self {
+        self.wasm_config = Some(wasm_config);
+        self
+    }
+
+    /// Overrides the system contracts costs defined in the chainspec.
+    pub fn with_system_config(mut self, system_config: SystemConfig) -> Self {
+        self.system_config = Some(system_config);
+        self
+    }
+
+    /// Sets the gas price of every deploy sent to the vm.
+    pub fn with_gas_price(mut self, gas_price: u64) -> Self {
+        self.gas_price = gas_price;
+        self
+    }
+
+    /// Overrides the maximum deploy size defined in the chainspec.
+    pub fn with_max_deploy_size(mut self, max_deploy_size: u32) -> Self {
+        self.max_deploy_size = Some(max_deploy_size);
+        self
+    }
+
+    /// Sets the balances of the genesis accounts.
+    pub fn with_genesis_balances(mut self, genesis_balances: GenesisBalances) -> Self {
+        self.genesis_balances = genesis_balances;
+        self
+    }
+
+    /// Returns the path to the chainspec file.
+    pub fn chainspec_path(&self) -> &Path {
+        &self.chainspec_path
+    }
+
+    /// Returns the gas price of deploys.
+    pub fn gas_price(&self) -> u64 {
+        self.gas_price
+    }
+
+    /// Returns the balances of the genesis accounts.
+    pub fn genesis_balances(&self) -> &GenesisBalances {
+        &self.genesis_balances
+    }
+
+    /// Returns the maximum deploy size.
+    ///
+    /// If not overridden, the value is read from the `[deploys]` section of the chainspec.
+    pub fn max_deploy_size(&self) -> u32 {
+        self.max_deploy_size.unwrap_or_else(|| {
+            self.chainspec_value("deploys", "max_deploy_size")
+                .and_then(|size| size.as_integer())
+                .map(|size| size as u32)
+                .unwrap_or(DEFAULT_MAX_DEPLOY_SIZE)
+        })
+    }
+
+    /// Returns the protocol version read from the `[protocol]` section of the chainspec.
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.chainspec_value("protocol", "version")
+            .and_then(|version| version.as_str().map(ProtocolVersion::from_str))
+            .unwrap_or_else(|| panic!("No protocol version in {:?}", self.chainspec_path))
+            .unwrap_or_else(|_| panic!("Invalid protocol version in {:?}", self.chainspec_path))
+    }
+
+    /// Builds the execution engine configuration from the chainspec and the overrides.
+    pub(crate) fn engine_config(&self) -> EngineConfig {
+        let chainspec = ChainspecConfig::from_chainspec_path(&self.chainspec_path)
+            .unwrap_or_else(|_| panic!("Couldn't load chainspec {:?}", self.chainspec_path));
+        let config = EngineConfig::from(chainspec);
+
+        EngineConfigBuilder::new()
+            .with_max_query_depth(DEFAULT_MAX_QUERY_DEPTH)
+            .with_max_associated_keys(config.max_associated_keys())
+            .with_max_runtime_call_stack_height(config.max_runtime_call_stack_height())
+            .with_minimum_delegation_amount(config.minimum_delegation_amount())
+            .with_minimum_bid_amount(config.minimum_bid_amount())
+            .with_strict_argument_checking(config.strict_argument_checking())
+            .with_vesting_schedule_period_millis(config.vesting_schedule_period_millis())
+            .with_max_delegators_per_validator(config.max_delegators_per_validator())
+            .with_wasm_config(self.wasm_config.unwrap_or(*config.wasm_config()))
+            .with_system_config(self.system_config.unwrap_or(*config.system_config()))
+            .with_refund_handling(*config.refund_handling())
+            .with_fee_handling(config.fee_handling())
+            .build()
+    }
+
+    fn chainspec_value(&self, section: &str, key: &str) -> Option<toml::Value> {
+        let chainspec = std::fs::read_to_string(&self.chainspec_path)
+            .unwrap_or_else(|_| panic!("Couldn't read {:?}", self.chainspec_path));
+        chainspec
+            .parse::<toml::Value>()
+            .unwrap_or_else(|_| panic!("Couldn't parse {:?}", self.chainspec_path))
+            .get(section)
+            .and_then(|section| section.get(key))
+            .cloned()
+    }
+}
+
+impl Default for CasperVmConfig {
+    fn default() -> Self {
+        Self {
+            chainspec_path: PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("resources/chainspec.toml"),
+            wasm_config: None,
+            system_config: None,
+            gas_price: DEFAULT_GAS_PRICE,
+            max_deploy_size: None,
+            genesis_balances: GenesisBalances::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CasperVm;
+    use odra_core::casper_types::U512;
+    use odra_core::prelude::OdraError;
+    use odra_core::VmError;
+
+    // Writes a copy of the bundled chainspec with the given replacements applied.
+    fn custom_chainspec(name: &str, replacements: &[(&str, &str)]) -> PathBuf {
+        let default_path = CasperVmConfig::default().chainspec_path().to_path_buf();
+        let mut chainspec = std::fs::read_to_string(default_path).unwrap();
+        for (from, to) in replacements {
+            assert!(
+                chainspec.contains(from),
+                "{} not found in the chainspec",
+                from
+            );
+            chainspec = chainspec.replace(from, to);
+        }
+        let path = std::env::temp_dir().join(format!("odra_{}_chainspec.toml", name));
+        std::fs::write(&path, chainspec).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_default_config() {
+        let config = CasperVmConfig::default();
+        assert_eq!(
+            config.protocol_version(),
+            ProtocolVersion::from_parts(1, 5, 8)
+        );
+        assert_eq!(config.max_deploy_size(), 1_048_576);
+        assert_eq!(config.gas_price(), DEFAULT_GAS_PRICE);
+    }
+
+    #[test]
+    fn test_custom_chainspec_is_loaded_and_enforced() {
+        // given a chainspec with a different protocol version and transfer cost
+        let path = custom_chainspec(
+            "custom",
+            &[
+                ("version = '1.5.8'", "version = '1.6.0'"),
+                (
+                    "wasmless_transfer_cost = 100_000_000",
+                    "wasmless_transfer_cost = 200_000_000"
+                )
+            ]
+        );
+        let config = CasperVmConfig::new().with_chainspec(path);
+        assert_eq!(
+            config.protocol_version(),
+            ProtocolVersion::from_parts(1, 6, 0)
+        );
+        assert_eq!(
+            config
+                .engine_config()
+                .system_config()
+                .wasmless_transfer_cost(),
+            200_000_000
+        );
+
+        // when a transfer is executed on a vm booted with the chainspec
+        let vm = CasperVm::new_with_config(config);
+        let mut vm = vm.borrow_mut();
+        let recipient = vm.get_account(1);
+        vm.transfer(recipient, U512::from(5_000_000_000u64))
+            .unwrap();
+
+        // then the custom cost is charged
+        assert_eq!(vm.last_call_contract_gas_cost(), U512::from(200_000_000));
+    }
+
+    #[test]
+    fn test_max_deploy_size_is_enforced() {
+        // given a chainspec with a tiny max deploy size
+        let path = custom_chainspec(
+            "small_deploys",
+            &[("max_deploy_size = 1_048_576", "max_deploy_size = 16")]
+        );
+        let config = CasperVmConfig::new().with_chainspec(&path);
+        assert_eq!(config.max_deploy_size(), 16);
+
+        // when a transfer is executed
+        let vm = CasperVm::new_with_config(config);
+        let mut vm = vm.borrow_mut();
+        let recipient = vm.get_account(1);
+        let result = vm.transfer(recipient, U512::from(5_000_000_000u64));
+
+        // then the deploy is rejected
+        assert!(matches!(result, Err(OdraError::VmError(VmError::Other(_)))));
+
+        // when the limit is overridden
+        let vm = CasperVm::new_with_config(
+            CasperVmConfig::new()
+                .with_chainspec(&path)
+                .with_max_deploy_size(1_024)
+        );
+        let mut vm = vm.borrow_mut();
+
+        // then the deploy is accepted
+        assert!(vm.transfer(recipient, U512::from(5_000_000_000u64)).is_ok());
+    }
+}
@@ -1 +1,2 @@
 pub mod casper_vm;
+pub mod casper_vm_config;
@@ -26,8 +26,9 @@ use odra_core::casper_types::{
     bytesrepr::{Bytes, FromBytes, ToBytes},
     contracts::NamedKeys,
     system::CallStackElement,
-    ApiError, CLTyped, CLValue, ContractPackageHash, ContractVersion, EntryPoints, Key,
-    RuntimeArgs, URef, DICTIONARY_ITEM_KEY_MAX_LENGTH, U512, UREF_SERIALIZED_LENGTH
+    ApiError, CLTyped, CLValue, ContractPackageHash, ContractVersion, EntryPoint, EntryPointAccess,
+    EntryPoints, Key, RuntimeArgs, URef, DICTIONARY_ITEM_KEY_MAX_LENGTH, U512,
+    UREF_SERIALIZED_LENGTH
 };
 use odra_core::consts;
 use odra_core::{
@@ -112,6 +113,50 @@ pub fn install_contract(
     contract_package_hash
 }
 
+/// Installs a new contract from within a contract.
+///
+/// The new contract shares the code with the current contract, so the wasm of the current contract
+/// must export the given entry points. The contract package is not stored under a named key and
+/// the access to the package is not retained, so the contract cannot be upgraded.
+///
+/// Along with the contract, named keys with events and state are created.
+pub fn new_contract(
+    entry_points: EntryPoints,
+    events: Schemas,
+    init_args: Option<RuntimeArgs>
+) -> ContractPackageHash {
+    let (contract_package_hash, _) = storage::create_contract_package_at_hash();
+    let named_keys = child_named_keys(events);
+    storage::add_contract_version(contract_package_hash, entry_points, named_keys);
+
+    if let Some(args) = init_args {
+        let init_access = create_constructor_group(contract_package_hash);
+        let _: () = runtime::call_versioned_contract(contract_package_hash, None, "init", args);
+        revoke_access_to_constructor_group(contract_package_hash, init_access);
+    }
+
+    contract_package_hash
+}
+
+/// Adds the entry points of a contract installed by the current contract to the given entry points.
+///
+/// The added entry points cannot be called on the current contract, they are listed only to keep
+/// them exported from the shared wasm. Entry points already defined by the current contract are not replaced.
+pub fn add_template_entry_points(entry_points: &mut EntryPoints, template: EntryPoints) {
+    for entry_point in template.take_entry_points() {
+        if entry_points.has_entry_point(entry_point.name()) {
+            continue;
+        }
+        entry_points.add_entry_point(EntryPoint::new(
+            entry_point.name(),
+            entry_point.args().to_vec(),
+            entry_point.ret().clone(),
+            EntryPointAccess::Groups(vec![]),
+            entry_point.entry_point_type()
+        ));
+    }
+}
+
 /// Stops a contract execution and reverts the state with a given error.
 #[inline(always)]
 pub fn revert(error: u16) -> ! {
@@ -326,15 +371,20 @@ fn get_dictionary(name: &str) -> URef {
 }
 
 /// Transfers native token from the contract caller to the given address.
+///
+/// A transfer to a contract calls its receive entry point with the amount attached.
+/// If the contract does not define a receive entry point, the execution reverts.
 pub fn transfer_tokens(to: &Address, amount: &U512) {
-    let main_purse = get_or_create_main_purse();
-
     match to {
         Address::Account(account) => {
+            let main_purse = get_or_create_main_purse();
             transfer_from_purse_to_account(main_purse, *account, *amount, None).unwrap_or_revert();
         }
-        // todo: Why?
-        Address::Contract(_) => revert(ExecutionError::TransferToContract.code())
+        Address::Contract(_) => {
+            let call_def = CallDef::new(consts::RECEIVE_ENTRY_POINT, true, RuntimeArgs::new())
+                .with_amount(*amount);
+            call_contract(*to, call_def);
+        }
     };
 }
 
@@ -343,6 +393,17 @@ pub fn emit_event(event: &Bytes) {
     casper_event_standard::emit_bytes(event.clone())
 }
 
+/// Prints a debug message using the host `print` function.
+///
+/// The host function is available in the test environment only, so the message is printed
+/// only if the `debug-log` feature is enabled.
+pub fn print(message: &str) {
+    #[cfg(feature = "debug-log")]
+    runtime::print(message);
+    #[cfg(not(feature = "debug-log"))]
+    let _ = message;
+}
+
 /// Gets the immediate session caller of the current execution.
 ///
 /// This function ensures that only session code can execute this function, and disallows stored
@@ -537,6 +598,43 @@ fn initial_named_keys(schemas: Schemas) -> NamedKeys {
     named_keys
 }
 
+/// Creates the named keys of a contract installed by another contract.
+///
+/// Unlike [initial_named_keys], the dictionaries are not stored under the named keys
+/// of the current contract, as they may already be taken by the current contract.
+fn child_named_keys(schemas: Schemas) -> NamedKeys {
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(
+        String::from(consts::STATE_KEY),
+        Key::URef(new_unnamed_dictionary())
+    );
+    named_keys.insert(
+        String::from(casper_event_standard::EVENTS_DICT),
+        Key::URef(new_unnamed_dictionary())
+    );
+    named_keys.insert(
+        String::from(casper_event_standard::EVENTS_LENGTH),
+        Key::URef(storage::new_uref(0u32))
+    );
+    named_keys.insert(
+        String::from(casper_event_standard::CES_VERSION_KEY),
+        Key::URef(storage::new_uref(casper_event_standard::CES_VERSION))
+    );
+    named_keys.insert(
+        String::from(casper_event_standard::EVENTS_SCHEMA),
+        Key::URef(storage::new_uref(schemas))
+    );
+    named_keys
+}
+
+fn new_unnamed_dictionary() -> URef {
+    let mut value_size = MaybeUninit::uninit();
+    let ret = unsafe { ext_ffi::casper_new_dictionary(value_size.as_mut_ptr()) };
+    api_error::result_from(ret).unwrap_or_revert();
+    let value_bytes = read_host_buffer(unsafe { value_size.assume_init() }).unwrap_or_revert();
+    bytesrepr::deserialize(value_bytes).unwrap_or_revert()
+}
+
 fn deserialize_contract_result(bytes_written: usize) -> Vec<u8> {
     if bytes_written == 0 {
         // If no bytes were written, the host buffer hasn't been set and hence shouldn't be read.
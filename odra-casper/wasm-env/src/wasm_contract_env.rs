@@ -4,7 +4,8 @@ use casper_types::bytesrepr::ToBytes;
 use casper_types::U512;
 use odra_core::casper_types;
 use odra_core::casper_types::bytesrepr::Bytes;
-use odra_core::casper_types::{CLType, CLValue, BLAKE2B_DIGEST_LENGTH};
+use odra_core::casper_types::{CLType, CLValue, RuntimeArgs, BLAKE2B_DIGEST_LENGTH};
+use odra_core::factory::ContractTemplate;
 use odra_core::prelude::*;
 use odra_core::{ContractContext, ContractEnv};
 
@@ -53,6 +54,18 @@ impl ContractContext for WasmContractEnv {
         host_functions::call_contract(address, call_def)
     }
 
+    #[cfg(target_arch = "wasm32")]
+    fn new_contract(&self, template: &ContractTemplate, init_args: Option<RuntimeArgs>) -> Address {
+        let contract_package_hash =
+            host_functions::new_contract(template.entry_points(), template.events(), init_args);
+        Address::Contract(contract_package_hash)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn new_contract(&self, template: &ContractTemplate, init_args: Option<RuntimeArgs>) -> Address {
+        unimplemented!("Contract templates are available in wasm only")
+    }
+
     fn get_block_time(&self) -> u64 {
         host_functions::get_block_time()
     }
@@ -98,6 +111,10 @@ impl ContractContext for WasmContractEnv {
     fn hash(&self, bytes: &[u8]) -> [u8; BLAKE2B_DIGEST_LENGTH] {
         host_functions::blake2b(bytes)
     }
+
+    fn log(&self, message: &str) {
+        host_functions::print(message)
+    }
 }
 
 impl WasmContractEnv {
@@ -118,9 +118,9 @@ impl TryFrom<&'_ ModuleImplIR> for ContractRefTraitImplItem {
 }
 
 #[derive(syn_derive::ToTokens)]
-struct ContractRefImplItem {
+pub struct ContractRefImplItem {
     impl_token: syn::token::Impl,
-    trait_name: Option<syn::Ident>,
+    trait_name: Option<syn::Path>,
     for_token: Option<syn::token::For>,
     ref_ident: syn::Ident,
     #[syn(braced)]
@@ -135,7 +135,7 @@ impl TryFrom<&'_ ModuleImplIR> for ContractRefImplItem {
 
     fn try_from(module: &'_ ModuleImplIR) -> Result<Self, Self::Error> {
         // If module implements a trait, set trait name
-        let trait_name = module.impl_trait_ident();
+        let trait_name = module.impl_trait_path();
         let for_token: Option<syn::token::For> = match module.is_trait_impl() {
             true => Some(Default::default()),
             false => None
@@ -291,6 +291,68 @@ mod ref_item_tests {
         test_utils::assert_eq(actual, expected);
     }
 
+    #[test]
+    fn contract_ref_view() {
+        let module = test_utils::mock::module_view();
+        let expected = quote! {
+            /// [Counter] Contract Ref.
+            pub struct CounterContractRef {
+                env: Rc<odra::ContractEnv>,
+                address: Address,
+            }
+
+            impl odra::ContractRef for CounterContractRef {
+                fn new(env: Rc<odra::ContractEnv>, address: Address) -> Self {
+                    Self { env, address }
+                }
+
+                fn address(&self) -> &Address {
+                    &self.address
+                }
+            }
+
+            impl CounterContractRef {
+                pub fn count(&self) -> u32 {
+                    self.env.call_contract(
+                        self.address,
+                        odra::CallDef::new(
+                            odra::prelude::string::String::from("count"),
+                            false,
+                            {
+                                let mut named_args = odra::casper_types::RuntimeArgs::new();
+                                named_args
+                            }
+                        ).into_view(),
+                    )
+                }
+
+                pub fn log(&self) {
+                    self.env.call_contract(
+                        self.address,
+                        odra::CallDef::new(
+                            odra::prelude::string::String::from("log"),
+                            false,
+                            {
+                                let mut named_args = odra::casper_types::RuntimeArgs::new();
+                                named_args
+                            }
+                        ),
+                    )
+                }
+            }
+
+            #[automatically_derived]
+            #[cfg(not(target_arch = "wasm32"))]
+            impl odra::schema::SchemaErrors for CounterContractRef {}
+
+            #[automatically_derived]
+            #[cfg(not(target_arch = "wasm32"))]
+            impl odra::schema::SchemaEvents for CounterContractRef {}
+        };
+        let actual = RefItem::try_from(&module).unwrap();
+        test_utils::assert_eq(actual, expected);
+    }
+
     #[test]
     fn contract_trait_impl_ref() {
         let module = test_utils::mock::module_trait_impl();
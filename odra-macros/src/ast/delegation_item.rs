@@ -0,0 +1,256 @@
+use crate::ir::ModuleImplIR;
+use proc_macro2::{Literal, TokenStream};
+use quote::{quote, ToTokens};
+
+/// A macro listing the signatures of the module entry points, used by the modules delegating
+/// all the entry points of the module.
+///
+/// The macro is exported under the name of the module, so it is imported along with the module.
+/// It passes the signatures to the given callback macro, which resolves the delegation.
+/// The types in the signatures are projected through `odra::module::DelegatedType`
+/// implemented for the module, so they resolve wherever the module is in scope.
+pub struct DelegationMacroItem {
+    code: TokenStream
+}
+
+impl ToTokens for DelegationMacroItem {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(self.code.clone());
+    }
+}
+
+impl TryFrom<&'_ ModuleImplIR> for DelegationMacroItem {
+    type Error = syn::Error;
+
+    fn try_from(module: &'_ ModuleImplIR) -> Result<Self, Self::Error> {
+        let macro_ident = match module.delegation_macro_ident()? {
+            Some(ident) => ident,
+            None => return Ok(Self { code: quote!() })
+        };
+        let module_ident = module.module_ident()?;
+        let mut types = Vec::new();
+        let entrypoints = module
+            .delegated_entrypoints()?
+            .into_iter()
+            .map(|mut entrypoint| {
+                delegated_signature(&mut entrypoint.sig, &mut types);
+                entrypoint
+            })
+            .collect::<Vec<_>>();
+        let delegated_types = types.iter().enumerate().map(|(index, ty)| {
+            let index = Literal::usize_unsuffixed(index);
+            quote!(
+                impl odra::module::DelegatedType<#index> for #module_ident {
+                    type Type = #ty;
+                }
+            )
+        });
+
+        Ok(Self {
+            code: quote!(
+                #(#delegated_types)*
+
+                #[doc(hidden)]
+                #[macro_export]
+                macro_rules! #macro_ident {
+                    ($callback:path [$($module:tt)*] { $($args:tt)* }) => {
+                        $callback! { $($args)* { #(#entrypoints)* } }
+                    };
+                }
+
+                #[doc(hidden)]
+                #[allow(unused_imports)]
+                pub use #macro_ident as #module_ident;
+            )
+        })
+    }
+}
+
+/// Replaces the types of the arguments and the return type with the types projected through
+/// the delegated module, collecting the distinct types.
+fn delegated_signature(sig: &mut syn::Signature, types: &mut Vec<syn::Type>) {
+    for input in sig.inputs.iter_mut() {
+        if let syn::FnArg::Typed(pat_ty) = input {
+            delegated_type(&mut pat_ty.ty, types);
+        }
+    }
+    if let syn::ReturnType::Type(_, ty) = &mut sig.output {
+        delegated_type(ty, types);
+    }
+}
+
+fn delegated_type(ty: &mut syn::Type, types: &mut Vec<syn::Type>) {
+    if let syn::Type::Reference(reference) = ty {
+        return delegated_type(&mut reference.elem, types);
+    }
+    let index = match types.iter().position(|t| t == ty) {
+        Some(index) => index,
+        None => {
+            types.push(ty.clone());
+            types.len() - 1
+        }
+    };
+    let index = Literal::usize_unsuffixed(index);
+    *ty = syn::Type::Verbatim(
+        quote!(<$($module)* as odra::module::DelegatedType<#index>>::Type)
+    );
+}
+
+/// A module delegating all the entry points of a submodule.
+///
+/// The macro of the submodule passes the signatures of the entry points
+/// back to the module, which is then expanded again.
+pub struct DelegatedModuleItem {
+    code: TokenStream
+}
+
+impl ToTokens for DelegatedModuleItem {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(self.code.clone());
+    }
+}
+
+impl TryFrom<(&'_ TokenStream, &'_ TokenStream)> for DelegatedModuleItem {
+    type Error = syn::Error;
+
+    fn try_from((attr, item): (&'_ TokenStream, &'_ TokenStream)) -> Result<Self, Self::Error> {
+        let module = ModuleImplIR::try_from((attr, item))?;
+        let ty = module.delegated_module()?.ok_or_else(|| {
+            syn::Error::new_spanned(item, "The module does not delegate to a submodule")
+        })?;
+
+        Ok(Self {
+            code: quote!(#ty! { odra::delegated_module [#ty] { { #attr } #item } })
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::{self, mock};
+
+    #[test]
+    fn delegation_macro() {
+        let module = mock::module_delegation();
+        let actual = DelegationMacroItem::try_from(&module).unwrap();
+        let ident = module.delegation_macro_ident().unwrap().unwrap();
+        assert!(ident.to_string().starts_with("__odra_erc20_delegation_"));
+
+        let expected = quote! {
+            impl odra::module::DelegatedType<0> for Erc20 {
+                type Type = U256;
+            }
+
+            impl odra::module::DelegatedType<1> for Erc20 {
+                type Type = Address;
+            }
+
+            impl odra::module::DelegatedType<2> for Erc20 {
+                type Type = String;
+            }
+
+            #[doc(hidden)]
+            #[macro_export]
+            macro_rules! #ident {
+                ($callback:path [$($module:tt)*] { $($args:tt)* }) => {
+                    $callback! { $($args)* {
+                        /// Returns the total supply of the token.
+                        fn total_supply(&self) -> <$($module)* as odra::module::DelegatedType<0>>::Type;
+                        /// Returns the owner of the contract.
+                        fn get_owner(&self) -> <$($module)* as odra::module::DelegatedType<1>>::Type;
+                        /// Sets the owner of the contract.
+                        fn set_owner(&mut self, new_owner: <$($module)* as odra::module::DelegatedType<1>>::Type);
+                        /// Returns the name of the token.
+                        fn name(&self) -> <$($module)* as odra::module::DelegatedType<2>>::Type;
+                        #[doc = " Delegated. See `self.metadata.symbol()` for details."]
+                        fn symbol(&self) -> <$($module)* as odra::module::DelegatedType<2>>::Type;
+                    } }
+                };
+            }
+
+            #[doc(hidden)]
+            #[allow(unused_imports)]
+            pub use #ident as Erc20;
+        };
+
+        test_utils::assert_eq(actual, expected);
+    }
+
+    #[test]
+    fn generic_module_has_no_delegation_macro() {
+        let module = mock::generic_module_impl();
+        let actual = DelegationMacroItem::try_from(&module).unwrap();
+
+        test_utils::assert_eq(actual, quote!());
+    }
+
+    #[test]
+    fn delegated_module() {
+        let attr = quote!(events = [Transfer]);
+        let item = quote! {
+            impl Erc20Wrapper {
+                pub fn init(&mut self) {}
+
+                delegate! {
+                    to self.ownable {
+                        fn get_owner(&self) -> Address;
+                    }
+                    to self.token: erc20::Erc20 except [set_owner];
+                }
+            }
+        };
+        let actual = DelegatedModuleItem::try_from((&attr, &item)).unwrap();
+
+        let expected = quote! {
+            erc20::Erc20! {
+                odra::delegated_module [erc20::Erc20] {
+                    { events = [Transfer] }
+                    impl Erc20Wrapper {
+                        pub fn init(&mut self) {}
+
+                        delegate! {
+                            to self.ownable {
+                                fn get_owner(&self) -> Address;
+                            }
+                            to self.token: erc20::Erc20 except [set_owner];
+                        }
+                    }
+                }
+            }
+        };
+
+        test_utils::assert_eq(actual, expected);
+    }
+
+    #[test]
+    fn resolve_delegated_module() {
+        let attr = quote!();
+        let entrypoints = mock::module_delegation().delegated_entrypoints().unwrap();
+        let function_names = |except: TokenStream| {
+            let item = quote! {
+                impl Erc20Wrapper {
+                    delegate! {
+                        to self.token: Erc20 #except;
+                    }
+                }
+            };
+            let module = ModuleImplIR::try_from((&attr, &item)).unwrap();
+            let item = module
+                .resolve_delegated_module(entrypoints.clone())
+                .unwrap()
+                .into_token_stream();
+            let module = ModuleImplIR::try_from((&attr, &item)).unwrap();
+            assert!(module.delegated_module().unwrap().is_none());
+            module
+                .functions()
+                .map(|functions| functions.iter().map(|f| f.name_str()).collect::<Vec<_>>())
+        };
+
+        assert_eq!(
+            function_names(quote!(except [set_owner, symbol])).unwrap(),
+            vec!["total_supply", "get_owner", "name"]
+        );
+        assert!(function_names(quote!(except [mint])).is_err());
+    }
+}
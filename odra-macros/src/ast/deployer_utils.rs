@@ -20,13 +20,15 @@ impl TryFrom<&'_ ModuleImplIR> for EntrypointsInitExpr {
     type Error = syn::Error;
 
     fn try_from(module: &'_ ModuleImplIR) -> Result<Self, Self::Error> {
-        let functions = module.functions()?;
-        
-        let entry_points = functions
+        let interfaces = module
+            .interfaces()
             .iter()
-            .map(|f| utils::expr::new_entry_point(f.name_str(), f.raw_typed_args(), f.is_payable()))
-            .collect::<Punctuated<_, syn::Token![,]>>();
-        let value_expr = utils::expr::vec(entry_points);
+            .map(|interface| {
+                let test_parts = module.interface_parts_mod_ident(interface, "test_parts")?;
+                Ok(parse_quote!(#test_parts::entry_points()))
+            })
+            .collect::<syn::Result<Vec<syn::Expr>>>()?;
+        let value_expr = utils::expr::extend_vec(entry_points_expr(module)?, &interfaces);
 
         Ok(Self {
             let_token: Default::default(),
@@ -38,6 +40,49 @@ impl TryFrom<&'_ ModuleImplIR> for EntrypointsInitExpr {
     }
 }
 
+pub fn entry_points_expr(module: &ModuleImplIR) -> syn::Result<syn::Expr> {
+    let entry_points = module
+        .functions()?
+        .iter()
+        .map(|f| {
+            let entry_point =
+                utils::expr::new_entry_point(f.name_str(), f.raw_typed_args(), f.is_payable());
+            match (f.is_receive(), f.is_fallback()) {
+                (true, _) => utils::expr::with_entry_point_kind(entry_point, "Receive"),
+                (false, true) => utils::expr::with_entry_point_kind(entry_point, "Fallback"),
+                (false, false) => entry_point
+            }
+        })
+        .collect::<Punctuated<_, syn::Token![,]>>();
+    Ok(utils::expr::vec(entry_points))
+}
+
+/// Calls the entrypoint of an interface implementation, returns `None` if the interface
+/// does not define the entrypoint.
+pub fn interface_call_fn(module: &ModuleImplIR) -> syn::Result<syn::ItemFn> {
+    let contract_env_ident = utils::ident::contract_env();
+    let ty_contract_env = utils::ty::contract_env();
+    let result_ident = utils::ident::result();
+    let branches = module
+        .functions()?
+        .iter()
+        .map(|f| FunctionCallBranch::try_from((module, f)))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(parse_quote!(
+        pub fn call(
+            #contract_env_ident: #ty_contract_env,
+            entry_point: &str
+        ) -> Option<OdraResult<odra::casper_types::bytesrepr::Bytes>> {
+            let #result_ident = match entry_point {
+                #(#branches)*
+                _ => return None
+            };
+            Some(#result_ident)
+        }
+    ))
+}
+
 #[derive(syn_derive::ToTokens)]
 pub struct EpcSignature {
     fn_token: syn::token::Fn,
@@ -98,7 +143,12 @@ impl EntrypointCallerExpr {
             .map(|f| FunctionCallBranch::try_from((module, f)))
             .map(|r| r.map(CallerBranch::Function))
             .collect::<syn::Result<_>>()?;
-        branches.push(CallerBranch::Default(DefaultBranch));
+        let interfaces = module
+            .interfaces()
+            .iter()
+            .map(|interface| module.interface_parts_mod_ident(interface, "test_parts"))
+            .collect::<syn::Result<_>>()?;
+        branches.push(CallerBranch::Default(DefaultBranch { interfaces }));
 
         Ok(parse_quote!(
             #ty_caller::new(#env_ident.clone(), #entry_points_ident, |#contract_env_ident, #call_def_ident| {
@@ -188,12 +238,29 @@ impl<'a> FunctionCallBranch {
     }
 }
 
-struct DefaultBranch;
+struct DefaultBranch {
+    interfaces: Vec<syn::Ident>
+}
 
 impl ToTokens for DefaultBranch {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        tokens.extend(quote::quote!(name => Err(OdraError::VmError(
+        let no_such_method = quote::quote!(Err(OdraError::VmError(
             odra::VmError::NoSuchMethod(odra::prelude::String::from(name))
-        ))))
+        )));
+        if self.interfaces.is_empty() {
+            tokens.extend(quote::quote!(name => #no_such_method));
+            return;
+        }
+
+        let contract_env_ident = utils::ident::contract_env();
+        let interfaces = &self.interfaces;
+        tokens.extend(quote::quote!(name => {
+            #(
+                if let Some(result) = #interfaces::call(#contract_env_ident.clone(), name) {
+                    return result;
+                }
+            )*
+            #no_such_method
+        }))
     }
 }
@@ -13,7 +13,9 @@ pub struct HasEntrypointsImplItem {
     #[syn(braced)]
     brace_token: syn::token::Brace,
     #[syn(in = brace_token)]
-    events_fn: EntrypointsFnItem
+    events_fn: EntrypointsFnItem,
+    #[syn(in = brace_token)]
+    interfaces_fn: Option<syn::ItemFn>
 }
 
 impl TryFrom<&'_ ModuleImplIR> for HasEntrypointsImplItem {
@@ -26,7 +28,8 @@ impl TryFrom<&'_ ModuleImplIR> for HasEntrypointsImplItem {
             for_token: Default::default(),
             module_ident: struct_ir.module_ident()?,
             brace_token: Default::default(),
-            events_fn: struct_ir.try_into()?
+            events_fn: struct_ir.try_into()?,
+            interfaces_fn: interfaces_fn(struct_ir)?
         })
     }
 }
@@ -46,7 +49,15 @@ impl TryFrom<&'_ ModuleImplIR> for EntrypointsFnItem {
     fn try_from(struct_ir: &'_ ModuleImplIR) -> Result<Self, Self::Error> {
         let ident_entrypoints = utils::ident::entrypoints();
         let entrypoint_ty = utils::ty::entry_point_def();
-        let expr = struct_entrypoints_expr(struct_ir)?;
+        let interfaces = struct_ir
+            .interfaces()
+            .iter()
+            .map(|interface| {
+                let exec_parts = struct_ir.interface_parts_mod_ident(interface, "exec_parts")?;
+                Ok(parse_quote!(#exec_parts::#ident_entrypoints()))
+            })
+            .collect::<syn::Result<Vec<syn::Expr>>>()?;
+        let expr = utils::expr::extend_vec(struct_entrypoints_expr(struct_ir)?, &interfaces);
         let vec_ty = utils::ty::vec_of(&entrypoint_ty);
 
         Ok(Self {
@@ -57,14 +68,31 @@ impl TryFrom<&'_ ModuleImplIR> for EntrypointsFnItem {
     }
 }
 
-fn struct_entrypoints_expr(ir: &ModuleImplIR) -> syn::Result<syn::Expr> {
+fn interfaces_fn(ir: &ModuleImplIR) -> syn::Result<Option<syn::ItemFn>> {
+    let interfaces = ir
+        .interfaces()
+        .iter()
+        .map(|interface| {
+            let ident = utils::syn::last_segment_ident(&parse_quote!(#interface))?;
+            Ok(utils::expr::string_from(ident.to_string()))
+        })
+        .collect::<syn::Result<Punctuated<syn::Expr, syn::token::Comma>>>()?;
+    if interfaces.is_empty() {
+        return Ok(None);
+    }
+    let vec_ty = utils::ty::vec_of(&utils::ty::string());
+    let expr = utils::expr::vec(interfaces);
+    Ok(Some(parse_quote!(fn interfaces() -> #vec_ty { #expr })))
+}
+
+pub fn struct_entrypoints_expr(ir: &ModuleImplIR) -> syn::Result<syn::Expr> {
     let struct_entrypoints = ir
         .functions()?
         .iter()
         .map(|f| {
             let ident = f.name_str();
             let args = entrypoint_args(f)?;
-            let is_mut = f.is_mut();
+            let is_mut = f.is_mutable_entrypoint();
             let ret = match f.return_type() {
                 syn::ReturnType::Default => utils::expr::unit_cl_type(),
                 syn::ReturnType::Type(_, ty) => utils::expr::as_cl_type(&ty)
@@ -270,4 +298,35 @@ mod test {
         let actual = HasEntrypointsImplItem::try_from(&module).unwrap();
         test_utils::assert_eq(actual, expected);
     }
-}
+
+    #[test]
+    fn test_entrypoints_with_interfaces() {
+        let module = test_utils::mock::module_implementing_interface();
+        let expected = quote!(
+            impl odra::contract_def::HasEntrypoints for Counter {
+                fn entrypoints() -> odra::prelude::vec::Vec<odra::contract_def::Entrypoint> {
+                    {
+                        let mut items = odra::prelude::vec![
+                            odra::contract_def::Entrypoint {
+                                name: odra::prelude::string::String::from("reset"),
+                                args: odra::prelude::vec![],
+                                is_mutable: true,
+                                return_ty: <() as odra::casper_types::CLTyped>::cl_type(),
+                                ty: odra::contract_def::EntrypointType::Public,
+                                attributes: odra::prelude::vec![]
+                            }
+                        ];
+                        items.extend(__counter_countable_exec_parts::entrypoints());
+                        items
+                    }
+                }
+
+                fn interfaces() -> odra::prelude::vec::Vec<odra::prelude::string::String> {
+                    odra::prelude::vec![odra::prelude::string::String::from("Countable")]
+                }
+            }
+        );
+        let actual = HasEntrypointsImplItem::try_from(&module).unwrap();
+        test_utils::assert_eq(actual, expected);
+    }
+}
\ No newline at end of file
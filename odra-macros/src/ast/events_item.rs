@@ -20,7 +20,7 @@ impl TryFrom<&'_ ModuleStructIR> for HasEventsImplItem {
 
     fn try_from(ir: &'_ ModuleStructIR) -> Result<Self, Self::Error> {
         Ok(Self {
-            impl_item: ImplItem::has_events(ir)?,
+            impl_item: ImplItem::module_has_events(ir)?,
             brace_token: Default::default(),
             events_fn: ir.try_into()?
         })
@@ -1,6 +1,9 @@
-use super::parts_utils::{UsePreludeItem, UseSuperItem};
+use super::{
+    entrypoints_item,
+    parts_utils::{UsePreludeItem, UseSuperItem}
+};
 use crate::{
-    ir::{FnIR, ModuleImplIR},
+    ir::{EntrypointGuard, FnIR, ModuleImplIR},
     utils
 };
 use quote::TokenStreamExt;
@@ -17,7 +20,9 @@ pub struct ExecPartsItem {
     use_prelude: UsePreludeItem,
     #[syn(in = brace_token)]
     #[to_tokens(|tokens, f| tokens.append_all(f))]
-    exec_functions: Vec<ExecFunctionItem>
+    exec_functions: Vec<ExecFunctionItem>,
+    #[syn(in = brace_token)]
+    interface_entrypoints_fn: Option<syn::ItemFn>
 }
 
 impl TryFrom<&'_ ModuleImplIR> for ExecPartsItem {
@@ -34,11 +39,23 @@ impl TryFrom<&'_ ModuleImplIR> for ExecPartsItem {
                 .iter()
                 .map(|f| (module, f))
                 .map(TryInto::try_into)
-                .collect::<Result<Vec<_>, _>>()?
+                .collect::<Result<Vec<_>, _>>()?,
+            interface_entrypoints_fn: interface_entrypoints_fn(module)?
         })
     }
 }
 
+/// Returns the entrypoints of an interface implementation, listed by the module implementing the interface.
+fn interface_entrypoints_fn(module: &ModuleImplIR) -> syn::Result<Option<syn::ItemFn>> {
+    if !module.is_interface_impl() {
+        return Ok(None);
+    }
+    let ident_entrypoints = utils::ident::entrypoints();
+    let vec_ty = utils::ty::vec_of(&utils::ty::entry_point_def());
+    let expr = entrypoints_item::struct_entrypoints_expr(module)?;
+    Ok(Some(parse_quote!(pub fn #ident_entrypoints() -> #vec_ty { #expr })))
+}
+
 #[derive(syn_derive::ToTokens)]
 struct ExecFunctionItem {
     inline_attr: syn::Attribute,
@@ -59,6 +76,9 @@ struct ExecFunctionItem {
     #[syn(in = braces)]
     init_contract_stmt: syn::Stmt,
     #[syn(in = braces)]
+    #[to_tokens(|tokens, f| tokens.append_all(f))]
+    guard_stmts: Vec<syn::Stmt>,
+    #[syn(in = braces)]
     call_contract_stmt: syn::Stmt,
     #[syn(in = braces)]
     clear_attached_value_stmt: Option<ExecEnvStmt>,
@@ -111,11 +131,20 @@ impl TryFrom<(&'_ ModuleImplIR, &'_ FnIR)> for ExecFunctionItem {
             false => utils::stmt::new_module(&contract_ident, &module_ident, &env_rc_ident)
         };
 
+        let guard_stmts = func
+            .guards()
+            .iter()
+            .map(|guard| guard_stmt(guard, &contract_ident, &module_ident))
+            .collect();
+
         Ok(Self {
             inline_attr: utils::attr::inline(),
             sig: func.try_into()?,
             braces: Default::default(),
-            env_rc_stmt: utils::stmt::new_rc(&env_rc_ident, &env_ident),
+            env_rc_stmt: match func.is_view() {
+                true => utils::stmt::new_read_only_rc(&env_rc_ident, &env_ident),
+                false => utils::stmt::new_rc(&env_rc_ident, &env_ident)
+            },
             exec_env_stmt,
             non_reentrant_before_stmt: func
                 .is_non_reentrant()
@@ -123,6 +152,7 @@ impl TryFrom<(&'_ ModuleImplIR, &'_ FnIR)> for ExecFunctionItem {
             handle_attached_value_stmt: func.is_payable().then(ExecEnvStmt::handle_attached_value),
             args,
             init_contract_stmt,
+            guard_stmts,
             call_contract_stmt: parse_quote!(let #result_ident = #contract_ident.#fn_ident(#fn_args);),
             clear_attached_value_stmt: func.is_payable().then(ExecEnvStmt::clear_attached_value),
             non_reentrant_after_stmt: func
@@ -133,6 +163,37 @@ impl TryFrom<(&'_ ModuleImplIR, &'_ FnIR)> for ExecFunctionItem {
     }
 }
 
+fn guard_stmt(
+    guard: &EntrypointGuard,
+    contract_ident: &syn::Ident,
+    module_ident: &syn::Ident
+) -> syn::Stmt {
+    match guard {
+        EntrypointGuard::Owner(None) => {
+            let guard_ty = utils::ty::owner_guard();
+            parse_quote!(<#module_ident as #guard_ty>::assert_owner(&#contract_ident, &#contract_ident.env().caller());)
+        }
+        EntrypointGuard::Owner(Some(field)) => {
+            let guard_ty = utils::ty::owner_guard();
+            parse_quote!(#guard_ty::assert_owner(&*#contract_ident.#field, &#contract_ident.env().caller());)
+        }
+        EntrypointGuard::Role(field, role) => {
+            let guard_ty = utils::ty::role_guard();
+            parse_quote!(#guard_ty::assert_role(&*#contract_ident.#field, #role, &#contract_ident.env().caller());)
+        }
+        EntrypointGuard::Custom(path) => {
+            // The exec function is not a method, so `Self` must be replaced with the module type.
+            let mut path = path.clone();
+            if let Some(first) = path.segments.first_mut() {
+                if first.ident == "Self" {
+                    first.ident = module_ident.clone();
+                }
+            }
+            parse_quote!(#path(&#contract_ident);)
+        }
+    }
+}
+
 #[derive(syn_derive::ToTokens)]
 struct ExecFnSignature {
     vis: syn::Visibility,
@@ -331,6 +392,60 @@ mod test {
         test_utils::assert_eq(actual, expected);
     }
 
+    #[test]
+    fn test_guarded_parts() {
+        let module = mock::module_guards();
+        let actual = ExecPartsItem::try_from(&module).unwrap();
+
+        let expected = quote::quote! {
+            #[allow(missing_docs)]
+            mod __token_exec_parts {
+                use super::*;
+                use odra::prelude::*;
+
+                #[inline]
+                pub fn execute_pause(env: odra::ContractEnv) {
+                    let env_rc = Rc::new(env);
+                    let mut contract = <Token as Module>::new(env_rc);
+                    <Token as odra::module::OwnerGuard>::assert_owner(&contract, &contract.env().caller());
+                    let result = contract.pause();
+                    return result;
+                }
+
+                #[inline]
+                pub fn execute_unpause(env: odra::ContractEnv) {
+                    let env_rc = Rc::new(env);
+                    let mut contract = <Token as Module>::new(env_rc);
+                    odra::module::OwnerGuard::assert_owner(&*contract.ownable, &contract.env().caller());
+                    let result = contract.unpause();
+                    return result;
+                }
+
+                #[inline]
+                pub fn execute_is_paused(env: odra::ContractEnv) -> bool {
+                    let env_rc = Rc::new(env.into_read_only());
+                    let contract = <Token as Module>::new(env_rc);
+                    let result = contract.is_paused();
+                    return result;
+                }
+
+                #[inline]
+                pub fn execute_mint(env: odra::ContractEnv) {
+                    let env_rc = Rc::new(env);
+                    let exec_env = odra::ExecutionEnv::new(env_rc.clone());
+                    let amount = exec_env.get_named_arg::<U256>("amount");
+                    let mut contract = <Token as Module>::new(env_rc);
+                    odra::module::RoleGuard::assert_role(&*contract.access_control, "MINTER", &contract.env().caller());
+                    Token::not_paused(&contract);
+                    let result = contract.mint(amount);
+                    return result;
+                }
+            }
+        };
+
+        test_utils::assert_eq(actual, expected);
+    }
+
     #[test]
     fn test_delegated_parts() {
         let module = mock::module_delegation();
@@ -0,0 +1,399 @@
+use crate::{
+    ast::{
+        module_impl_item::ModuleImplItem, module_struct_item::ModuleStructItem,
+        wasm_parts::{FactoryExportsMacroItem, FactoryTemplateModuleItem}
+    },
+    ir::{ModuleImplIR, ModuleStructIR}
+};
+use quote::{quote, ToTokens};
+
+/// Factory module installing new instances of a module.
+///
+/// Generated only for modules marked with `#[odra::module(factory)]`.
+pub struct FactoryItem {
+    code: proc_macro2::TokenStream
+}
+
+impl ToTokens for FactoryItem {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        tokens.extend(self.code.clone());
+    }
+}
+
+impl TryFrom<&'_ ModuleImplIR> for FactoryItem {
+    type Error = syn::Error;
+
+    fn try_from(module: &'_ ModuleImplIR) -> Result<Self, Self::Error> {
+        let factory = match module.factory()? {
+            Some(factory) => factory,
+            None => return Ok(Self { code: quote!() })
+        };
+
+        let factory_ident = module.factory_ident()?;
+        let comment = format!(
+            " A contract installing new instances of the [{}] contract.",
+            module.module_str()?
+        );
+        let factory_struct = ModuleStructIR::try_from((
+            &quote!(),
+            &quote!(#[doc = #comment] pub struct #factory_ident;)
+        ))?;
+        let factory_struct = ModuleStructItem::try_from(&factory_struct)?;
+        let factory_impl = ModuleImplItem::try_from(&factory)?;
+        let template = FactoryTemplateModuleItem::try_from(module)?;
+        let exports = FactoryExportsMacroItem::try_from(module)?;
+        let has_template = HasTemplateItem::try_from(module)?;
+
+        Ok(Self {
+            code: quote!(#factory_struct #factory_impl #template #exports #has_template)
+        })
+    }
+}
+
+/// Implementation of `odra::factory::HasTemplate` for a module installed by factories.
+struct HasTemplateItem {
+    code: proc_macro2::TokenStream
+}
+
+impl ToTokens for HasTemplateItem {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        tokens.extend(self.code.clone());
+    }
+}
+
+impl TryFrom<&'_ ModuleImplIR> for HasTemplateItem {
+    type Error = syn::Error;
+
+    fn try_from(module: &'_ ModuleImplIR) -> Result<Self, Self::Error> {
+        let module_ident = module.module_ident()?;
+        let module_str = module.module_str()?;
+        let host_ref_ident = module.host_ref_ident()?;
+        let template_mod_ident = module.factory_template_mod_ident()?;
+
+        Ok(Self {
+            code: quote! {
+                impl odra::factory::HasTemplate for #module_ident {
+                    fn template() -> odra::factory::ContractTemplate {
+                        #[cfg(not(target_arch = "wasm32"))]
+                        let template = odra::factory::ContractTemplate::new(
+                            #module_str,
+                            <#host_ref_ident as odra::host::EntryPointsCallerProvider>::entry_points_caller
+                        );
+                        #[cfg(target_arch = "wasm32")]
+                        let template = odra::factory::ContractTemplate::new(
+                            #module_str,
+                            #template_mod_ident::entry_points(),
+                            odra::casper_event_standard::Schemas(
+                                <#module_ident as odra::contract_def::HasEvents>::event_schemas()
+                            )
+                        );
+                        template
+                    }
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ast::wasm_parts::WasmPartsModuleItem;
+    use crate::test_utils::{self, mock};
+
+    #[test]
+    fn factory_new_contract() {
+        let module = mock::module_factory();
+        let actual = module.factory().unwrap().unwrap().self_code().unwrap();
+
+        let expected = quote! {
+            impl CounterFactory {
+                /// Installs a new [Counter] contract and returns its address.
+                pub fn new_contract(&mut self, value: u32) -> odra::prelude::Address {
+                    let template = <Counter as odra::factory::HasTemplate>::template();
+                    odra::module::Module::env(self).new_contract(&template, Some({
+                        let mut named_args = odra::casper_types::RuntimeArgs::new();
+                        odra::args::EntrypointArgument::insert_runtime_arg(value.clone(), "value", &mut named_args);
+                        named_args
+                    }))
+                }
+            }
+        };
+
+        test_utils::assert_eq(actual, expected);
+    }
+
+    #[test]
+    fn factory_has_template() {
+        let module = mock::module_factory();
+        let actual = HasTemplateItem::try_from(&module).unwrap();
+
+        let expected = quote! {
+            impl odra::factory::HasTemplate for Counter {
+                fn template() -> odra::factory::ContractTemplate {
+                    #[cfg(not(target_arch = "wasm32"))]
+                    let template = odra::factory::ContractTemplate::new(
+                        "Counter",
+                        <CounterHostRef as odra::host::EntryPointsCallerProvider>::entry_points_caller
+                    );
+                    #[cfg(target_arch = "wasm32")]
+                    let template = odra::factory::ContractTemplate::new(
+                        "Counter",
+                        __counter_factory_template::entry_points(),
+                        odra::casper_event_standard::Schemas(
+                            <Counter as odra::contract_def::HasEvents>::event_schemas()
+                        )
+                    );
+                    template
+                }
+            }
+        };
+
+        test_utils::assert_eq(actual, expected);
+    }
+
+    #[test]
+    fn factory_template() {
+        let module = mock::module_factory();
+        let actual = FactoryTemplateModuleItem::try_from(&module).unwrap();
+
+        let expected = quote! {
+            #[cfg(target_arch = "wasm32")]
+            #[doc(hidden)]
+            pub mod __counter_factory_template {
+                use super::*;
+                use odra::prelude::*;
+
+                #[inline]
+                pub fn entry_points() -> odra::casper_types::EntryPoints {
+                    let mut entry_points = odra::casper_types::EntryPoints::new();
+                    entry_points.add_entry_point(odra::casper_types::EntryPoint::new(
+                        "init",
+                        vec![odra::args::parameter::<u32>("value")].into_iter().filter_map(|x| x).collect(),
+                        <() as odra::casper_types::CLTyped>::cl_type(),
+                        odra::casper_types::EntryPointAccess::Groups(vec![odra::casper_types::Group::new("constructor_group")]),
+                        odra::casper_types::EntryPointType::Contract
+                    ));
+                    entry_points.add_entry_point(odra::casper_types::EntryPoint::new(
+                        "count",
+                        vec![],
+                        <u32 as odra::casper_types::CLTyped>::cl_type(),
+                        odra::casper_types::EntryPointAccess::Public,
+                        odra::casper_types::EntryPointType::Contract
+                    ));
+                    entry_points
+                }
+
+                pub fn init() {
+                    __counter_exec_parts::execute_init(odra::odra_casper_wasm_env::WasmContractEnv::new_env());
+                }
+
+                pub fn count() {
+                    let result = __counter_exec_parts::execute_count(odra::odra_casper_wasm_env::WasmContractEnv::new_env());
+                    odra::odra_casper_wasm_env::casper_contract::contract_api::runtime::ret(
+                        odra::odra_casper_wasm_env::casper_contract::unwrap_or_revert::UnwrapOrRevert::unwrap_or_revert(
+                            odra::casper_types::CLValue::from_t(result)
+                        )
+                    );
+                }
+            }
+        };
+
+        test_utils::assert_eq(actual, expected);
+    }
+
+    #[test]
+    fn factory_exports_macro() {
+        let module = mock::module_factory();
+        let actual = FactoryExportsMacroItem::try_from(&module).unwrap();
+        let ident = module.factory_exports_macro_ident().unwrap();
+        assert!(ident.to_string().starts_with("__odra_counter_factory_exports_"));
+
+        let expected = quote! {
+            #[cfg(target_arch = "wasm32")]
+            #[doc(hidden)]
+            #[macro_export]
+            macro_rules! #ident {
+                ($export:ident, $($template:tt)*) => {
+                    $export! { [$($template)*] init }
+                    $export! { [$($template)*] count }
+                };
+            }
+
+            #[cfg(target_arch = "wasm32")]
+            #[doc(hidden)]
+            #[allow(unused_imports)]
+            pub use #ident as __counter_factory_exports;
+        };
+
+        test_utils::assert_eq(actual, expected);
+    }
+
+    #[test]
+    fn factory_wasm_parts() {
+        let module = mock::module_factory();
+        let factory = module.factory().unwrap().unwrap();
+        let actual = WasmPartsModuleItem::try_from(&factory).unwrap();
+
+        let expected = quote! {
+            #[cfg(target_arch = "wasm32")]
+            #[cfg(odra_module = "CounterFactory")]
+            mod __counter_factory_wasm_parts {
+                use super::*;
+                use odra::prelude::*;
+
+                #[inline]
+                fn entry_points() -> odra::casper_types::EntryPoints {
+                    let mut entry_points = odra::casper_types::EntryPoints::new();
+                    entry_points.add_entry_point(odra::casper_types::EntryPoint::new(
+                        "new_contract",
+                        vec![odra::args::parameter::<u32>("value")].into_iter().filter_map(|x| x).collect(),
+                        <odra::prelude::Address as odra::casper_types::CLTyped>::cl_type(),
+                        odra::casper_types::EntryPointAccess::Public,
+                        odra::casper_types::EntryPointType::Contract
+                    ));
+                    odra::odra_casper_wasm_env::host_functions::add_template_entry_points(
+                        &mut entry_points,
+                        __counter_factory_template::entry_points()
+                    );
+                    entry_points
+                }
+
+                #[no_mangle]
+                fn call() {
+                    let schemas = odra::casper_event_standard::Schemas(
+                        <CounterFactory as odra::contract_def::HasEvents>::event_schemas()
+                    );
+                    let named_args = Option::<odra::casper_types::RuntimeArgs>::None;
+                    odra::odra_casper_wasm_env::host_functions::install_contract(entry_points(), schemas, named_args);
+                }
+
+                #[no_mangle]
+                fn new_contract() {
+                    let result = __counter_factory_exec_parts::execute_new_contract(odra::odra_casper_wasm_env::WasmContractEnv::new_env());
+                    odra::odra_casper_wasm_env::casper_contract::contract_api::runtime::ret(
+                        odra::odra_casper_wasm_env::casper_contract::unwrap_or_revert::UnwrapOrRevert::unwrap_or_revert(
+                            odra::casper_types::CLValue::from_t(result)
+                        )
+                    );
+                }
+
+                macro_rules! __odra_template_export {
+                    ([$($template:tt)*] new_contract) => {};
+                    ([$($template:tt)*] call) => {};
+                    ([$($template:tt)*] $entry_point:ident) => {
+                        #[no_mangle]
+                        fn $entry_point() {
+                            $($template)*::$entry_point()
+                        }
+                    };
+                }
+
+                __counter_factory_exports! { __odra_template_export, __counter_factory_template }
+            }
+        };
+
+        test_utils::assert_eq(actual, expected);
+    }
+
+    #[test]
+    fn factory_of_wasm_parts() {
+        let module = mock::module_factory_of();
+        let actual = WasmPartsModuleItem::try_from(&module).unwrap();
+
+        let expected = quote! {
+            #[cfg(target_arch = "wasm32")]
+            #[cfg(odra_module = "Deployer")]
+            mod __deployer_wasm_parts {
+                use super::*;
+                use odra::prelude::*;
+
+                #[inline]
+                fn entry_points() -> odra::casper_types::EntryPoints {
+                    let mut entry_points = odra::casper_types::EntryPoints::new();
+                    entry_points.add_entry_point(odra::casper_types::EntryPoint::new(
+                        "init",
+                        vec![],
+                        <() as odra::casper_types::CLTyped>::cl_type(),
+                        odra::casper_types::EntryPointAccess::Groups(vec![odra::casper_types::Group::new("constructor_group")]),
+                        odra::casper_types::EntryPointType::Contract
+                    ));
+                    entry_points.add_entry_point(odra::casper_types::EntryPoint::new(
+                        "deploy",
+                        vec![],
+                        <Address as odra::casper_types::CLTyped>::cl_type(),
+                        odra::casper_types::EntryPointAccess::Public,
+                        odra::casper_types::EntryPointType::Contract
+                    ));
+                    odra::odra_casper_wasm_env::host_functions::add_template_entry_points(
+                        &mut entry_points,
+                        __counter_factory_template::entry_points()
+                    );
+                    odra::odra_casper_wasm_env::host_functions::add_template_entry_points(
+                        &mut entry_points,
+                        super::super::token::__erc20_factory_template::entry_points()
+                    );
+                    entry_points
+                }
+
+                #[no_mangle]
+                fn call() {
+                    let schemas = odra::casper_event_standard::Schemas(
+                        <Deployer as odra::contract_def::HasEvents>::event_schemas()
+                    );
+                    let named_args = {
+                        let env = odra::odra_casper_wasm_env::WasmContractEnv::new_env();
+                        let env_rc = Rc::new(env);
+                        let exec_env = odra::ExecutionEnv::new(env_rc);
+                        Some({
+                            let mut named_args = odra::casper_types::RuntimeArgs::new();
+                            named_args
+                        })
+                    };
+                    odra::odra_casper_wasm_env::host_functions::install_contract(entry_points(), schemas, named_args);
+                }
+
+                #[no_mangle]
+                fn init() {
+                    __deployer_exec_parts::execute_init(odra::odra_casper_wasm_env::WasmContractEnv::new_env());
+                }
+
+                #[no_mangle]
+                fn deploy() {
+                    let result = __deployer_exec_parts::execute_deploy(odra::odra_casper_wasm_env::WasmContractEnv::new_env());
+                    odra::odra_casper_wasm_env::casper_contract::contract_api::runtime::ret(
+                        odra::odra_casper_wasm_env::casper_contract::unwrap_or_revert::UnwrapOrRevert::unwrap_or_revert(
+                            odra::casper_types::CLValue::from_t(result)
+                        )
+                    );
+                }
+
+                // The installed contracts share the `init` export with the factory.
+                macro_rules! __odra_template_export {
+                    ([$($template:tt)*] init) => {};
+                    ([$($template:tt)*] deploy) => {};
+                    ([$($template:tt)*] call) => {};
+                    ([$($template:tt)*] $entry_point:ident) => {
+                        #[no_mangle]
+                        fn $entry_point() {
+                            $($template)*::$entry_point()
+                        }
+                    };
+                }
+
+                __counter_factory_exports! { __odra_template_export, __counter_factory_template }
+                super::super::token::__erc20_factory_exports! { __odra_template_export, super::super::token::__erc20_factory_template }
+            }
+        };
+
+        test_utils::assert_eq(actual, expected);
+    }
+
+    #[test]
+    fn no_factory_without_attribute() {
+        let module = mock::module_impl();
+        let actual = FactoryItem::try_from(&module).unwrap();
+
+        test_utils::assert_eq(actual, quote!());
+    }
+}
@@ -30,10 +30,12 @@ impl TryFrom<&'_ ModuleImplIR> for HostRefStructItem {
         let ty_host_env = utils::ty::host_env();
         let ty_u512 = utils::ty::u512();
 
+        // The implementations of the interfaces call the contract from a sibling module.
+        let vis = (!module.interfaces().is_empty()).then(|| quote!(pub(super)));
         let named_fields: syn::FieldsNamed = parse_quote!({
-            #address: #ty_address,
-            #env: #ty_host_env,
-            #attached_value: #ty_u512
+            #vis #address: #ty_address,
+            #vis #env: #ty_host_env,
+            #vis #attached_value: #ty_u512
         });
 
         let comment = format!(" [{}] Host Ref.", module.module_str()?);
@@ -119,9 +121,9 @@ impl TryFrom<&'_ ModuleImplIR> for HostRefTraitImplItem {
 }
 
 #[derive(syn_derive::ToTokens)]
-struct HostRefImplItem {
+pub struct HostRefImplItem {
     impl_token: syn::token::Impl,
-    trait_name: Option<Ident>,
+    trait_name: Option<syn::Path>,
     for_token: Option<syn::token::For>,
     ref_ident: Ident,
     #[syn(braced)]
@@ -136,7 +138,7 @@ impl TryFrom<&'_ ModuleImplIR> for HostRefImplItem {
 
     fn try_from(module: &'_ ModuleImplIR) -> Result<Self, Self::Error> {
         // If module implements a trait, set trait name
-        let trait_name = module.impl_trait_ident();
+        let trait_name = module.impl_trait_path();
         let for_token: Option<syn::token::For> = match module.is_trait_impl() {
             true => Some(Default::default()),
             false => None
@@ -158,7 +160,7 @@ impl TryFrom<&'_ ModuleImplIR> for HostRefImplItem {
 }
 
 #[derive(syn_derive::ToTokens)]
-struct HostRefTryImplItem {
+pub struct HostRefTryImplItem {
     impl_token: syn::token::Impl,
     ref_ident: Ident,
     #[syn(braced)]
@@ -1,6 +1,6 @@
 use syn::parse_quote;
 
-use crate::ir::ModuleStructIR;
+use crate::ir::{ModuleImplIR, ModuleStructIR};
 use crate::utils;
 
 #[derive(syn_derive::ToTokens)]
@@ -30,6 +30,21 @@ impl TryFrom<&'_ ModuleStructIR> for HasIdentImplItem {
     }
 }
 
+impl TryFrom<&'_ ModuleImplIR> for HasIdentImplItem {
+    type Error = syn::Error;
+
+    fn try_from(ir: &'_ ModuleImplIR) -> Result<Self, Self::Error> {
+        Ok(Self {
+            impl_token: Default::default(),
+            has_ident_ty: utils::ty::has_ident(),
+            for_token: Default::default(),
+            module_ident: ir.module_ident()?,
+            brace_token: Default::default(),
+            ident_fn: IdentFnItem::new(ir.module_str()?)
+        })
+    }
+}
+
 #[derive(syn_derive::ToTokens)]
 pub struct IdentFnItem {
     sig: syn::Signature,
@@ -43,14 +58,20 @@ impl TryFrom<&'_ ModuleStructIR> for IdentFnItem {
     type Error = syn::Error;
 
     fn try_from(ir: &'_ ModuleStructIR) -> Result<Self, Self::Error> {
+        Ok(Self::new(ir.module_str()))
+    }
+}
+
+impl IdentFnItem {
+    fn new(module_str: String) -> Self {
         let ident = utils::ident::ident();
         let ret_ty = utils::ty::string();
 
-        Ok(Self {
+        Self {
             sig: parse_quote!(fn #ident() -> #ret_ty),
             brace_token: Default::default(),
-            expr: utils::expr::string_from(ir.module_str())
-        })
+            expr: utils::expr::string_from(module_str)
+        }
     }
 }
 
@@ -0,0 +1,275 @@
+use crate::ast::contract_ref_item::ContractRefImplItem;
+use crate::ast::deployer_utils;
+use crate::ast::exec_parts::ExecPartsItem;
+use crate::ast::external_contract_item::ExternalContractImpl;
+use crate::ast::host_ref_item::{HostRefImplItem, HostRefTryImplItem};
+use crate::ast::parts_utils::{UsePreludeItem, UseSuperItem};
+use crate::ast::schema;
+use crate::ast::test_parts::PartsModuleItem;
+use crate::ast::wasm_parts::InterfaceWasmPartsModuleItem;
+use crate::ir::{FnIR, ModuleImplIR};
+use crate::utils;
+use derive_try_from_ref::TryFromRef;
+use quote::{ToTokens, TokenStreamExt};
+use syn::parse_quote;
+
+/// An interface definition, a trait with references to any contract implementing it.
+#[derive(syn_derive::ToTokens, TryFromRef)]
+#[source(ModuleImplIR)]
+#[err(syn::Error)]
+pub struct InterfaceItem {
+    #[expr(input.self_code()?)]
+    self_code: proc_macro2::TokenStream,
+    refs: ExternalContractImpl,
+    refs_impl: InterfaceRefsImplItem
+}
+
+/// Implements the interface for the references, delegating to the generated functions.
+struct InterfaceRefsImplItem {
+    items: Vec<syn::ItemImpl>
+}
+
+impl ToTokens for InterfaceRefsImplItem {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        tokens.append_all(&self.items);
+    }
+}
+
+impl TryFrom<&'_ ModuleImplIR> for InterfaceRefsImplItem {
+    type Error = syn::Error;
+
+    fn try_from(module: &'_ ModuleImplIR) -> Result<Self, Self::Error> {
+        let interface = module.module_ident()?;
+        let contract_ref = module.contract_ref_ident()?;
+        let host_ref = module.host_ref_ident()?;
+        let functions = module
+            .functions()?
+            .iter()
+            .map(delegating_function_item)
+            .collect::<Vec<_>>();
+        let not_wasm_attr = utils::attr::not_wasm32();
+
+        Ok(Self {
+            items: vec![
+                parse_quote!(
+                    impl #interface for #contract_ref {
+                        #(#functions)*
+                    }
+                ),
+                parse_quote!(
+                    #not_wasm_attr
+                    impl #interface for #host_ref {
+                        #(#functions)*
+                    }
+                ),
+            ]
+        })
+    }
+}
+
+fn delegating_function_item(fun: &FnIR) -> syn::ItemFn {
+    let fun_name = fun.name();
+    let args = fun.typed_args();
+    let arg_names = fun.arg_names();
+    let return_type = fun.return_type();
+    let mutability = fun.is_mut().then(|| quote::quote!(mut));
+
+    parse_quote!(
+        fn #fun_name(& #mutability self #(, #args)*) #return_type {
+            Self::#fun_name(self #(, #arg_names)*)
+        }
+    )
+}
+
+/// An implementation of an interface by a module.
+///
+/// The entry points of the interface are exported along with the entry points of the module,
+/// which lists the interface in `#[odra::module(implements = [..])]`.
+#[derive(syn_derive::ToTokens, TryFromRef)]
+#[source(ModuleImplIR)]
+#[err(syn::Error)]
+pub struct InterfaceImplItem {
+    #[expr(input.self_code()?)]
+    self_code: proc_macro2::TokenStream,
+    contract_ref_impl: ContractRefImplItem,
+    test_parts: InterfaceTestPartsItem,
+    exec_parts: ExecPartsItem,
+    wasm_parts: InterfaceWasmPartsModuleItem
+}
+
+#[derive(syn_derive::ToTokens, TryFromRef)]
+#[source(ModuleImplIR)]
+#[err(syn::Error)]
+struct InterfaceTestPartsItem {
+    parts_module: PartsModuleItem,
+    #[syn(braced)]
+    #[default]
+    brace_token: syn::token::Brace,
+    #[syn(in = brace_token)]
+    #[expr(UseSuperItem)]
+    use_super: UseSuperItem,
+    #[syn(in = brace_token)]
+    #[expr(UsePreludeItem)]
+    use_prelude: UsePreludeItem,
+    #[syn(in = brace_token)]
+    host_ref_impl: HostRefImplItem,
+    #[syn(in = brace_token)]
+    host_ref_try_impl: HostRefTryImplItem,
+    #[syn(in = brace_token)]
+    parts_fns: InterfacePartsFnsItem
+}
+
+/// Functions listing and calling the entry points of the interface, used by the implementing module.
+struct InterfacePartsFnsItem {
+    items: Vec<syn::ItemFn>
+}
+
+impl ToTokens for InterfacePartsFnsItem {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        tokens.append_all(&self.items);
+    }
+}
+
+impl TryFrom<&'_ ModuleImplIR> for InterfacePartsFnsItem {
+    type Error = syn::Error;
+
+    fn try_from(module: &'_ ModuleImplIR) -> Result<Self, Self::Error> {
+        let functions = module.functions()?;
+        let ty_entry_point = utils::ty::odra_entry_point();
+        let entry_points = deployer_utils::entry_points_expr(module)?;
+        let schema_entry_points = schema::schema_entrypoints_expr(&functions);
+        let custom_types = schema::custom_types_chain(&functions);
+
+        Ok(Self {
+            items: vec![
+                parse_quote!(
+                    pub fn entry_points() -> odra::prelude::vec::Vec<#ty_entry_point> {
+                        #entry_points
+                    }
+                ),
+                deployer_utils::interface_call_fn(module)?,
+                parse_quote!(
+                    pub fn schema_entrypoints() -> odra::prelude::vec::Vec<odra::schema::casper_contract_schema::Entrypoint> {
+                        #schema_entry_points
+                    }
+                ),
+                parse_quote!(
+                    pub fn schema_types() -> odra::prelude::vec::Vec<Option<odra::schema::casper_contract_schema::CustomType>> {
+                        odra::prelude::BTreeSet::<Option<odra::schema::casper_contract_schema::CustomType>>::new()
+                            .into_iter()
+                            #(#custom_types)*
+                            .collect()
+                    }
+                ),
+            ]
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::{self, mock};
+
+    #[test]
+    fn interface_refs_impl() {
+        let module = mock::interface();
+        let actual = InterfaceRefsImplItem::try_from(&module).unwrap();
+
+        let expected = quote::quote! {
+            impl Countable for CountableContractRef {
+                fn value(&self) -> u32 {
+                    Self::value(self)
+                }
+
+                fn increment(&mut self, by: u32) {
+                    Self::increment(self, by)
+                }
+            }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            impl Countable for CountableHostRef {
+                fn value(&self) -> u32 {
+                    Self::value(self)
+                }
+
+                fn increment(&mut self, by: u32) {
+                    Self::increment(self, by)
+                }
+            }
+        };
+
+        test_utils::assert_eq(actual, expected);
+    }
+
+    #[test]
+    fn interface_parts_fns() {
+        let module = mock::interface_impl();
+        let actual = InterfacePartsFnsItem::try_from(&module).unwrap();
+
+        let expected = quote::quote! {
+            pub fn entry_points() -> odra::prelude::vec::Vec<odra::entry_point_callback::EntryPoint> {
+                odra::prelude::vec![
+                    odra::entry_point_callback::EntryPoint::new(odra::prelude::string::String::from("value"), odra::prelude::vec![]),
+                    odra::entry_point_callback::EntryPoint::new(
+                        odra::prelude::string::String::from("increment"),
+                        odra::prelude::vec![odra::entry_point_callback::Argument::new::<u32>(odra::prelude::string::String::from("by"))]
+                    )
+                ]
+            }
+
+            pub fn call(
+                contract_env: odra::ContractEnv,
+                entry_point: &str
+            ) -> Option<OdraResult<odra::casper_types::bytesrepr::Bytes>> {
+                let result = match entry_point {
+                    "value" => {
+                        let result = __counter_countable_exec_parts::execute_value(contract_env);
+                        odra::casper_types::bytesrepr::ToBytes::to_bytes(&result)
+                            .map(Into::into)
+                            .map_err(|err| OdraError::ExecutionError(err.into()))
+                    }
+                    "increment" => {
+                        let result = __counter_countable_exec_parts::execute_increment(contract_env);
+                        odra::casper_types::bytesrepr::ToBytes::to_bytes(&result)
+                            .map(Into::into)
+                            .map_err(|err| OdraError::ExecutionError(err.into()))
+                    }
+                    _ => return None
+                };
+                Some(result)
+            }
+
+            pub fn schema_entrypoints() -> odra::prelude::vec::Vec<odra::schema::casper_contract_schema::Entrypoint> {
+                odra::prelude::vec![
+                    odra::schema::entry_point::<u32>("value", "Returns the value.", false, odra::prelude::vec![]),
+                    odra::schema::entry_point::<()>("increment", "", true, odra::prelude::vec![odra::schema::argument::<u32>("by")])
+                ]
+            }
+
+            pub fn schema_types() -> odra::prelude::vec::Vec<Option<odra::schema::casper_contract_schema::CustomType>> {
+                odra::prelude::BTreeSet::<Option<odra::schema::casper_contract_schema::CustomType>>::new()
+                    .into_iter()
+                    .chain(<u32 as odra::schema::SchemaCustomTypes>::schema_types())
+                    .collect()
+            }
+        };
+
+        test_utils::assert_eq(actual, expected);
+    }
+
+    #[test]
+    fn interface_validation() {
+        let attr = quote::quote!();
+        let invalid = [
+            quote::quote!(pub trait Countable { fn value(&self) -> u32 { 0 } }),
+            quote::quote!(pub trait Countable { fn init(&mut self); }),
+            quote::quote!(impl Counter { pub fn value(&self) -> u32 { 0 } }),
+            quote::quote!(impl Countable for Counter { fn init(&mut self) {} }),
+        ];
+        for item in invalid {
+            let ir = ModuleImplIR::try_from((&attr, &item)).unwrap();
+            assert!(ir.into_interface().is_err());
+        }
+    }
+}
@@ -1,5 +1,6 @@
 mod blueprint;
 mod contract_ref_item;
+mod delegation_item;
 mod deployer_item;
 mod deployer_utils;
 mod entrypoints_item;
@@ -7,13 +8,17 @@ mod error_item;
 mod events_item;
 mod exec_parts;
 mod external_contract_item;
+mod factory_item;
 mod fn_utils;
 mod host_ref_item;
 mod ident_item;
+mod interface_item;
 mod module_def;
 mod module_impl_item;
+mod module_instance_item;
 mod module_item;
 mod module_struct_item;
+mod multicall_item;
 mod odra_type_item;
 mod parts_utils;
 mod ref_utils;
@@ -25,9 +30,12 @@ mod wasm_parts;
 mod wasm_parts_utils;
 mod contract_item;
 
+pub(crate) use delegation_item::DelegatedModuleItem;
 pub(crate) use error_item::OdraErrorAttrItem;
 pub(crate) use external_contract_item::ExternalContractImpl;
+pub(crate) use interface_item::{InterfaceImplItem, InterfaceItem};
 pub(crate) use module_impl_item::ModuleImplItem;
+pub(crate) use module_instance_item::GenericModuleImplItem;
 pub(crate) use module_struct_item::ModuleStructItem;
 pub(crate) use odra_type_item::OdraTypeAttrItem;
 pub(crate) use event::OdraEventItem;
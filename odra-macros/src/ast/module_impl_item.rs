@@ -1,8 +1,10 @@
 use crate::ast::blueprint::BlueprintItem;
 use crate::ast::contract_ref_item::RefItem;
 use crate::ast::contract_item::ContractItem;
+use crate::ast::delegation_item::DelegationMacroItem;
 use crate::ast::entrypoints_item::HasEntrypointsImplItem;
 use crate::ast::exec_parts::ExecPartsItem;
+use crate::ast::factory_item::FactoryItem;
 use crate::ast::schema::{SchemaCustomTypesItem, SchemaEntrypointsItem};
 use crate::ast::test_parts::{TestPartsItem, TestPartsReexportItem};
 use crate::ast::wasm_parts::WasmPartsModuleItem;
@@ -25,6 +27,8 @@ pub struct ModuleImplItem {
     contract_item: ContractItem,
     blueprint: BlueprintItem,
     schema_entrypoints: SchemaEntrypointsItem,
-    schema_custom_types: SchemaCustomTypesItem
+    schema_custom_types: SchemaCustomTypesItem,
+    factory: FactoryItem,
+    delegation_macro: DelegationMacroItem
 }
 
@@ -0,0 +1,171 @@
+use crate::{
+    ast::{ident_item::HasIdentImplItem, module_impl_item::ModuleImplItem, schema::SchemaItem},
+    ir::ModuleImplIR,
+    utils
+};
+use quote::{ToTokens, TokenStreamExt};
+use syn::parse_quote;
+
+/// Implementation of a generic module.
+///
+/// The generic code is left intact, the contract parts are generated for every
+/// concrete instance of the module listed in `#[odra::module(instances = [..])]`.
+pub struct GenericModuleImplItem {
+    self_code: proc_macro2::TokenStream,
+    instances: Vec<ModuleInstanceItem>
+}
+
+impl ToTokens for GenericModuleImplItem {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        tokens.extend(self.self_code.clone());
+        tokens.append_all(&self.instances);
+    }
+}
+
+impl TryFrom<&'_ ModuleImplIR> for GenericModuleImplItem {
+    type Error = syn::Error;
+
+    fn try_from(module: &'_ ModuleImplIR) -> Result<Self, Self::Error> {
+        Ok(Self {
+            self_code: module.self_code()?,
+            instances: module
+                .instances()?
+                .iter()
+                .map(TryInto::try_into)
+                .collect::<Result<Vec<_>, _>>()?
+        })
+    }
+}
+
+#[derive(syn_derive::ToTokens)]
+struct ModuleInstanceItem {
+    alias: syn::ItemType,
+    has_ident: HasIdentImplItem,
+    schema: SchemaItem,
+    module: ModuleImplItem
+}
+
+impl TryFrom<&'_ ModuleImplIR> for ModuleInstanceItem {
+    type Error = syn::Error;
+
+    fn try_from(instance: &'_ ModuleImplIR) -> Result<Self, Self::Error> {
+        let ident = instance.module_ident()?;
+        let ty = instance.instance_of().ok_or_else(|| {
+            syn::Error::new_spanned(&ident, "Not an instance of a generic module")
+        })?;
+        let comment = format!(
+            " Instance of the [{}] module exported as a contract.",
+            utils::syn::last_segment_ident(&ty)?
+        );
+
+        Ok(Self {
+            alias: parse_quote!(#[doc = #comment] pub type #ident = #ty;),
+            has_ident: instance.try_into()?,
+            schema: instance.try_into()?,
+            module: instance.try_into()?
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ast::entrypoints_item::HasEntrypointsImplItem;
+    use crate::test_utils::{self, mock};
+    use quote::quote;
+
+    #[test]
+    fn instance() {
+        let module = mock::generic_module_impl();
+        let instances = module.instances().unwrap();
+        let instance = &instances[0];
+        let item = ModuleInstanceItem::try_from(instance).unwrap();
+
+        let expected = quote! {
+            /// Instance of the [Registry] module exported as a contract.
+            pub type NumberRegistry = Registry<u32>;
+        };
+        test_utils::assert_eq(item.alias, expected);
+
+        let expected = quote! {
+            impl odra::contract_def::HasIdent for NumberRegistry {
+                fn ident() -> odra::prelude::string::String {
+                    odra::prelude::string::String::from("NumberRegistry")
+                }
+            }
+        };
+        test_utils::assert_eq(item.has_ident, expected);
+        test_utils::assert_eq(instance.self_code().unwrap(), quote!());
+    }
+
+    #[test]
+    fn instance_entrypoints() {
+        let module = mock::generic_module_impl();
+        let instances = module.instances().unwrap();
+        let actual = HasEntrypointsImplItem::try_from(&instances[1]).unwrap();
+
+        let expected = quote! {
+            impl odra::contract_def::HasEntrypoints for NameRegistry {
+                fn entrypoints() -> odra::prelude::vec::Vec<odra::contract_def::Entrypoint> {
+                    odra::prelude::vec![
+                        odra::contract_def::Entrypoint {
+                            name: odra::prelude::string::String::from("add"),
+                            args: odra::prelude::vec![
+                                odra::args::odra_argument::<String>("item")
+                            ],
+                            is_mutable: true,
+                            return_ty: <u32 as odra::casper_types::CLTyped>::cl_type(),
+                            ty: odra::contract_def::EntrypointType::Public,
+                            attributes: odra::prelude::vec![]
+                        },
+                        odra::contract_def::Entrypoint {
+                            name: odra::prelude::string::String::from("get"),
+                            args: odra::prelude::vec![
+                                odra::args::odra_argument::<u32>("id")
+                            ],
+                            is_mutable: false,
+                            return_ty: <Option::<String> as odra::casper_types::CLTyped>::cl_type(),
+                            ty: odra::contract_def::EntrypointType::Public,
+                            attributes: odra::prelude::vec![]
+                        }
+                    ]
+                }
+            }
+        };
+        test_utils::assert_eq(actual, expected);
+    }
+
+    #[test]
+    fn generic_code_is_kept() {
+        let module = mock::generic_module_impl();
+        let actual = GenericModuleImplItem::try_from(&module).unwrap();
+
+        test_utils::assert_eq(actual.self_code, module.self_code().unwrap());
+        assert_eq!(actual.instances.len(), 2);
+    }
+
+    #[test]
+    fn invalid_instances() {
+        let module = quote!(
+            impl<T: ToBytes> Registry<T> {
+                pub fn add(&mut self, item: T) {}
+            }
+        );
+
+        let attr = quote!(instances = [NumberRegistry = Registry<u32, u32>]);
+        let ir = ModuleImplIR::try_from((&attr, &module)).unwrap();
+        assert!(ir.instances().is_err());
+
+        let attr = quote!(instances = [NumberRegistry = Storage<u32>]);
+        let ir = ModuleImplIR::try_from((&attr, &module)).unwrap();
+        assert!(ir.instances().is_err());
+
+        let attr = quote!(multicall, instances = [NumberRegistry = Registry<u32>]);
+        let ir = ModuleImplIR::try_from((&attr, &module)).unwrap();
+        assert!(ir.instances().is_err());
+
+        let attr = quote!(instances = [NumberRegistry = Registry<u32>]);
+        let ir = ModuleImplIR::try_from((&attr, &quote!(impl Registry {}))).unwrap();
+        assert!(ir.instances().is_err());
+    }
+}
@@ -37,9 +37,11 @@ impl TryFrom<&'_ ModuleStructIR> for ModuleModItem {
 #[derive(syn_derive::ToTokens)]
 struct ModuleImplItem {
     impl_token: syn::token::Impl,
+    impl_generics: proc_macro2::TokenStream,
     trait_path: syn::Type,
     for_token: syn::token::For,
-    module_path: syn::Ident,
+    module_path: syn::Type,
+    where_clause: Option<syn::WhereClause>,
     #[syn(braced)]
     braces: syn::token::Brace,
     #[syn(in = braces)]
@@ -54,9 +56,11 @@ impl TryFrom<&'_ ModuleStructIR> for ModuleImplItem {
     fn try_from(ir: &'_ ModuleStructIR) -> Result<Self, Self::Error> {
         Ok(Self {
             impl_token: Default::default(),
+            impl_generics: ir.impl_generics(),
             trait_path: utils::ty::module(),
             for_token: Default::default(),
-            module_path: ir.module_ident(),
+            module_path: ir.module_ty(),
+            where_clause: ir.where_clause(&utils::ty::module_component())?,
             braces: Default::default(),
             new_fn: ir.try_into()?,
             env_fn: EnvFnItem
@@ -268,4 +272,41 @@ mod test {
         let actual = ModuleModItem::try_from(&module).unwrap();
         test_utils::assert_eq(actual, expected);
     }
+
+    #[test]
+    fn generic_module() {
+        let module = test_utils::mock::generic_module_definition();
+        let expected = quote!(
+            mod __registry_module {
+                use super::*;
+
+                impl<T: ToBytes> Module for Registry<T>
+                where
+                    Mapping<u32, T>: odra::module::ModuleComponent
+                {
+                    fn new(env: Rc<odra::ContractEnv>) -> Self {
+                        let items = <Mapping<u32, T> as odra::module::ModuleComponent>::instance(
+                            odra::prelude::Rc::clone(&env),
+                            1u8
+                        );
+                        let count = <Var<u32> as odra::module::ModuleComponent>::instance(
+                            odra::prelude::Rc::clone(&env),
+                            2u8
+                        );
+                        Self {
+                            items,
+                            count,
+                            __env: env
+                        }
+                    }
+
+                    fn env(&self) -> Rc<odra::ContractEnv> {
+                        self.__env.clone()
+                    }
+                }
+            }
+        );
+        let actual = ModuleModItem::try_from(&module).unwrap();
+        test_utils::assert_eq(actual, expected);
+    }
 }
@@ -12,9 +12,12 @@ use derive_try_from_ref::TryFromRef;
 pub struct ModuleStructItem {
     self_code: ModuleDefItem,
     mod_item: ModuleModItem,
-    has_ident_item: HasIdentImplItem,
+    // The ident and the contract schema of a generic module are defined by its instances.
+    #[expr((!input.is_generic()).then(|| input.try_into()).transpose()?)]
+    has_ident_item: Option<HasIdentImplItem>,
     has_events_item: HasEventsImplItem,
-    schema_item: SchemaItem,
+    #[expr((!input.is_generic()).then(|| input.try_into()).transpose()?)]
+    schema_item: Option<SchemaItem>,
     schema_events_item: SchemaEventsItem,
     schema_errors: SchemaErrorsItem
 }
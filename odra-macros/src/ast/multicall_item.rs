@@ -0,0 +1,202 @@
+use crate::{
+    ast::{fn_utils, ref_utils},
+    ir::{FnIR, ModuleImplIR},
+    utils
+};
+use quote::{ToTokens, TokenStreamExt};
+use syn::parse_quote;
+
+/// Builder of the calls executed by the `multicall` entrypoint.
+///
+/// Generated only for modules marked with `#[odra::module(multicall)]`.
+pub struct BatchItem {
+    items: Vec<syn::Item>
+}
+
+impl ToTokens for BatchItem {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        tokens.append_all(&self.items);
+    }
+}
+
+impl TryFrom<&'_ ModuleImplIR> for BatchItem {
+    type Error = syn::Error;
+
+    fn try_from(module: &'_ ModuleImplIR) -> Result<Self, Self::Error> {
+        if !module.is_multicall() {
+            return Ok(Self { items: vec![] });
+        }
+
+        let batch_ident = module.batch_ident()?;
+        let host_ref_ident = module.host_ref_ident()?;
+        let ty_call = utils::ty::multicall_call();
+        let comment = format!(
+            " Builds a batch of calls to the [{}] entrypoints executed by `multicall`.",
+            module.module_str()?
+        );
+        let functions = module
+            .functions()?
+            .iter()
+            .filter(|f| f.is_batchable())
+            .map(batch_function_item)
+            .collect::<Vec<_>>();
+
+        Ok(Self {
+            items: vec![
+                parse_quote!(
+                    #[doc = #comment]
+                    #[derive(Clone, Default)]
+                    pub struct #batch_ident {
+                        calls: odra::prelude::Vec<#ty_call>
+                    }
+                ),
+                parse_quote!(
+                    impl #batch_ident {
+                        #(#functions)*
+
+                        /// Returns the calls of the batch.
+                        pub fn into_calls(self) -> odra::prelude::Vec<#ty_call> {
+                            self.calls
+                        }
+                    }
+                ),
+                parse_quote!(
+                    impl #host_ref_ident {
+                        /// Returns an empty batch of calls to the contract.
+                        pub fn batch(&self) -> #batch_ident {
+                            #batch_ident::default()
+                        }
+                    }
+                ),
+            ]
+        })
+    }
+}
+
+fn batch_function_item(fun: &FnIR) -> syn::ItemFn {
+    let fun_name = fun.name();
+    let fun_name_str = fun.name_str();
+    let args = fun.typed_args();
+    let args_block = fn_utils::runtime_args_block(fun, ref_utils::insert_arg_stmt);
+    let comment = format!(" Adds a call of `{}` to the batch.", fun_name_str);
+
+    parse_quote!(
+        #[doc = #comment]
+        pub fn #fun_name(mut self #(, #args)*) -> Self {
+            self.calls.push(odra::multicall::encode_call(#fun_name_str, #args_block));
+            self
+        }
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::{self, mock};
+
+    #[test]
+    fn batch() {
+        let module = mock::module_multicall();
+        let actual = BatchItem::try_from(&module).unwrap();
+
+        let expected = quote::quote! {
+            /// Builds a batch of calls to the [Counter] entrypoints executed by `multicall`.
+            #[derive(Clone, Default)]
+            pub struct CounterBatch {
+                calls: odra::prelude::Vec<odra::multicall::Call>
+            }
+
+            impl CounterBatch {
+                /// Adds a call of `add` to the batch.
+                pub fn add(mut self, value: u32) -> Self {
+                    self.calls.push(odra::multicall::encode_call("add", {
+                        let mut named_args = odra::casper_types::RuntimeArgs::new();
+                        odra::args::EntrypointArgument::insert_runtime_arg(value.clone(), "value", &mut named_args);
+                        named_args
+                    }));
+                    self
+                }
+
+                /// Adds a call of `count` to the batch.
+                pub fn count(mut self) -> Self {
+                    self.calls.push(odra::multicall::encode_call("count", {
+                        let mut named_args = odra::casper_types::RuntimeArgs::new();
+                        named_args
+                    }));
+                    self
+                }
+
+                /// Returns the calls of the batch.
+                pub fn into_calls(self) -> odra::prelude::Vec<odra::multicall::Call> {
+                    self.calls
+                }
+            }
+
+            impl CounterHostRef {
+                /// Returns an empty batch of calls to the contract.
+                pub fn batch(&self) -> CounterBatch {
+                    CounterBatch::default()
+                }
+            }
+        };
+
+        test_utils::assert_eq(actual, expected);
+    }
+
+    #[test]
+    fn multicall_entrypoint() {
+        let module = mock::module_multicall();
+        let actual = module.self_code().unwrap();
+
+        let expected = quote::quote! {
+            impl Counter {
+                pub fn init(&mut self, value: u32) {
+                    self.value.set(value);
+                }
+
+                pub fn add(&mut self, value: u32) {
+                    self.value.add(value);
+                }
+
+                pub fn count(&self) -> u32 {
+                    self.value.get_or_default()
+                }
+
+                pub fn deposit(&mut self) {
+                }
+
+                /// Executes a batch of calls to the contract entrypoints in a single transaction.
+                ///
+                /// Every call is a pair of an entrypoint name and serialized runtime args.
+                /// Returns the serialized results of the calls. If any call reverts, the whole batch reverts.
+                pub fn multicall(
+                    &mut self,
+                    calls: odra::prelude::Vec<odra::multicall::Call>
+                ) -> odra::prelude::Vec<odra::casper_types::bytesrepr::Bytes> {
+                    let env = odra::module::Module::env(self);
+                    calls
+                        .iter()
+                        .map(|(entry_point, args)| {
+                            let call_env = odra::multicall::call_env(&env, args);
+                            match entry_point.as_str() {
+                                "add" => odra::multicall::call_result(&env, __counter_exec_parts::execute_add(call_env)),
+                                "count" => odra::multicall::call_result(&env, __counter_exec_parts::execute_count(call_env)),
+                                _ => env.revert(odra::prelude::ExecutionError::UnknownEntrypoint)
+                            }
+                        })
+                        .collect()
+                }
+            }
+        };
+
+        test_utils::assert_eq(actual, expected);
+    }
+
+    #[test]
+    fn no_batch_without_multicall() {
+        let module = mock::module_impl();
+        let actual = BatchItem::try_from(&module).unwrap();
+
+        test_utils::assert_eq(actual, quote::quote!());
+    }
+}
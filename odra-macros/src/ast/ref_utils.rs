@@ -71,20 +71,30 @@ fn call_def(fun: &FnIR) -> syn::Expr {
     let ty_call_def = utils::ty::call_def();
     let fun_name_str = fun.name_str();
     let args_block = fn_utils::runtime_args_block(fun, insert_arg_stmt);
-    let is_mut = fun.is_mut();
+    let is_mut = fun.is_mutable_entrypoint();
     let fun_name = utils::expr::string_from(fun_name_str);
-    syn::parse_quote!(#ty_call_def::new(#fun_name, #is_mut, #args_block))
+    let call_def = syn::parse_quote!(#ty_call_def::new(#fun_name, #is_mut, #args_block));
+    into_view(fun, call_def)
 }
 
 fn call_def_with_amount(fun: &FnIR) -> syn::Expr {
     let ty_call_def = utils::ty::call_def();
     let fun_name_str = fun.name_str();
     let args_block = runtime_args_with_amount_block(fun, insert_arg_stmt);
-    let is_mut = fun.is_mut();
+    let is_mut = fun.is_mutable_entrypoint();
     let attached_value = utils::member::attached_value();
     let fun_name = utils::expr::string_from(fun_name_str);
 
-    syn::parse_quote!(#ty_call_def::new(#fun_name, #is_mut, #args_block).with_amount(#attached_value))
+    let call_def = syn::parse_quote!(#ty_call_def::new(#fun_name, #is_mut, #args_block).with_amount(#attached_value));
+    into_view(fun, call_def)
+}
+
+/// Marks the call of a view, so it can be made from another view.
+fn into_view(fun: &FnIR, call_def: syn::Expr) -> syn::Expr {
+    match fun.is_view() {
+        true => syn::parse_quote!(#call_def.into_view()),
+        false => call_def
+    }
 }
 
 fn function_signature(fun: &FnIR) -> syn::Signature {
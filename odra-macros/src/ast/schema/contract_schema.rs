@@ -1,4 +1,4 @@
-use crate::ir::ModuleStructIR;
+use crate::ir::{ModuleImplIR, ModuleStructIR};
 use quote::ToTokens;
 
 pub struct SchemaItem {
@@ -71,6 +71,19 @@ impl TryFrom<&'_ ModuleStructIR> for SchemaItem {
     }
 }
 
+impl TryFrom<&'_ ModuleImplIR> for SchemaItem {
+    type Error = syn::Error;
+
+    fn try_from(module: &'_ ModuleImplIR) -> Result<Self, Self::Error> {
+        Ok(Self {
+            mod_ident: module.contract_schema_mod_ident()?,
+            module_ident: module.module_ident()?,
+            name: module.module_str()?,
+            version: String::new()
+        })
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::test_utils;
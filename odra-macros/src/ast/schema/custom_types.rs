@@ -9,38 +9,15 @@ use crate::{
 
 pub struct SchemaCustomTypesItem {
     module_ident: syn::Ident,
-    fns: Vec<FnIR>
+    fns: Vec<FnIR>,
+    interfaces: Vec<syn::Ident>
 }
 
 impl ToTokens for SchemaCustomTypesItem {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let module_ident = &self.module_ident;
-        let types = self
-            .fns
-            .iter()
-            .flat_map(|f| {
-                let args = f.typed_args();
-                let mut types = args
-                    .iter()
-                    .map(|arg| ty::unreferenced_ty(&arg.ty))
-                    .collect::<Vec<_>>();
-                if let syn::ReturnType::Type(_, t) = f.return_type() {
-                    types.push(*t);
-                };
-                types
-            })
-            .collect::<Vec<_>>();
-
-        let mut tmp = HashSet::<String>::new();
-        let mut chain = vec![];
-        types
-            .iter()
-            .for_each(|t| {
-                let v = quote::quote!(.chain(<#t as odra::schema::SchemaCustomTypes>::schema_types()));
-                if tmp.insert(v.to_string()) {
-                    chain.push(v);
-                }
-            });
+        let chain = custom_types_chain(&self.fns);
+        let interfaces = &self.interfaces;
 
         let item = quote::quote! {
             #[automatically_derived]
@@ -50,6 +27,7 @@ impl ToTokens for SchemaCustomTypesItem {
                     odra::prelude::BTreeSet::<Option<odra::schema::casper_contract_schema::CustomType>>::new()
                         .into_iter()
                         #(#chain)*
+                        #(.chain(#interfaces::schema_types()))*
                         .chain(<Self as odra::schema::SchemaEvents>::custom_types())
                         .collect()
                 }
@@ -60,13 +38,49 @@ impl ToTokens for SchemaCustomTypesItem {
     }
 }
 
+/// Returns the calls chaining the custom types of the arguments and return values of the functions.
+pub fn custom_types_chain(fns: &[FnIR]) -> Vec<proc_macro2::TokenStream> {
+    let types = fns
+        .iter()
+        .flat_map(|f| {
+            let args = f.typed_args();
+            let mut types = args
+                .iter()
+                .map(|arg| ty::unreferenced_ty(&arg.ty))
+                .collect::<Vec<_>>();
+            if let syn::ReturnType::Type(_, t) = f.return_type() {
+                types.push(*t);
+            };
+            types
+        })
+        .collect::<Vec<_>>();
+
+    let mut tmp = HashSet::<String>::new();
+    let mut chain = vec![];
+    types
+        .iter()
+        .for_each(|t| {
+            let v = quote::quote!(.chain(<#t as odra::schema::SchemaCustomTypes>::schema_types()));
+            if tmp.insert(v.to_string()) {
+                chain.push(v);
+            }
+        });
+    chain
+}
+
 impl TryFrom<&ModuleImplIR> for SchemaCustomTypesItem {
     type Error = syn::Error;
 
     fn try_from(ir: &ModuleImplIR) -> Result<Self, Self::Error> {
+        let interfaces = ir
+            .interfaces()
+            .iter()
+            .map(|interface| ir.interface_parts_mod_ident(interface, "test_parts"))
+            .collect::<syn::Result<_>>()?;
         Ok(Self {
             module_ident: ir.module_ident()?,
-            fns: ir.functions()?
+            fns: ir.functions()?,
+            interfaces
         })
     }
 }
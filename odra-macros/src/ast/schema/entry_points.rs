@@ -1,51 +1,28 @@
 use quote::ToTokens;
 
 use crate::{
-    ir::{FnIR, ModuleImplIR},
-    utils::ty
+    ir::{EntrypointGuard, FnIR, ModuleImplIR},
+    utils::{self, ty}
 };
 
 pub struct SchemaEntrypointsItem {
     module_ident: syn::Ident,
-    fns: Vec<FnIR>
+    fns: Vec<FnIR>,
+    interfaces: Vec<syn::Expr>
 }
 
 impl ToTokens for SchemaEntrypointsItem {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let module_ident = &self.module_ident;
-        let fns = self
-            .fns
-            .iter()
-            .map(|f| {
-                let desc = f
-                    .docs()
-                    .first()
-                    .map(|s| s.trim().to_string())
-                    .unwrap_or_default();
-                let name = f.name_str();
-                let ret_ty = match f.return_type() {
-                    syn::ReturnType::Default => quote::quote! { () },
-                    syn::ReturnType::Type(_, t) => quote::quote! { #t }
-                };
-                let is_mut = f.is_mut();
-                let args = args_to_tokens(&f.raw_typed_args());
-                quote::quote! {
-                    odra::schema::entry_point::<#ret_ty>(
-                        #name,
-                        #desc,
-                        #is_mut,
-                        odra::prelude::vec![ #(#args),* ]
-                    )
-                }
-            })
-            .collect::<Vec<_>>();
+        let entry_points =
+            utils::expr::extend_vec(schema_entrypoints_expr(&self.fns), &self.interfaces);
 
         let item = quote::quote! {
             #[automatically_derived]
             #[cfg(not(target_arch = "wasm32"))]
             impl odra::schema::SchemaEntrypoints for #module_ident {
                 fn schema_entrypoints() -> odra::prelude::vec::Vec<odra::schema::casper_contract_schema::Entrypoint> {
-                    odra::prelude::vec![ #(#fns),* ]
+                    #entry_points
                 }
             }
         };
@@ -54,6 +31,59 @@ impl ToTokens for SchemaEntrypointsItem {
     }
 }
 
+pub fn schema_entrypoints_expr(fns: &[FnIR]) -> proc_macro2::TokenStream {
+    let fns = fns
+        .iter()
+        .map(|f| {
+            let desc = f
+                .docs()
+                .first()
+                .map(|s| s.trim().to_string())
+                .unwrap_or_default();
+            let name = f.name_str();
+            let ret_ty = match f.return_type() {
+                syn::ReturnType::Default => quote::quote! { () },
+                syn::ReturnType::Type(_, t) => quote::quote! { #t }
+            };
+            let is_mut = f.is_mutable_entrypoint();
+            let args = args_to_tokens(&f.raw_typed_args());
+            let entry_point = quote::quote! {
+                odra::schema::entry_point::<#ret_ty>(
+                    #name,
+                    #desc,
+                    #is_mut,
+                    odra::prelude::vec![ #(#args),* ]
+                )
+            };
+            let guards = f.guards().iter().map(guard_name).collect::<Vec<_>>();
+            if guards.is_empty() {
+                entry_point
+            } else {
+                quote::quote! {
+                    odra::schema::with_guards(#entry_point, odra::prelude::vec![ #(#guards),* ])
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    quote::quote!(odra::prelude::vec![ #(#fns),* ])
+}
+
+fn guard_name(guard: &EntrypointGuard) -> String {
+    match guard {
+        EntrypointGuard::Owner(_) => String::from("owner"),
+        EntrypointGuard::Role(_, role) => format!("role:{}", role),
+        EntrypointGuard::Custom(path) => {
+            let name = path
+                .segments
+                .last()
+                .map(|segment| segment.ident.to_string())
+                .unwrap_or_default();
+            format!("guard:{}", name)
+        }
+    }
+}
+
 fn args_to_tokens(args: &[syn::PatType]) -> Vec<proc_macro2::TokenStream> {
     args.iter()
         .map(|syn::PatType { pat, ty, .. }| {
@@ -68,9 +98,18 @@ impl TryFrom<&ModuleImplIR> for SchemaEntrypointsItem {
     type Error = syn::Error;
 
     fn try_from(module: &ModuleImplIR) -> Result<Self, Self::Error> {
+        let interfaces = module
+            .interfaces()
+            .iter()
+            .map(|interface| {
+                let test_parts = module.interface_parts_mod_ident(interface, "test_parts")?;
+                Ok(syn::parse_quote!(#test_parts::schema_entrypoints()))
+            })
+            .collect::<syn::Result<_>>()?;
         Ok(Self {
             module_ident: module.module_ident()?,
-            fns: module.functions()?
+            fns: module.functions()?,
+            interfaces
         })
     }
 }
@@ -155,6 +194,41 @@ mod test {
         test_utils::assert_eq(actual, expected);
     }
 
+    #[test]
+    fn test_guarded_entrypoints() {
+        let module = test_utils::mock::module_guards();
+        let expected = quote!(
+            #[automatically_derived]
+            #[cfg(not(target_arch = "wasm32"))]
+            impl odra::schema::SchemaEntrypoints for Token {
+                fn schema_entrypoints() -> odra::prelude::vec::Vec<odra::schema::casper_contract_schema::Entrypoint> {
+                    odra::prelude::vec![
+                        odra::schema::with_guards(
+                            odra::schema::entry_point::<()>("pause", "", true, odra::prelude::vec![]),
+                            odra::prelude::vec!["owner"]
+                        ),
+                        odra::schema::with_guards(
+                            odra::schema::entry_point::<()>("unpause", "", true, odra::prelude::vec![]),
+                            odra::prelude::vec!["owner"]
+                        ),
+                        odra::schema::entry_point::<bool>("is_paused", "", false, odra::prelude::vec![]),
+                        odra::schema::with_guards(
+                            odra::schema::entry_point::<()>(
+                                "mint",
+                                "",
+                                true,
+                                odra::prelude::vec![odra::schema::argument::<U256>("amount")]
+                            ),
+                            odra::prelude::vec!["role:MINTER", "guard:not_paused"]
+                        )
+                    ]
+                }
+            }
+        );
+        let actual = SchemaEntrypointsItem::try_from(&module).unwrap();
+        test_utils::assert_eq(actual, expected);
+    }
+
     #[test]
     fn test_delegated_entrypoints() {
         let module = test_utils::mock::module_delegation();
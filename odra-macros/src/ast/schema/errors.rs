@@ -1,15 +1,20 @@
 use quote::ToTokens;
+use syn::parse_quote;
 use crate::{ast::utils::Named, ir::{EnumeratedTypedField, ModuleStructIR, TypeIR}, utils};
 
 pub struct SchemaErrorsItem {
-    module_ident: syn::Ident,
+    impl_generics: proc_macro2::TokenStream,
+    module_ty: syn::Type,
+    where_clause: Option<syn::WhereClause>,
     errors: Option<syn::Type>,
     fields: Vec<EnumeratedTypedField>
 }
 
 impl ToTokens for SchemaErrorsItem {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        let module_ident = &self.module_ident;
+        let impl_generics = &self.impl_generics;
+        let module_ty = &self.module_ty;
+        let where_clause = &self.where_clause;
 
         let errors = self.errors
             .iter()
@@ -22,7 +27,7 @@ impl ToTokens for SchemaErrorsItem {
         let item = quote::quote! {
             #[automatically_derived]
             #[cfg(not(target_arch = "wasm32"))]
-            impl odra::schema::SchemaErrors for #module_ident {
+            impl #impl_generics odra::schema::SchemaErrors for #module_ty #where_clause {
                 fn schema_errors() -> odra::prelude::Vec<odra::schema::casper_contract_schema::UserError> {
                     odra::prelude::BTreeSet::<odra::schema::casper_contract_schema::UserError>::new()
                         .into_iter()
@@ -43,7 +48,9 @@ impl TryFrom<&ModuleStructIR> for SchemaErrorsItem {
 
     fn try_from(ir: &ModuleStructIR) -> Result<Self, Self::Error> {
         Ok(Self {
-            module_ident: ir.module_ident(),
+            impl_generics: ir.impl_generics(),
+            module_ty: ir.module_ty(),
+            where_clause: ir.where_clause(&parse_quote!(odra::schema::SchemaErrors))?,
             errors: ir.errors(),
             fields: ir.typed_fields()?
         })
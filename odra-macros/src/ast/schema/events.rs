@@ -1,16 +1,21 @@
 use quote::ToTokens;
+use syn::parse_quote;
 
 use crate::ir::{EnumeratedTypedField, ModuleStructIR};
 
 pub struct SchemaEventsItem {
-    module_ident: syn::Ident,
+    impl_generics: proc_macro2::TokenStream,
+    module_ty: syn::Type,
+    where_clause: Option<syn::WhereClause>,
     events: Vec<syn::Type>,
     fields: Vec<EnumeratedTypedField>
 }
 
 impl ToTokens for SchemaEventsItem {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        let module_ident = &self.module_ident;
+        let impl_generics = &self.impl_generics;
+        let module_ty = &self.module_ty;
+        let where_clause = &self.where_clause;
         let events = self.events.iter().map(|ty| {
             quote::quote!(odra::schema::event(&<#ty as odra::casper_event_standard::EventInstance>::name()))
         }).collect::<Vec<_>>();
@@ -38,7 +43,7 @@ impl ToTokens for SchemaEventsItem {
         let item = quote::quote! {
             #[automatically_derived]
             #[cfg(not(target_arch = "wasm32"))]
-            impl odra::schema::SchemaEvents for #module_ident {
+            impl #impl_generics odra::schema::SchemaEvents for #module_ty #where_clause {
                 fn schema_events() -> odra::prelude::Vec<odra::schema::casper_contract_schema::Event> {
                     odra::prelude::vec::Vec::<odra::schema::casper_contract_schema::Event>::new()
                         .into_iter()
@@ -70,7 +75,9 @@ impl TryFrom<&ModuleStructIR> for SchemaEventsItem {
 
     fn try_from(ir: &ModuleStructIR) -> Result<Self, Self::Error> {
         Ok(Self {
-            module_ident: ir.module_ident(),
+            impl_generics: ir.impl_generics(),
+            module_ty: ir.module_ty(),
+            where_clause: ir.where_clause(&parse_quote!(odra::schema::SchemaEvents))?,
             events: ir.events(),
             fields: ir.typed_fields()?
         })
@@ -7,7 +7,7 @@ mod errors;
 
 pub use contract_schema::SchemaItem;
 pub use custom_item::SchemaCustomTypeItem;
-pub use custom_types::SchemaCustomTypesItem;
-pub use entry_points::SchemaEntrypointsItem;
+pub use custom_types::{custom_types_chain, SchemaCustomTypesItem};
+pub use entry_points::{schema_entrypoints_expr, SchemaEntrypointsItem};
 pub use events::SchemaEventsItem;
 pub use errors::{SchemaErrorItem, SchemaErrorsItem};
@@ -6,6 +6,7 @@ use crate::{ir::ModuleImplIR, utils};
 use super::{
     deployer_item::DeployerItem,
     host_ref_item::{HasIdentTraitImplItem, HostRefItem},
+    multicall_item::BatchItem,
     parts_utils::{UsePreludeItem, UseSuperItem}
 };
 
@@ -65,7 +66,9 @@ pub struct TestPartsItem {
     #[syn(in = brace_token)]
     trait_has_ident_impl_item: HasIdentTraitImplItem,
     #[syn(in = brace_token)]
-    deployer: DeployerItem
+    deployer: DeployerItem,
+    #[syn(in = brace_token)]
+    batch: BatchItem
 }
 
 #[cfg(test)]
@@ -7,18 +7,34 @@ use crate::utils::misc::AsType;
 #[derive(syn_derive::ToTokens)]
 pub struct ImplItem {
     impl_token: syn::Token![impl],
+    impl_generics: Option<proc_macro2::TokenStream>,
     ty: syn::Type,
     for_token: syn::Token![for],
-    for_ty: syn::Type
+    for_ty: syn::Type,
+    where_clause: Option<syn::WhereClause>
 }
 
 impl ImplItem {
     fn new<T: Named>(named: &T, ty: syn::Type) -> syn::Result<Self> {
         Ok(Self {
             impl_token: Default::default(),
+            impl_generics: None,
             ty,
             for_token: Default::default(),
-            for_ty: named.name()?.as_type()
+            for_ty: named.name()?.as_type(),
+            where_clause: None
+        })
+    }
+
+    /// Creates an implementation of the given trait for a module, including the generic parameters of the module.
+    fn for_module(ir: &ModuleStructIR, ty: syn::Type) -> syn::Result<Self> {
+        Ok(Self {
+            impl_token: Default::default(),
+            impl_generics: Some(ir.impl_generics()),
+            where_clause: ir.where_clause(&ty)?,
+            ty,
+            for_token: Default::default(),
+            for_ty: ir.module_ty()
         })
     }
 
@@ -34,17 +50,23 @@ impl ImplItem {
         Self::new(ir, utils::ty::cl_typed())
     }
 
-    pub fn has_events<T: Named>(named: &T) -> syn::Result<Self> {
-        Self::new(named, utils::ty::has_events())
+    pub fn has_events(ir: &TypeIR) -> syn::Result<Self> {
+        Self::new(ir, utils::ty::has_events())
+    }
+
+    pub fn module_has_events(ir: &ModuleStructIR) -> syn::Result<Self> {
+        Self::for_module(ir, utils::ty::has_events())
     }
 
     pub fn from<T: Named>(named: &T, for_ty: &syn::Type) -> syn::Result<Self> {
         let ty_from = utils::ty::from(&named.name()?);
         Ok(Self {
             impl_token: Default::default(),
+            impl_generics: None,
             ty: ty_from,
             for_token: Default::default(),
-            for_ty: for_ty.clone()
+            for_ty: for_ty.clone(),
+            where_clause: None
         })
     }
 }
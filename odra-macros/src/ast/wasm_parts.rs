@@ -1,4 +1,4 @@
-use quote::TokenStreamExt;
+use quote::{quote, ToTokens, TokenStreamExt};
 use syn::parse_quote;
 
 use crate::utils::misc::AsType;
@@ -31,7 +31,12 @@ pub struct WasmPartsModuleItem {
     call_fn: CallFnItem,
     #[syn(in = braces)]
     #[to_tokens(|tokens, f| tokens.append_all(f))]
-    entry_points: Vec<NoMangleFnItem>
+    entry_points: Vec<NoMangleFnItem>,
+    #[syn(in = braces)]
+    template_export_macro: Option<syn::ItemMacro>,
+    #[syn(in = braces)]
+    #[to_tokens(|tokens, f| tokens.append_all(f))]
+    factory_exports: Vec<syn::ItemMacro>
 }
 
 impl TryFrom<&'_ ModuleImplIR> for WasmPartsModuleItem {
@@ -39,6 +44,19 @@ impl TryFrom<&'_ ModuleImplIR> for WasmPartsModuleItem {
 
     fn try_from(module: &'_ ModuleImplIR) -> Result<Self, Self::Error> {
         let module_str = module.module_str()?;
+        let entry_points = NoMangleFnItem::for_module(module)?;
+        // A factory exports the entry points of the installed contracts, as they share the code with the factory.
+        let export_ident = utils::ident::template_export();
+        let factory_exports = module
+            .factory_of_items("exports")
+            .into_iter()
+            .zip(module.factory_of_items("template"))
+            .map(|(exports, template)| parse_quote!(#exports! { #export_ident, #template }))
+            .collect::<Vec<syn::ItemMacro>>();
+        let template_export_macro = match factory_exports.is_empty() {
+            true => None,
+            false => Some(template_export_macro(&entry_points))
+        };
         Ok(Self {
             attrs: vec![utils::attr::wasm32(), utils::attr::odra_module(&module_str)],
             mod_token: Default::default(),
@@ -48,19 +66,42 @@ impl TryFrom<&'_ ModuleImplIR> for WasmPartsModuleItem {
             use_prelude: UsePreludeItem,
             entry_points_fn: module.try_into()?,
             call_fn: module.try_into()?,
-            entry_points: module
-                .functions()?
-                .iter()
-                .map(|f| (module, f))
-                .map(TryInto::try_into)
-                .collect::<Result<Vec<_>, _>>()?
+            entry_points,
+            template_export_macro,
+            factory_exports
         })
     }
 }
 
+/// A macro exporting an entry point of an installed contract, called back by [FactoryExportsMacroItem].
+///
+/// The entry points sharing a name with an export of the factory are skipped, the factory export is called instead.
+fn template_export_macro(entry_points: &[NoMangleFnItem]) -> syn::ItemMacro {
+    let export_ident = utils::ident::template_export();
+    let no_mangle = utils::attr::no_mangle();
+    let names = entry_points
+        .iter()
+        .map(|item| &item.sig.ident)
+        .chain(std::iter::once(&utils::ident::call()))
+        .cloned()
+        .collect::<Vec<_>>();
+    parse_quote!(
+        macro_rules! #export_ident {
+            #(([$($template:tt)*] #names) => {};)*
+            ([$($template:tt)*] $entry_point:ident) => {
+                #no_mangle
+                fn $entry_point() {
+                    $($template)*::$entry_point()
+                }
+            };
+        }
+    )
+}
+
 #[derive(syn_derive::ToTokens)]
 struct EntryPointsFnItem {
     inline_attr: syn::Attribute,
+    vis: syn::Visibility,
     sig: syn::Signature,
     #[syn(braced)]
     braces: syn::token::Brace,
@@ -70,6 +111,12 @@ struct EntryPointsFnItem {
     #[to_tokens(|tokens, f| tokens.append_all(f))]
     items: Vec<AddEntryPointStmtItem>,
     #[syn(in = braces)]
+    #[to_tokens(|tokens, f| tokens.append_all(f))]
+    interface_stmts: Vec<syn::Stmt>,
+    #[syn(in = braces)]
+    #[to_tokens(|tokens, f| tokens.append_all(f))]
+    template_stmts: Vec<syn::Stmt>,
+    #[syn(in = braces)]
     ret: syn::Expr
 }
 
@@ -81,21 +128,184 @@ impl TryFrom<&'_ ModuleImplIR> for EntryPointsFnItem {
         let ident_entry_points = utils::ident::entry_points();
         let expr_entry_points = utils::expr::new_entry_points();
 
+        // A factory must list the entry points of the installed contract, otherwise they are removed from its wasm.
+        let template_stmts = module
+            .factory_of_items("template")
+            .into_iter()
+            .map(|template| {
+                parse_quote!(
+                    odra::odra_casper_wasm_env::host_functions::add_template_entry_points(
+                        &mut #ident_entry_points,
+                        #template::#ident_entry_points()
+                    );
+                )
+            })
+            .collect();
+        let interface_stmts = module
+            .interfaces()
+            .iter()
+            .map(|interface| {
+                let wasm_parts_ident = module.interface_parts_mod_ident(interface, "wasm_parts")?;
+                Ok(parse_quote!(
+                    #wasm_parts_ident::#ident_entry_points()
+                        .take_entry_points()
+                        .into_iter()
+                        .for_each(|entry_point| #ident_entry_points.add_entry_point(entry_point));
+                ))
+            })
+            .collect::<syn::Result<_>>()?;
+
         Ok(Self {
             inline_attr: utils::attr::inline(),
+            vis: syn::Visibility::Inherited,
             sig: parse_quote!(fn #ident_entry_points() -> #ty_entry_points),
             braces: Default::default(),
             var_declaration: parse_quote!(let mut #ident_entry_points = #expr_entry_points;),
-            items: module
-                .functions()?
-                .iter()
-                .map(TryInto::try_into)
-                .collect::<Result<Vec<_>, _>>()?,
+            items: AddEntryPointStmtItem::for_module(module)?,
+            interface_stmts,
+            template_stmts,
             ret: parse_quote!(#ident_entry_points)
         })
     }
 }
 
+/// Wasm parts of a module installed by a factory.
+///
+/// The entry points are exported from the wasm of the factory by [FactoryExportsMacroItem],
+/// as the contracts installed by the factory share the code with the factory.
+#[derive(syn_derive::ToTokens)]
+pub struct FactoryTemplateModuleItem {
+    #[to_tokens(|tokens, f| tokens.append_all(f))]
+    attrs: Vec<syn::Attribute>,
+    vis: syn::Visibility,
+    mod_token: syn::token::Mod,
+    ident: syn::Ident,
+    #[syn(braced)]
+    braces: syn::token::Brace,
+    #[syn(in = braces)]
+    use_super: UseSuperItem,
+    #[syn(in = braces)]
+    use_prelude: UsePreludeItem,
+    #[syn(in = braces)]
+    entry_points_fn: EntryPointsFnItem,
+    #[syn(in = braces)]
+    #[to_tokens(|tokens, f| tokens.append_all(f))]
+    entry_points: Vec<NoMangleFnItem>
+}
+
+impl TryFrom<&'_ ModuleImplIR> for FactoryTemplateModuleItem {
+    type Error = syn::Error;
+
+    fn try_from(module: &'_ ModuleImplIR) -> Result<Self, Self::Error> {
+        let mut entry_points_fn = EntryPointsFnItem::try_from(module)?;
+        entry_points_fn.vis = utils::syn::visibility_pub();
+        let mut entry_points = NoMangleFnItem::for_module(module)?;
+        entry_points.iter_mut().for_each(|item| {
+            item.attr = None;
+            item.vis = utils::syn::visibility_pub();
+        });
+        Ok(Self {
+            attrs: vec![utils::attr::wasm32(), utils::attr::doc_hidden()],
+            vis: utils::syn::visibility_pub(),
+            mod_token: Default::default(),
+            ident: module.factory_template_mod_ident()?,
+            braces: Default::default(),
+            use_super: UseSuperItem,
+            use_prelude: UsePreludeItem,
+            entry_points_fn,
+            entry_points
+        })
+    }
+}
+
+/// A macro exporting the entry points of a module installed by a factory.
+///
+/// The macro is invoked in the wasm parts of every factory declaring the module in `factory_of`,
+/// with the macro exporting a single entry point and the path to the [FactoryTemplateModuleItem] of the module.
+pub struct FactoryExportsMacroItem {
+    code: proc_macro2::TokenStream
+}
+
+impl ToTokens for FactoryExportsMacroItem {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        tokens.extend(self.code.clone());
+    }
+}
+
+impl TryFrom<&'_ ModuleImplIR> for FactoryExportsMacroItem {
+    type Error = syn::Error;
+
+    fn try_from(module: &'_ ModuleImplIR) -> Result<Self, Self::Error> {
+        let macro_ident = module.factory_exports_macro_ident()?;
+        let alias = module.factory_exports_macro_alias()?;
+        let wasm32 = utils::attr::wasm32();
+        let fns = NoMangleFnItem::for_module(module)?
+            .into_iter()
+            .map(|item| item.sig.ident)
+            .collect::<Vec<_>>();
+
+        Ok(Self {
+            code: quote!(
+                #wasm32
+                #[doc(hidden)]
+                #[macro_export]
+                macro_rules! #macro_ident {
+                    ($export:ident, $($template:tt)*) => {
+                        #($export! { [$($template)*] #fns })*
+                    };
+                }
+
+                #wasm32
+                #[doc(hidden)]
+                #[allow(unused_imports)]
+                pub use #macro_ident as #alias;
+            )
+        })
+    }
+}
+
+/// Wasm parts of an implementation of an interface.
+///
+/// The entry points are exported from the wasm of the module implementing the interface.
+#[derive(syn_derive::ToTokens)]
+pub struct InterfaceWasmPartsModuleItem {
+    #[to_tokens(|tokens, f| tokens.append_all(f))]
+    attrs: Vec<syn::Attribute>,
+    mod_token: syn::token::Mod,
+    ident: syn::Ident,
+    #[syn(braced)]
+    braces: syn::token::Brace,
+    #[syn(in = braces)]
+    use_super: UseSuperItem,
+    #[syn(in = braces)]
+    use_prelude: UsePreludeItem,
+    #[syn(in = braces)]
+    entry_points_fn: EntryPointsFnItem,
+    #[syn(in = braces)]
+    #[to_tokens(|tokens, f| tokens.append_all(f))]
+    entry_points: Vec<NoMangleFnItem>
+}
+
+impl TryFrom<&'_ ModuleImplIR> for InterfaceWasmPartsModuleItem {
+    type Error = syn::Error;
+
+    fn try_from(module: &'_ ModuleImplIR) -> Result<Self, Self::Error> {
+        let module_str = module.module_str()?;
+        let mut entry_points_fn = EntryPointsFnItem::try_from(module)?;
+        entry_points_fn.vis = utils::syn::visibility_pub();
+        Ok(Self {
+            attrs: vec![utils::attr::wasm32(), utils::attr::odra_module(&module_str)],
+            mod_token: Default::default(),
+            ident: module.wasm_parts_mod_ident()?,
+            braces: Default::default(),
+            use_super: UseSuperItem,
+            use_prelude: UsePreludeItem,
+            entry_points_fn,
+            entry_points: NoMangleFnItem::for_module(module)?
+        })
+    }
+}
+
 #[derive(syn_derive::ToTokens)]
 struct CallFnItem {
     attr: syn::Attribute,
@@ -117,6 +327,7 @@ impl TryFrom<&'_ ModuleImplIR> for CallFnItem {
         let module_ident = module.module_ident()?.as_type();
         let ident_args = utils::ident::named_args();
         let ident_schemas = utils::ident::schemas();
+        let ident_call = utils::ident::call();
         let ty_args = utils::ty::runtime_args();
         let ident_entry_points = utils::ident::entry_points();
         let runtime_args_expr: syn::Expr = match module.constructor() {
@@ -142,7 +353,7 @@ impl TryFrom<&'_ ModuleImplIR> for CallFnItem {
 
         Ok(Self {
             attr: utils::attr::no_mangle(),
-            sig: parse_quote!(fn call()),
+            sig: parse_quote!(fn #ident_call()),
             braces: Default::default(),
             schemas_init_stmt: parse_quote!(let #ident_schemas = #expr_new_schemas;),
             runtime_args_stmt: parse_quote!(#runtime_args_expr;),
@@ -153,7 +364,8 @@ impl TryFrom<&'_ ModuleImplIR> for CallFnItem {
 
 #[derive(syn_derive::ToTokens)]
 struct NoMangleFnItem {
-    attr: syn::Attribute,
+    attr: Option<syn::Attribute>,
+    vis: syn::Visibility,
     sig: syn::Signature,
     #[syn(braced)]
     braces: syn::token::Brace,
@@ -187,7 +399,8 @@ impl TryFrom<(&'_ ModuleImplIR, &'_ FnIR)> for NoMangleFnItem {
         };
 
         Ok(Self {
-            attr: utils::attr::no_mangle(),
+            attr: Some(utils::attr::no_mangle()),
+            vis: syn::Visibility::Inherited,
             sig: parse_quote!(fn #fn_ident()),
             braces: Default::default(),
             execute_stmt,
@@ -196,6 +409,23 @@ impl TryFrom<(&'_ ModuleImplIR, &'_ FnIR)> for NoMangleFnItem {
     }
 }
 
+impl NoMangleFnItem {
+    /// Exports all the entrypoints of the module, the receive and the fallback entrypoints
+    /// are exported under the reserved names as well.
+    fn for_module(module: &'_ ModuleImplIR) -> syn::Result<Vec<Self>> {
+        let mut items = vec![];
+        for func in module.functions()? {
+            items.push(Self::try_from((module, &func))?);
+            for name in wasm_parts_utils::reserved_names(&func) {
+                let mut item = Self::try_from((module, &func))?;
+                item.sig.ident = name;
+                items.push(item);
+            }
+        }
+        Ok(items)
+    }
+}
+
 #[derive(syn_derive::ToTokens)]
 struct AddEntryPointStmtItem {
     var_ident: syn::Ident,
@@ -223,6 +453,24 @@ impl TryFrom<&'_ FnIR> for AddEntryPointStmtItem {
     }
 }
 
+impl AddEntryPointStmtItem {
+    /// Adds all the entrypoints of the module, the receive and the fallback entrypoints
+    /// are added under the reserved names as well.
+    fn for_module(module: &'_ ModuleImplIR) -> syn::Result<Vec<Self>> {
+        let mut items = vec![];
+        for func in module.functions()? {
+            items.push(Self::try_from(&func)?);
+            for name in wasm_parts_utils::reserved_names(&func) {
+                let mut item = Self::try_from(&func)?;
+                let name = name.to_string();
+                item.new_entry_point_expr.params[0] = parse_quote!(#name);
+                items.push(item);
+            }
+        }
+        Ok(items)
+    }
+}
+
 #[derive(syn_derive::ToTokens)]
 struct NewEntryPointItem {
     ty: syn::Type,
@@ -468,6 +716,90 @@ mod test {
         test_utils::assert_eq(actual, expected);
     }
 
+    #[test]
+    fn test_receive_and_fallback() {
+        let module = test_utils::mock::module_receive();
+        let actual = WasmPartsModuleItem::try_from(&module).unwrap();
+
+        let expected = quote::quote! {
+            #[cfg(target_arch = "wasm32")]
+            #[cfg(odra_module = "DonationBox")]
+            mod __donation_box_wasm_parts {
+                use super::*;
+                use odra::prelude::*;
+
+                #[inline]
+                fn entry_points() -> odra::casper_types::EntryPoints {
+                    let mut entry_points = odra::casper_types::EntryPoints::new();
+                    entry_points.add_entry_point(odra::casper_types::EntryPoint::new(
+                        "donate",
+                        vec![],
+                        <() as odra::casper_types::CLTyped>::cl_type(),
+                        odra::casper_types::EntryPointAccess::Public,
+                        odra::casper_types::EntryPointType::Contract
+                    ));
+                    entry_points.add_entry_point(odra::casper_types::EntryPoint::new(
+                        "__odra_receive",
+                        vec![],
+                        <() as odra::casper_types::CLTyped>::cl_type(),
+                        odra::casper_types::EntryPointAccess::Public,
+                        odra::casper_types::EntryPointType::Contract
+                    ));
+                    entry_points.add_entry_point(odra::casper_types::EntryPoint::new(
+                        "fallback",
+                        vec![],
+                        <() as odra::casper_types::CLTyped>::cl_type(),
+                        odra::casper_types::EntryPointAccess::Public,
+                        odra::casper_types::EntryPointType::Contract
+                    ));
+                    entry_points.add_entry_point(odra::casper_types::EntryPoint::new(
+                        "__odra_fallback",
+                        vec![],
+                        <() as odra::casper_types::CLTyped>::cl_type(),
+                        odra::casper_types::EntryPointAccess::Public,
+                        odra::casper_types::EntryPointType::Contract
+                    ));
+                    entry_points
+                }
+
+                #[no_mangle]
+                fn call() {
+                    let schemas = odra::casper_event_standard::Schemas(
+                        <DonationBox as odra::contract_def::HasEvents>::event_schemas()
+                    );
+                    let named_args = Option::<odra::casper_types::RuntimeArgs>::None;
+                    odra::odra_casper_wasm_env::host_functions::install_contract(
+                        entry_points(),
+                        schemas,
+                        named_args
+                    );
+                }
+
+                #[no_mangle]
+                fn donate() {
+                    __donation_box_exec_parts::execute_donate(odra::odra_casper_wasm_env::WasmContractEnv::new_env());
+                }
+
+                #[no_mangle]
+                fn __odra_receive() {
+                    __donation_box_exec_parts::execute_donate(odra::odra_casper_wasm_env::WasmContractEnv::new_env());
+                }
+
+                #[no_mangle]
+                fn fallback() {
+                    __donation_box_exec_parts::execute_fallback(odra::odra_casper_wasm_env::WasmContractEnv::new_env());
+                }
+
+                #[no_mangle]
+                fn __odra_fallback() {
+                    __donation_box_exec_parts::execute_fallback(odra::odra_casper_wasm_env::WasmContractEnv::new_env());
+                }
+            }
+        };
+
+        test_utils::assert_eq(actual, expected);
+    }
+
     #[test]
     fn test_delegate() {
         let module = test_utils::mock::module_delegation();
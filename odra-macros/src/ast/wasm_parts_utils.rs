@@ -42,3 +42,18 @@ pub fn insert_arg_stmt(arg: &FnArgIR) -> syn::Stmt {
         &mut #args
     );)
 }
+
+/// Returns the reserved names under which the entrypoint is additionally exported.
+///
+/// The host calls the receive and the fallback entrypoints by the reserved names,
+/// as it does not know their names.
+pub fn reserved_names(func: &FnIR) -> Vec<syn::Ident> {
+    let mut names = vec![];
+    if func.is_receive() {
+        names.push(utils::ident::receive_entry_point());
+    }
+    if func.is_fallback() {
+        names.push(utils::ident::fallback_entry_point());
+    }
+    names
+}
@@ -73,6 +73,53 @@ impl OdraAttribute {
             .iter()
             .any(|attr_kind| matches!(attr_kind, &AttrType::NonReentrant))
     }
+
+    pub fn is_view(&self) -> bool {
+        self.types
+            .iter()
+            .any(|attr_kind| matches!(attr_kind, &AttrType::View))
+    }
+
+    pub fn is_receive(&self) -> bool {
+        self.types
+            .iter()
+            .any(|attr_kind| matches!(attr_kind, &AttrType::Receive))
+    }
+
+    pub fn is_fallback(&self) -> bool {
+        self.types
+            .iter()
+            .any(|attr_kind| matches!(attr_kind, &AttrType::Fallback))
+    }
+
+    pub fn guards(&self) -> Vec<EntrypointGuard> {
+        self.types
+            .iter()
+            .filter_map(|attr_kind| match attr_kind {
+                AttrType::OnlyOwner(field) => Some(EntrypointGuard::Owner(field.clone())),
+                AttrType::OnlyRole(field, role) => {
+                    Some(EntrypointGuard::Role(field.clone(), role.clone()))
+                }
+                AttrType::Guard(path) => Some(EntrypointGuard::Custom(path.clone())),
+                _ => None
+            })
+            .collect()
+    }
+}
+
+/// An access check executed before an entrypoint is called.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum EntrypointGuard {
+    /// `#[odra(only_owner)]` - the caller must be the owner of the contract.
+    ///
+    /// If a field is given, e.g. `#[odra(only_owner = ownable)]`, the owner is checked
+    /// by the submodule stored in the field, otherwise by the module itself.
+    Owner(Option<Ident>),
+    /// `#[odra(only_role(access_control, "ROLE"))]` - the caller must have the given role,
+    /// checked by the submodule stored in the given field.
+    Role(Ident, String),
+    /// `#[odra(guard = Self::check)]` - the given function must not revert.
+    Custom(Path)
 }
 
 impl TryFrom<syn::Attribute> for OdraAttribute {
@@ -94,7 +141,13 @@ impl TryFrom<syn::Attribute> for OdraAttribute {
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 enum AttrType {
     Payable,
-    NonReentrant
+    NonReentrant,
+    View,
+    Receive,
+    Fallback,
+    OnlyOwner(Option<Ident>),
+    OnlyRole(Ident, String),
+    Guard(Path)
 }
 
 impl TryFrom<&'_ syn::Meta> for AttrType {
@@ -105,10 +158,51 @@ impl TryFrom<&'_ syn::Meta> for AttrType {
             Meta::Path(path) => match path.try_to_string(path)?.as_str() {
                 "payable" => Ok(AttrType::Payable),
                 "non_reentrant" => Ok(AttrType::NonReentrant),
+                "view" => Ok(AttrType::View),
+                "receive" => Ok(AttrType::Receive),
+                "fallback" => Ok(AttrType::Fallback),
+                "only_owner" => Ok(AttrType::OnlyOwner(None)),
                 _ => Err(AttrTypeError::Path(meta).into())
             },
-            Meta::List(_) => Err(AttrTypeError::List(meta).into()),
-            Meta::NameValue(_) => Err(AttrTypeError::NameValue(meta).into())
+            Meta::List(list) => match list.path.try_to_string(&list.path)?.as_str() {
+                "only_role" => {
+                    let (field, role) = list
+                        .parse_args_with(|input: syn::parse::ParseStream| {
+                            let field = input.parse::<Ident>()?;
+                            input.parse::<Token![,]>()?;
+                            let role = input.parse::<syn::LitStr>()?;
+                            Ok((field, role.value()))
+                        })
+                        .map_err(|_| syn::Error::new_spanned(list, "expected a field and a role name, e.g. only_role(access_control, \"MINTER\")"))?;
+                    Ok(AttrType::OnlyRole(field, role))
+                }
+                _ => Err(AttrTypeError::List(meta).into())
+            },
+            Meta::NameValue(name_value) => {
+                match name_value.path.try_to_string(&name_value.path)?.as_str() {
+                    "only_owner" => match &name_value.value {
+                        syn::Expr::Path(path) if path.path.get_ident().is_some() => {
+                            Ok(AttrType::OnlyOwner(path.path.get_ident().cloned()))
+                        }
+                        value => Err(syn::Error::new_spanned(
+                            value,
+                            "expected a name of a field, e.g. only_owner = ownable"
+                        ))
+                    },
+                    "only_role" => Err(syn::Error::new_spanned(
+                        meta,
+                        "expected a field and a role name, e.g. only_role(access_control, \"MINTER\")"
+                    )),
+                    "guard" => match &name_value.value {
+                        syn::Expr::Path(path) => Ok(AttrType::Guard(path.path.clone())),
+                        value => Err(syn::Error::new_spanned(
+                            value,
+                            "expected a path to a function, e.g. guard = Self::check"
+                        ))
+                    },
+                    _ => Err(AttrTypeError::NameValue(meta).into())
+                }
+            }
         }
     }
 }
@@ -228,6 +322,76 @@ mod tests {
         );
     }
 
+    #[test]
+    fn view_attr_works() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[odra(view)]
+            },
+            Ok(Attribute::Odra(OdraAttribute {
+                types: vec![AttrType::View]
+            }))
+        );
+    }
+
+    #[test]
+    fn receive_and_fallback_attrs_work() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[odra(receive, fallback)]
+            },
+            Ok(Attribute::Odra(OdraAttribute {
+                types: vec![AttrType::Receive, AttrType::Fallback]
+            }))
+        );
+    }
+
+    #[test]
+    fn access_attrs_work() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[odra(only_owner, only_role(access_control, "MINTER"), guard = Self::check)]
+            },
+            Ok(Attribute::Odra(OdraAttribute {
+                types: vec![
+                    AttrType::OnlyOwner(None),
+                    AttrType::OnlyRole(syn::parse_quote!(access_control), String::from("MINTER")),
+                    AttrType::Guard(syn::parse_quote!(Self::check)),
+                ]
+            }))
+        );
+
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[odra(only_owner = ownable)]
+            },
+            Ok(Attribute::Odra(OdraAttribute {
+                types: vec![AttrType::OnlyOwner(Some(syn::parse_quote!(ownable)))]
+            }))
+        );
+
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[odra(only_role = "MINTER")]
+            },
+            Err("expected a field and a role name, e.g. only_role(access_control, \"MINTER\")")
+        );
+
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[odra(only_role(access_control, MINTER))]
+            },
+            Err("expected a field and a role name, e.g. only_role(access_control, \"MINTER\")")
+        );
+
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[odra(only_owner = self.ownable)]
+            },
+            Err("expected a name of a field, e.g. only_owner = ownable")
+        );
+    }
+
     #[test]
     fn non_odra_attr_works() {
         let expected_value: syn::Attribute = syn::parse_quote! {
@@ -26,6 +26,11 @@ mod kw {
     syn::custom_keyword!(version);
     syn::custom_keyword!(events);
     syn::custom_keyword!(errors);
+    syn::custom_keyword!(multicall);
+    syn::custom_keyword!(factory);
+    syn::custom_keyword!(factory_of);
+    syn::custom_keyword!(instances);
+    syn::custom_keyword!(implements);
 }
 
 #[derive(Default, Clone)]
@@ -33,7 +38,17 @@ pub struct ModuleConfiguration {
     pub events: ModuleEvents,
     pub errors: ModuleErrors,
     pub name: ModuleName,
-    pub version: ModuleVersion
+    pub version: ModuleVersion,
+    pub multicall: bool,
+    pub factory: bool,
+    /// The modules installed by the factory, their entry points are exported from the factory wasm.
+    pub factory_of: ModuleFactoryOf,
+    pub instances: ModuleInstances,
+    /// The concrete type of a generic module, set for the generated module instances only.
+    pub instance_of: Option<syn::Type>,
+    pub implements: ModuleInterfaces,
+    /// Set for an implementation of an interface annotated with `#[odra::interface]`.
+    pub interface_impl: bool
 }
 
 impl Parse for ModuleConfiguration {
@@ -42,6 +57,11 @@ impl Parse for ModuleConfiguration {
         let mut version = None;
         let mut events = None;
         let mut errors = None;
+        let mut multicall = false;
+        let mut factory = false;
+        let mut factory_of = None;
+        let mut instances = None;
+        let mut implements = None;
 
         while !input.is_empty() {
             if events.is_none() && input.peek(kw::events) {
@@ -67,6 +87,38 @@ impl Parse for ModuleConfiguration {
                 let _ = input.parse::<Token![,]>(); // optional comma
                 continue;
             }
+
+            if !multicall && input.peek(kw::multicall) {
+                input.parse::<kw::multicall>()?;
+                multicall = true;
+                let _ = input.parse::<Token![,]>(); // optional comma
+                continue;
+            }
+
+            if !factory && input.peek(kw::factory) {
+                input.parse::<kw::factory>()?;
+                factory = true;
+                let _ = input.parse::<Token![,]>(); // optional comma
+                continue;
+            }
+
+            if factory_of.is_none() && input.peek(kw::factory_of) {
+                factory_of = Some(input.parse::<ModuleFactoryOf>()?);
+                let _ = input.parse::<Token![,]>(); // optional comma
+                continue;
+            }
+
+            if instances.is_none() && input.peek(kw::instances) {
+                instances = Some(input.parse::<ModuleInstances>()?);
+                let _ = input.parse::<Token![,]>(); // optional comma
+                continue;
+            }
+
+            if implements.is_none() && input.peek(kw::implements) {
+                implements = Some(input.parse::<ModuleInterfaces>()?);
+                let _ = input.parse::<Token![,]>(); // optional comma
+                continue;
+            }
             return Err(input.error("Unexpected token"));
         }
 
@@ -74,7 +126,14 @@ impl Parse for ModuleConfiguration {
             name: name.unwrap_or_default(),
             version: version.unwrap_or_default(),
             events: events.unwrap_or_default(),
-            errors: errors.unwrap_or_default()
+            errors: errors.unwrap_or_default(),
+            multicall,
+            factory,
+            factory_of: factory_of.unwrap_or_default(),
+            instances: instances.unwrap_or_default(),
+            instance_of: None,
+            implements: implements.unwrap_or_default(),
+            interface_impl: false
         })
     }
 }
@@ -160,6 +219,91 @@ impl Parse for ModuleErrors {
     }
 }
 
+#[derive(Default, Clone, Debug)]
+pub struct ModuleInstances(Punctuated<ModuleInstance, Token![,]>);
+
+impl ModuleInstances {
+    pub fn iter(&self) -> impl Iterator<Item = &ModuleInstance> {
+        self.0.iter()
+    }
+}
+
+impl Parse for ModuleInstances {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        // a sample input: instances = [U32Registry = Registry<u32>, StringRegistry = Registry<String>]
+        input.parse::<kw::instances>()?;
+        input.parse::<Token![=]>()?;
+
+        let content: ParseBuffer;
+        let _brace_token = syn::bracketed!(content in input);
+        Punctuated::parse_terminated(&content).map(Self)
+    }
+}
+
+/// A concrete type of a generic module exported as a contract under the given name.
+#[derive(Clone, Debug)]
+pub struct ModuleInstance {
+    pub ident: syn::Ident,
+    pub ty: syn::Type
+}
+
+impl Parse for ModuleInstance {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let ident = input.parse::<syn::Ident>()?;
+        input.parse::<Token![=]>()?;
+        let ty = input.parse::<syn::Type>()?;
+        Ok(Self { ident, ty })
+    }
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct ModuleInterfaces(Punctuated<syn::Path, Token![,]>);
+
+impl ModuleInterfaces {
+    pub fn iter(&self) -> impl Iterator<Item = &syn::Path> {
+        self.0.iter()
+    }
+}
+
+impl Parse for ModuleInterfaces {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        // a sample input: implements = [Cep18Interface, OwnableInterface]
+        input.parse::<kw::implements>()?;
+        input.parse::<Token![=]>()?;
+
+        let content: ParseBuffer;
+        let _brace_token = syn::bracketed!(content in input);
+        Punctuated::parse_terminated(&content).map(Self)
+    }
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct ModuleFactoryOf(Punctuated<syn::Path, Token![,]>);
+
+impl ModuleFactoryOf {
+    pub fn iter(&self) -> impl Iterator<Item = &syn::Path> {
+        self.0.iter()
+    }
+}
+
+impl From<syn::Ident> for ModuleFactoryOf {
+    fn from(module_ident: syn::Ident) -> Self {
+        Self(Punctuated::from_iter([syn::Path::from(module_ident)]))
+    }
+}
+
+impl Parse for ModuleFactoryOf {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        // a sample input: factory_of = [Counter, super::Token]
+        input.parse::<kw::factory_of>()?;
+        input.parse::<Token![=]>()?;
+
+        let content: ParseBuffer;
+        let _brace_token = syn::bracketed!(content in input);
+        Punctuated::parse_terminated(&content).map(Self)
+    }
+}
+
 fn parse_list<T: Parse>(
     input: syn::parse::ParseStream
 ) -> syn::Result<Punctuated<syn::Type, Token![,]>> {
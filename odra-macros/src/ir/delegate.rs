@@ -1,27 +1,111 @@
 use crate::utils;
-use quote::ToTokens;
+use quote::{ToTokens, TokenStreamExt};
+use syn::{parse::Parse, spanned::Spanned};
 
 mod kw {
     syn::custom_keyword!(to);
+    syn::custom_keyword!(except);
 }
 
+/// The content of the `delegate!` macro.
+///
+/// A delegation lists the signatures of the delegated functions:
+/// `to self.ownable { fn get_owner(&self) -> Address; }`,
+/// or delegates all the entry points of a submodule but the excluded ones:
+/// `to self.token: Cep18 except [mint, burn];`.
 #[derive(Debug, Clone)]
 pub struct Delegate {
-    pub functions: Vec<syn::ImplItemFn>
+    clauses: Vec<DelegateClause>
 }
 
-impl syn::parse::Parse for Delegate {
-    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+#[derive(Debug, Clone)]
+enum DelegateClause {
+    Functions(DelegatedFunctions),
+    Module(DelegatedModule)
+}
+
+#[derive(Debug, Clone)]
+struct DelegatedFunctions {
+    to: syn::ExprField,
+    except: Vec<syn::Ident>,
+    functions: Vec<syn::TraitItemFn>
+}
+
+/// A submodule delegating all its entry points, which are not known until resolved.
+#[derive(Debug, Clone)]
+pub struct DelegatedModule {
+    to: syn::ExprField,
+    pub ty: syn::Path,
+    except: Vec<syn::Ident>
+}
+
+impl Delegate {
+    pub fn functions(&self) -> syn::Result<Vec<syn::ImplItemFn>> {
         let mut functions = Vec::new();
-        while !input.is_empty() {
-            input.parse::<kw::to>()?;
-            let delegate_to = input.parse::<syn::ExprField>()?;
-            let content;
-            let _brace_token = syn::braced!(content in input);
-            while !content.is_empty() {
-                let fn_item = content.parse::<syn::TraitItemFn>()?;
+        for clause in &self.clauses {
+            if let DelegateClause::Functions(clause) = clause {
+                functions.extend(clause.functions()?);
+            }
+        }
+        Ok(functions)
+    }
+
+    /// Returns the first delegated module which entry points are not resolved yet.
+    pub fn module(&self) -> Option<&DelegatedModule> {
+        self.clauses.iter().find_map(|clause| match clause {
+            DelegateClause::Module(module) => Some(module),
+            DelegateClause::Functions(_) => None
+        })
+    }
+
+    /// Replaces the first delegated module with the signatures of its entry points.
+    pub fn resolve_module(&mut self, functions: Vec<syn::TraitItemFn>) {
+        let clause = self
+            .clauses
+            .iter_mut()
+            .find(|clause| matches!(clause, DelegateClause::Module(_)));
+        if let Some(clause) = clause {
+            if let DelegateClause::Module(module) = clause.clone() {
+                *clause = DelegateClause::Functions(DelegatedFunctions {
+                    to: module.to,
+                    except: module.except,
+                    functions
+                });
+            }
+        }
+    }
+}
+
+impl DelegatedFunctions {
+    fn functions(&self) -> syn::Result<Vec<syn::ImplItemFn>> {
+        if let Some(name) = self
+            .except
+            .iter()
+            .find(|name| !self.functions.iter().any(|f| &f.sig.ident == *name))
+        {
+            return Err(syn::Error::new_spanned(
+                name,
+                format!(
+                    "Cannot exclude `{}`, it is not a delegated entrypoint",
+                    name
+                )
+            ));
+        }
+
+        let delegate_to = &self.to;
+        Ok(self
+            .functions
+            .iter()
+            .filter(|f| !self.except.contains(&f.sig.ident))
+            .map(|fn_item| {
                 let fn_ident = fn_item.sig.ident.clone();
-                let args = utils::syn::function_typed_args(&fn_item.sig);
+                let mut sig = fn_item.sig.clone();
+                // The signatures of a delegated module come from another macro,
+                // the receiver must refer to the same `self` as the delegation.
+                if let Some(syn::FnArg::Receiver(receiver)) = sig.inputs.first_mut() {
+                    receiver.self_token = syn::Token![self](delegate_to.base.span());
+                }
+                let args = utils::syn::function_typed_args(&sig);
                 let args = args
                     .iter()
                     .filter_map(|ty| match &*ty.pat {
@@ -44,15 +128,107 @@ impl syn::parse::Parse for Delegate {
                     attrs.push(attr);
                 }
 
-                functions.push(syn::ImplItemFn {
+                syn::ImplItemFn {
                     attrs,
                     vis: utils::syn::visibility_pub(),
                     defaultness: None,
-                    sig: fn_item.sig,
+                    sig,
                     block: syn::parse_quote!({ #delegate_to.#fn_ident(#args) })
-                });
+                }
+            })
+            .collect())
+    }
+}
+
+impl Parse for Delegate {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut clauses = Vec::new();
+        while !input.is_empty() {
+            input.parse::<kw::to>()?;
+            let to = input.parse::<syn::ExprField>()?;
+            if input.parse::<Option<syn::Token![:]>>()?.is_some() {
+                let ty = input.parse::<syn::Path>()?;
+                let except = parse_except(input)?;
+                input.parse::<syn::Token![;]>()?;
+                clauses.push(DelegateClause::Module(DelegatedModule { to, ty, except }));
+                continue;
+            }
+
+            let except = parse_except(input)?;
+            let content;
+            let _brace_token = syn::braced!(content in input);
+            let mut functions = Vec::new();
+            while !content.is_empty() {
+                functions.push(content.parse::<syn::TraitItemFn>()?);
+            }
+            clauses.push(DelegateClause::Functions(DelegatedFunctions {
+                to,
+                except,
+                functions
+            }));
+        }
+        Ok(Self { clauses })
+    }
+}
+
+/// The input of `odra::delegated_module!`: the module attributes, the module implementation
+/// and the signatures of the entry points of the delegated module.
+pub struct DelegatedModuleInput {
+    pub attr: proc_macro2::TokenStream,
+    pub item: proc_macro2::TokenStream,
+    pub functions: Vec<syn::TraitItemFn>
+}
+
+impl Parse for DelegatedModuleInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let attr;
+        let _brace_token = syn::braced!(attr in input);
+        let item = input.parse::<syn::ItemImpl>()?;
+        let content;
+        let _brace_token = syn::braced!(content in input);
+        let mut functions = Vec::new();
+        while !content.is_empty() {
+            functions.push(content.parse::<syn::TraitItemFn>()?);
+        }
+        Ok(Self {
+            attr: attr.parse()?,
+            item: item.into_token_stream(),
+            functions
+        })
+    }
+}
+
+fn parse_except(input: syn::parse::ParseStream) -> syn::Result<Vec<syn::Ident>> {
+    if input.parse::<Option<kw::except>>()?.is_none() {
+        return Ok(vec![]);
+    }
+    let content;
+    let _bracket_token = syn::bracketed!(content in input);
+    let names = content.parse_terminated(syn::Ident::parse, syn::Token![,])?;
+    Ok(names.into_iter().collect())
+}
+
+impl ToTokens for Delegate {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        tokens.append_all(&self.clauses);
+    }
+}
+
+impl ToTokens for DelegateClause {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        match self {
+            DelegateClause::Functions(DelegatedFunctions {
+                to,
+                except,
+                functions
+            }) => {
+                let except = (!except.is_empty()).then(|| quote::quote!(except [#(#except),*]));
+                tokens.extend(quote::quote!(to #to #except { #(#functions)* }));
+            }
+            DelegateClause::Module(DelegatedModule { to, ty, except }) => {
+                let except = (!except.is_empty()).then(|| quote::quote!(except [#(#except),*]));
+                tokens.extend(quote::quote!(to #to: #ty #except;));
             }
         }
-        Ok(Self { functions })
     }
 }
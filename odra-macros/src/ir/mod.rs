@@ -2,11 +2,12 @@ use std::collections::HashSet;
 
 use crate::ir::delegate::Delegate;
 use crate::utils;
-use config::ConfigItem;
+use config::{ConfigItem, ModuleConfiguration};
 use proc_macro2::Ident;
 use quote::{format_ident, ToTokens};
 use syn::{parse_quote, spanned::Spanned, ImplItem};
 
+pub use self::attr::EntrypointGuard;
 use self::attr::OdraAttribute;
 
 mod attr;
@@ -14,6 +15,8 @@ mod config;
 pub mod delegate;
 
 const CONSTRUCTOR_NAME: &str = "init";
+const MULTICALL_NAME: &str = "multicall";
+const FACTORY_FN_NAME: &str = "new_contract";
 
 macro_rules! try_parse {
     ($from:path => $to:ident) => {
@@ -57,6 +60,41 @@ impl ModuleStructIR {
         self.module_ident().to_string()
     }
 
+    pub fn is_generic(&self) -> bool {
+        !self.code.generics.params.is_empty()
+    }
+
+    /// Returns the generics of the module in the form used in an `impl` block header.
+    pub fn impl_generics(&self) -> proc_macro2::TokenStream {
+        let (impl_generics, _, _) = self.code.generics.split_for_impl();
+        impl_generics.to_token_stream()
+    }
+
+    /// Returns the module type with the generic parameters, eg. `Registry<T>`.
+    pub fn module_ty(&self) -> syn::Type {
+        let ident = self.module_ident();
+        let (_, ty_generics, _) = self.code.generics.split_for_impl();
+        parse_quote!(#ident #ty_generics)
+    }
+
+    /// Returns the where clause of an implementation of the given trait.
+    ///
+    /// Every field which type depends on a generic parameter is required to implement the trait.
+    pub fn where_clause(&self, bound: &syn::Type) -> syn::Result<Option<syn::WhereClause>> {
+        if !self.is_generic() {
+            return Ok(self.code.generics.where_clause.clone());
+        }
+        let params = utils::syn::type_params(&self.code.generics);
+        let mut generics = self.code.generics.clone();
+        let where_clause = generics.make_where_clause();
+        for (_, ty) in utils::syn::struct_typed_fields(&self.code)? {
+            if utils::syn::uses_type_params(&ty, &params) {
+                where_clause.predicates.push(parse_quote!(#ty: #bound));
+            }
+        }
+        Ok(Some(where_clause.clone()))
+    }
+
     pub fn module_mod_ident(&self) -> syn::Ident {
         format_ident!(
             "__{}_module",
@@ -194,6 +232,8 @@ impl TryFrom<(&proc_macro2::TokenStream, &proc_macro2::TokenStream)> for ModuleI
 impl ModuleImplIR {
     pub fn self_code(&self) -> syn::Result<proc_macro2::TokenStream> {
         match self {
+            // the code of an instance is defined by the generic module
+            ModuleImplIR::Impl(ir) if ir.instance_of().is_some() => Ok(Default::default()),
             ModuleImplIR::Impl(ir) => ir.self_code().map(ToTokens::into_token_stream),
             ModuleImplIR::Trait(ir) => ir.self_code().map(ToTokens::into_token_stream)
         }
@@ -213,17 +253,65 @@ impl ModuleImplIR {
         }
     }
 
-    pub fn impl_trait_ident(&self) -> Option<Ident> {
+    pub fn impl_trait_path(&self) -> Option<syn::Path> {
         match self {
-            ModuleImplIR::Impl(ir) => ir
-                .code
-                .trait_
-                .clone()
-                .map(|(_, path, _)| path.get_ident().unwrap().clone()),
+            ModuleImplIR::Impl(ir) => ir.code.trait_.clone().map(|(_, path, _)| path),
             ModuleImplIR::Trait(_) => None
         }
     }
 
+    /// Returns `true` if the block implements an interface of a module annotated with `#[odra::interface]`.
+    pub fn is_interface_impl(&self) -> bool {
+        match self {
+            ModuleImplIR::Impl(ir) => ir.is_interface_impl(),
+            ModuleImplIR::Trait(_) => false
+        }
+    }
+
+    /// Validates an interface definition or converts an implementation of a trait
+    /// into an implementation of an interface.
+    pub fn into_interface(self) -> syn::Result<Self> {
+        match self {
+            ModuleImplIR::Impl(ir) => ir.into_interface_impl().map(ModuleImplIR::Impl),
+            ModuleImplIR::Trait(ir) => ir.validate_interface().map(|_| ModuleImplIR::Trait(ir))
+        }
+    }
+
+    /// Returns the interfaces the module declares to implement.
+    pub fn interfaces(&self) -> Vec<syn::Path> {
+        match self {
+            ModuleImplIR::Impl(ir) => ir.interfaces(),
+            ModuleImplIR::Trait(_) => vec![]
+        }
+    }
+
+    /// Returns the ident of the given parts module generated for an implementation of the interface.
+    pub fn interface_parts_mod_ident(
+        &self,
+        interface: &syn::Path,
+        parts: &str
+    ) -> syn::Result<Ident> {
+        let module_ident = self.snake_cased_module_ident()?;
+        let interface_ident = utils::syn::last_segment_ident(&parse_quote!(#interface))?;
+        Ok(format_ident!(
+            "__{}_{}_{}",
+            module_ident,
+            utils::string::camel_to_snake(interface_ident),
+            parts
+        ))
+    }
+
+    fn parts_mod_ident(&self, parts: &str) -> syn::Result<Ident> {
+        if let Some(interface) = self.impl_trait_path().filter(|_| self.is_interface_impl()) {
+            return self.interface_parts_mod_ident(&interface, parts);
+        }
+        let module_ident = self.snake_cased_module_ident()?;
+        Ok(Ident::new(
+            &format!("__{}_{}", module_ident, parts),
+            module_ident.span()
+        ))
+    }
+
     pub fn module_str(&self) -> syn::Result<String> {
         self.module_ident().map(|i| i.to_string())
     }
@@ -268,27 +356,15 @@ impl ModuleImplIR {
     }
 
     pub fn test_parts_mod_ident(&self) -> syn::Result<syn::Ident> {
-        let module_ident = self.snake_cased_module_ident()?;
-        Ok(Ident::new(
-            &format!("__{}_test_parts", module_ident),
-            module_ident.span()
-        ))
+        self.parts_mod_ident("test_parts")
     }
 
     pub fn wasm_parts_mod_ident(&self) -> syn::Result<syn::Ident> {
-        let module_ident = self.snake_cased_module_ident()?;
-        Ok(Ident::new(
-            &format!("__{}_wasm_parts", module_ident),
-            module_ident.span()
-        ))
+        self.parts_mod_ident("wasm_parts")
     }
 
     pub fn exec_parts_mod_ident(&self) -> syn::Result<syn::Ident> {
-        let module_ident = self.snake_cased_module_ident()?;
-        Ok(Ident::new(
-            &format!("__{}_exec_parts", module_ident),
-            module_ident.span()
-        ))
+        self.parts_mod_ident("exec_parts")
     }
 
     pub fn host_functions(&self) -> syn::Result<Vec<FnIR>> {
@@ -308,6 +384,200 @@ impl ModuleImplIR {
             ModuleImplIR::Trait(ir) => ir.functions()
         }
     }
+
+    pub fn is_multicall(&self) -> bool {
+        match self {
+            ModuleImplIR::Impl(ir) => ir.is_multicall(),
+            ModuleImplIR::Trait(_) => false
+        }
+    }
+
+    /// Returns the paths of the modules installed by the module, if the module is a factory.
+    pub fn factory_of(&self) -> Vec<syn::Path> {
+        match self {
+            ModuleImplIR::Impl(ir) => ir.factory_of(),
+            ModuleImplIR::Trait(_) => vec![]
+        }
+    }
+
+    /// Returns the paths of the given items generated for the modules installed by the module,
+    /// e.g. `__counter_factory_template` for `Counter`.
+    ///
+    /// The paths are relative to a module nested in the module of the factory.
+    pub fn factory_of_items(&self, item: &str) -> Vec<syn::Path> {
+        self.factory_of()
+            .into_iter()
+            .map(|mut path| {
+                if let Some(last) = path.segments.last_mut() {
+                    let snake = utils::string::camel_to_snake(&last.ident);
+                    last.ident = format_ident!("__{}_factory_{}", snake, item);
+                    last.arguments = syn::PathArguments::None;
+                }
+                match path
+                    .segments
+                    .first()
+                    .map(|segment| segment.ident.to_string())
+                {
+                    Some(first) if first == "self" => {
+                        path.segments[0].ident = format_ident!("super")
+                    }
+                    Some(first) if first == "super" => {
+                        path.segments.insert(0, format_ident!("super").into())
+                    }
+                    _ => {}
+                }
+                path
+            })
+            .collect()
+    }
+
+    pub fn factory_ident(&self) -> syn::Result<Ident> {
+        let module_ident = self.module_ident()?;
+        Ok(Ident::new(
+            &format!("{}Factory", module_ident),
+            module_ident.span()
+        ))
+    }
+
+    /// Returns the ident of the module with the wasm entry points of the module installed by a factory.
+    pub fn factory_template_mod_ident(&self) -> syn::Result<Ident> {
+        Ok(format_ident!(
+            "__{}_factory_template",
+            self.snake_cased_module_ident()?
+        ))
+    }
+
+    /// Returns the name under which the macro exporting the wasm entry points of the module
+    /// installed by a factory is imported along with the module.
+    pub fn factory_exports_macro_alias(&self) -> syn::Result<Ident> {
+        Ok(format_ident!(
+            "__{}_factory_exports",
+            self.snake_cased_module_ident()?
+        ))
+    }
+
+    /// Returns the ident of the macro exporting the wasm entry points of the module installed by a factory.
+    ///
+    /// The ident is unique in the crate, as the macro is exported from the crate root.
+    pub fn factory_exports_macro_ident(&self) -> syn::Result<Ident> {
+        Ok(format_ident!(
+            "__odra_{}_factory_exports_{:x}",
+            self.snake_cased_module_ident()?,
+            match self {
+                ModuleImplIR::Impl(ir) => ir.code_hash(),
+                ModuleImplIR::Trait(_) => 0
+            }
+        ))
+    }
+
+    /// Returns the factory module installing new instances of the module.
+    pub fn factory(&self) -> syn::Result<Option<ModuleImplIR>> {
+        match self {
+            ModuleImplIR::Impl(ir) if ir.is_factory() => ir.factory().map(Some),
+            _ => Ok(None)
+        }
+    }
+
+    pub fn is_generic(&self) -> bool {
+        match self {
+            ModuleImplIR::Impl(ir) => !ir.code.generics.params.is_empty(),
+            ModuleImplIR::Trait(_) => false
+        }
+    }
+
+    /// Returns the concrete type of a generic module, if the module is a generated instance.
+    pub fn instance_of(&self) -> Option<syn::Type> {
+        match self {
+            ModuleImplIR::Impl(ir) => ir.instance_of(),
+            ModuleImplIR::Trait(_) => None
+        }
+    }
+
+    /// Returns the concrete instances of a generic module exported as contracts.
+    pub fn instances(&self) -> syn::Result<Vec<ModuleImplIR>> {
+        match self {
+            ModuleImplIR::Impl(ir) => ir.instances(),
+            ModuleImplIR::Trait(_) => Ok(vec![])
+        }
+    }
+
+    pub fn contract_schema_mod_ident(&self) -> syn::Result<Ident> {
+        let ident = self.module_ident()?;
+        Ok(Ident::new(
+            utils::string::camel_to_snake(format!("__{}_contract_schema", ident)).as_str(),
+            ident.span()
+        ))
+    }
+
+    /// Returns the type of the first submodule delegating all its entry points.
+    pub fn delegated_module(&self) -> syn::Result<Option<syn::Path>> {
+        match self {
+            ModuleImplIR::Impl(ir) => ir.delegated_module(),
+            ModuleImplIR::Trait(_) => Ok(None)
+        }
+    }
+
+    /// Replaces the first submodule delegating all its entry points with the signatures of the entry points.
+    pub fn resolve_delegated_module(
+        &self,
+        functions: Vec<syn::TraitItemFn>
+    ) -> syn::Result<syn::ItemImpl> {
+        match self {
+            ModuleImplIR::Impl(ir) => ir.resolve_delegated_module(functions),
+            ModuleImplIR::Trait(ir) => Err(syn::Error::new_spanned(
+                &ir.code.ident,
+                "Delegation is not supported in a trait"
+            ))
+        }
+    }
+
+    /// Returns the ident of the macro listing the entry points the module can delegate,
+    /// `None` if the module cannot be delegated to.
+    ///
+    /// The ident is unique in the crate, as the macro is exported from the crate root.
+    pub fn delegation_macro_ident(&self) -> syn::Result<Option<Ident>> {
+        let ir = match self {
+            ModuleImplIR::Impl(ir)
+                if !self.is_generic()
+                    && ir.instance_of().is_none()
+                    && ir.factory_of().is_empty()
+                    && !ir.is_interface_impl() =>
+            {
+                ir
+            }
+            _ => return Ok(None)
+        };
+        Ok(Some(format_ident!(
+            "__odra_{}_delegation_{:x}",
+            self.snake_cased_module_ident()?,
+            ir.code_hash()
+        )))
+    }
+
+    /// Returns the signatures of the entry points a module delegating to the module exposes.
+    ///
+    /// The constructor and the special entry points are not delegated.
+    pub fn delegated_entrypoints(&self) -> syn::Result<Vec<syn::TraitItemFn>> {
+        Ok(self
+            .functions()?
+            .iter()
+            .filter(|f| {
+                !f.is_constructor()
+                    && !f.is_receive()
+                    && !f.is_fallback()
+                    && f.name_str() != MULTICALL_NAME
+            })
+            .map(FnIR::delegated_signature)
+            .collect())
+    }
+
+    pub fn batch_ident(&self) -> syn::Result<Ident> {
+        let module_ident = self.module_ident()?;
+        Ok(Ident::new(
+            &format!("{}Batch", module_ident),
+            module_ident.span()
+        ))
+    }
 }
 
 try_parse!(syn::ItemImpl => ModuleIR);
@@ -315,10 +585,11 @@ try_parse!(syn::ItemImpl => ModuleIR);
 impl ModuleIR {
     fn self_code(&self) -> syn::Result<syn::ItemImpl> {
         let mut code = self.code.clone();
-        // include delegated functions
+        // include delegated functions and the multicall entrypoint
         code.items.extend(
             self.delegated_functions()?
                 .into_iter()
+                .chain(self.multicall_function()?)
                 .map(syn::ImplItem::Fn)
         );
         // remove odra attributes
@@ -334,6 +605,14 @@ impl ModuleIR {
     }
 
     fn functions(&self) -> syn::Result<Vec<FnIR>> {
+        let mut functions = self.own_functions()?;
+        if let Some(multicall) = self.multicall_function()? {
+            functions.push(FnIR::try_from(multicall)?);
+        }
+        Ok(functions)
+    }
+
+    fn own_functions(&self) -> syn::Result<Vec<FnIR>> {
         self.code
             .items
             .clone()
@@ -346,6 +625,11 @@ impl ModuleIR {
             .map(FnIR::try_from)
             .filter(|r| self.is_trait_impl() || r.as_ref().map(FnIR::is_pub).unwrap_or(true))
             .collect::<Result<Vec<_>, _>>()
+            .and_then(|functions| {
+                ensure_single(&functions, FnIR::is_receive, "receive")?;
+                ensure_single(&functions, FnIR::is_fallback, "fallback")?;
+                Ok(functions)
+            })
     }
 
     fn delegated_functions(&self) -> syn::Result<Vec<syn::ImplItemFn>> {
@@ -360,15 +644,311 @@ impl ModuleIR {
             .map(|m| syn::parse2::<Delegate>(m.mac.tokens.clone()))
             .collect::<Result<Vec<_>, _>>()?;
 
-        Ok(delegate_items
-            .into_iter()
-            .flat_map(|delegate| delegate.functions)
-            .collect())
+        delegate_items
+            .iter()
+            .map(Delegate::functions)
+            .collect::<Result<Vec<_>, _>>()
+            .map(|functions| functions.into_iter().flatten().collect())
+    }
+
+    fn delegated_module(&self) -> syn::Result<Option<syn::Path>> {
+        for item in &self.code.items {
+            if let ImplItem::Macro(m) = item {
+                let delegate = syn::parse2::<Delegate>(m.mac.tokens.clone())?;
+                if let Some(module) = delegate.module() {
+                    return Ok(Some(module.ty.clone()));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn resolve_delegated_module(
+        &self,
+        functions: Vec<syn::TraitItemFn>
+    ) -> syn::Result<syn::ItemImpl> {
+        let mut code = self.code.clone();
+        for item in code.items.iter_mut() {
+            if let ImplItem::Macro(m) = item {
+                let mut delegate = syn::parse2::<Delegate>(m.mac.tokens.clone())?;
+                if delegate.module().is_some() {
+                    delegate.resolve_module(functions);
+                    m.mac.tokens = delegate.into_token_stream();
+                    return Ok(code);
+                }
+            }
+        }
+        Err(syn::Error::new_spanned(
+            &self.code.self_ty,
+            "No delegated module to resolve"
+        ))
     }
 
     fn is_trait_impl(&self) -> bool {
         self.code.trait_.is_some()
     }
+
+    fn is_multicall(&self) -> bool {
+        matches!(&self.config, ConfigItem::Module(cfg) if cfg.multicall)
+    }
+
+    fn is_interface_impl(&self) -> bool {
+        matches!(&self.config, ConfigItem::Module(cfg) if cfg.interface_impl)
+    }
+
+    fn interfaces(&self) -> Vec<syn::Path> {
+        match &self.config {
+            ConfigItem::Module(cfg) => cfg.implements.iter().cloned().collect(),
+            ConfigItem::Empty => vec![]
+        }
+    }
+
+    fn into_interface_impl(self) -> syn::Result<Self> {
+        if !self.is_trait_impl() {
+            return Err(syn::Error::new_spanned(
+                &self.code.self_ty,
+                "#[odra::interface] can be applied to a trait or a trait implementation only"
+            ));
+        }
+        if let Some(f) = self.functions()?.iter().find(|f| f.is_constructor()) {
+            return Err(syn::Error::new_spanned(
+                f.name(),
+                "An interface cannot define a constructor"
+            ));
+        }
+        if let Some(f) = self
+            .functions()?
+            .iter()
+            .find(|f| f.is_receive() || f.is_fallback())
+        {
+            return Err(syn::Error::new_spanned(
+                f.name(),
+                "An interface cannot define a receive or fallback entrypoint"
+            ));
+        }
+        let config = ConfigItem::Module(ModuleConfiguration {
+            interface_impl: true,
+            ..Default::default()
+        });
+        Ok(Self {
+            code: self.code,
+            config
+        })
+    }
+
+    fn code_hash(&self) -> u64 {
+        let mut hasher = std::hash::DefaultHasher::new();
+        std::hash::Hash::hash(&self.code.to_token_stream().to_string(), &mut hasher);
+        std::hash::Hasher::finish(&hasher)
+    }
+
+    fn is_factory(&self) -> bool {
+        matches!(&self.config, ConfigItem::Module(cfg) if cfg.factory)
+    }
+
+    fn factory_of(&self) -> Vec<syn::Path> {
+        match &self.config {
+            ConfigItem::Module(cfg) => cfg.factory_of.iter().cloned().collect(),
+            ConfigItem::Empty => vec![]
+        }
+    }
+
+    fn instance_of(&self) -> Option<syn::Type> {
+        match &self.config {
+            ConfigItem::Module(cfg) => cfg.instance_of.clone(),
+            ConfigItem::Empty => None
+        }
+    }
+
+    /// Generates a non-generic module for every instance of a generic module.
+    ///
+    /// An instance is an alias of the module with concrete types, the type parameters
+    /// in the function signatures are replaced with the concrete types.
+    fn instances(&self) -> syn::Result<Vec<ModuleImplIR>> {
+        let instances = match &self.config {
+            ConfigItem::Module(cfg) => cfg.instances.iter().cloned().collect::<Vec<_>>(),
+            ConfigItem::Empty => vec![]
+        };
+        let generics = &self.code.generics;
+        if generics.params.is_empty() {
+            return match instances.first() {
+                Some(instance) => Err(syn::Error::new_spanned(
+                    &instance.ident,
+                    "Instances can be defined for a generic module only"
+                )),
+                None => Ok(vec![])
+            };
+        }
+        if self.is_multicall() || self.is_factory() || !self.interfaces().is_empty() {
+            return Err(syn::Error::new_spanned(
+                generics,
+                "Multicall, factory and interfaces are not supported in a generic module"
+            ));
+        }
+        let params = utils::syn::type_params(generics);
+        if params.len() != generics.params.len() {
+            return Err(syn::Error::new_spanned(
+                generics,
+                "Only type parameters are supported in a generic module"
+            ));
+        }
+
+        let module_ident = utils::syn::ident_from_impl(&self.code)?;
+        let mut generic_code = self.code.clone();
+        generic_code.generics = Default::default();
+        generic_code.items.extend(
+            self.delegated_functions()?
+                .into_iter()
+                .map(syn::ImplItem::Fn)
+        );
+        generic_code
+            .items
+            .retain(|item| !matches!(item, syn::ImplItem::Macro(_)));
+
+        instances
+            .into_iter()
+            .map(|instance| {
+                let args = utils::syn::type_args(&instance.ty, &module_ident)?;
+                if args.len() != params.len() {
+                    return Err(syn::Error::new_spanned(
+                        &instance.ty,
+                        format!("Expected {} type arguments", params.len())
+                    ));
+                }
+                let substitutions = params.iter().cloned().zip(args).collect::<Vec<_>>();
+                let alias = &instance.ident;
+                let mut code = generic_code.clone();
+                code.self_ty = parse_quote!(#alias);
+                syn::visit_mut::VisitMut::visit_item_impl_mut(
+                    &mut utils::syn::SubstituteTypeParams::new(&substitutions),
+                    &mut code
+                );
+                let config = ConfigItem::Module(ModuleConfiguration {
+                    instance_of: Some(instance.ty),
+                    ..Default::default()
+                });
+                Ok(ModuleImplIR::Impl(ModuleIR { code, config }))
+            })
+            .collect()
+    }
+
+    /// Generates the factory module with the `new_contract` entrypoint installing
+    /// a new instance of the module.
+    fn factory(&self) -> syn::Result<ModuleImplIR> {
+        if self.is_trait_impl() {
+            return Err(syn::Error::new_spanned(
+                &self.code.self_ty,
+                "Factory is not supported in a trait implementation"
+            ));
+        }
+        if !self.interfaces().is_empty() {
+            return Err(syn::Error::new_spanned(
+                &self.code.self_ty,
+                "Factory is not supported in a module implementing interfaces"
+            ));
+        }
+        let functions = self.functions()?;
+        if let Some(f) = functions.iter().find(|f| f.name_str() == FACTORY_FN_NAME) {
+            return Err(syn::Error::new_spanned(
+                f.name(),
+                "Entrypoint name `new_contract` is reserved in a factory module"
+            ));
+        }
+
+        let module_ident = utils::syn::ident_from_impl(&self.code)?;
+        let factory_ident = format_ident!("{}Factory", module_ident);
+        let constructor = functions.iter().find(|f| f.is_constructor());
+        let args = constructor.map(FnIR::typed_args).unwrap_or_default();
+        let init_args: syn::Expr = match constructor {
+            Some(f) => {
+                let names = f.arg_names();
+                let names_str = names.iter().map(|name| name.to_string());
+                parse_quote!(Some({
+                    let mut named_args = odra::casper_types::RuntimeArgs::new();
+                    #(odra::args::EntrypointArgument::insert_runtime_arg(#names.clone(), #names_str, &mut named_args);)*
+                    named_args
+                }))
+            }
+            None => parse_quote!(None)
+        };
+        let doc = format!(
+            " Installs a new [{}] contract and returns its address.",
+            module_ident
+        );
+
+        let code = parse_quote!(
+            impl #factory_ident {
+                #[doc = #doc]
+                pub fn new_contract(&mut self #(, #args)*) -> odra::prelude::Address {
+                    let template = <#module_ident as odra::factory::HasTemplate>::template();
+                    odra::module::Module::env(self).new_contract(&template, #init_args)
+                }
+            }
+        );
+        let config = ConfigItem::Module(ModuleConfiguration {
+            factory_of: module_ident.into(),
+            ..Default::default()
+        });
+        Ok(ModuleImplIR::Impl(ModuleIR { code, config }))
+    }
+
+    /// Generates the `multicall` entrypoint dispatching the calls of a batch
+    /// to the exec functions of the module entrypoints.
+    fn multicall_function(&self) -> syn::Result<Option<syn::ImplItemFn>> {
+        if !self.is_multicall() {
+            return Ok(None);
+        }
+        if self.is_trait_impl() {
+            return Err(syn::Error::new_spanned(
+                &self.code.self_ty,
+                "Multicall is not supported in a trait implementation"
+            ));
+        }
+        let functions = self.own_functions()?;
+        if let Some(f) = functions.iter().find(|f| f.name_str() == MULTICALL_NAME) {
+            return Err(syn::Error::new_spanned(
+                f.name(),
+                "Entrypoint name `multicall` is reserved in a multicall module"
+            ));
+        }
+
+        let module_ident = utils::syn::ident_from_impl(&self.code)?;
+        let exec_parts_ident = format_ident!(
+            "__{}_exec_parts",
+            utils::string::camel_to_snake(&module_ident)
+        );
+        let arms = functions
+            .iter()
+            .filter(|f| f.is_batchable())
+            .map(|f| {
+                let name = f.name_str();
+                let exec_fn = f.execute_name();
+                quote::quote!(#name => odra::multicall::call_result(&env, #exec_parts_ident::#exec_fn(call_env)))
+            });
+
+        Ok(Some(parse_quote!(
+            /// Executes a batch of calls to the contract entrypoints in a single transaction.
+            ///
+            /// Every call is a pair of an entrypoint name and serialized runtime args.
+            /// Returns the serialized results of the calls. If any call reverts, the whole batch reverts.
+            pub fn multicall(
+                &mut self,
+                calls: odra::prelude::Vec<odra::multicall::Call>
+            ) -> odra::prelude::Vec<odra::casper_types::bytesrepr::Bytes> {
+                let env = odra::module::Module::env(self);
+                calls
+                    .iter()
+                    .map(|(entry_point, args)| {
+                        let call_env = odra::multicall::call_env(&env, args);
+                        match entry_point.as_str() {
+                            #(#arms,)*
+                            _ => env.revert(odra::prelude::ExecutionError::UnknownEntrypoint)
+                        }
+                    })
+                    .collect()
+            }
+        )))
+    }
 }
 
 try_parse!(syn::ItemTrait => ModuleTraitIR);
@@ -388,6 +968,33 @@ impl ModuleTraitIR {
         self.code.ident.clone()
     }
 
+    fn validate_interface(&self) -> syn::Result<()> {
+        for item in &self.code.items {
+            match item {
+                syn::TraitItem::Fn(func) if func.default.is_some() => {
+                    return Err(syn::Error::new_spanned(
+                        &func.sig.ident,
+                        "An interface function cannot have a default implementation"
+                    ))
+                }
+                syn::TraitItem::Fn(func) if func.sig.ident == CONSTRUCTOR_NAME => {
+                    return Err(syn::Error::new_spanned(
+                        &func.sig.ident,
+                        "An interface cannot define a constructor"
+                    ))
+                }
+                syn::TraitItem::Fn(_) => {}
+                item => {
+                    return Err(syn::Error::new_spanned(
+                        item,
+                        "An interface can define functions only"
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn functions(&self) -> syn::Result<Vec<FnIR>> {
         self.code
             .items
@@ -430,12 +1037,52 @@ fn validate_fn_name<T: ToTokens>(name: &str, ctx: T) -> syn::Result<()> {
     Ok(())
 }
 
+fn ensure_single(functions: &[FnIR], predicate: fn(&FnIR) -> bool, kind: &str) -> syn::Result<()> {
+    if let Some(second) = functions.iter().filter(|f| predicate(f)).nth(1) {
+        return Err(syn::Error::new_spanned(
+            second.name(),
+            format!("A module can define only one {} entrypoint", kind)
+        ));
+    }
+    Ok(())
+}
+
+fn validate_attrs<T: ToTokens>(
+    sig: &syn::Signature,
+    attrs: &[syn::Attribute],
+    ctx: T
+) -> syn::Result<()> {
+    // Fail on invalid Odra attributes, otherwise an access check could be silently skipped.
+    let (odra_attrs, _) = attr::partition_attributes(attrs.to_vec())?;
+    let is_constructor = sig.ident == CONSTRUCTOR_NAME;
+    let is_view = odra_attrs.iter().any(OdraAttribute::is_view);
+    let is_receive = odra_attrs.iter().any(OdraAttribute::is_receive);
+    let is_fallback = odra_attrs.iter().any(OdraAttribute::is_fallback);
+    let is_payable = is_receive || odra_attrs.iter().any(OdraAttribute::is_payable);
+    let error = |msg: &str| Err(syn::Error::new_spanned(&ctx, msg));
+
+    if is_view && is_constructor {
+        return error("A constructor cannot be a view");
+    }
+    if is_view && is_payable {
+        return error("A view entrypoint cannot be payable");
+    }
+    if (is_receive || is_fallback) && is_constructor {
+        return error("A constructor cannot be a receive or fallback entrypoint");
+    }
+    if (is_receive || is_fallback) && !utils::syn::function_arg_names(sig).is_empty() {
+        return error("A receive or fallback entrypoint cannot take arguments");
+    }
+    Ok(())
+}
+
 impl TryFrom<syn::TraitItemFn> for FnIR {
     type Error = syn::Error;
 
     fn try_from(code: syn::TraitItemFn) -> Result<Self, Self::Error> {
         let fn_name = utils::syn::function_name(&code.sig);
         validate_fn_name(&fn_name, &code)?;
+        validate_attrs(&code.sig, &code.attrs, &code)?;
         Ok(Self::Def(FnTraitIR::new(code)))
     }
 }
@@ -446,6 +1093,7 @@ impl TryFrom<syn::ImplItemFn> for FnIR {
     fn try_from(code: syn::ImplItemFn) -> Result<Self, Self::Error> {
         let fn_name = utils::syn::function_name(&code.sig);
         validate_fn_name(&fn_name, &code)?;
+        validate_attrs(&code.sig, &code.attrs, &code)?;
         Ok(Self::Impl(FnImplIR::new(code)))
     }
 }
@@ -467,9 +1115,31 @@ impl FnIR {
         self.name().to_string()
     }
 
+    /// Returns true if the entrypoint accepts native tokens, a receive entrypoint is always payable.
     pub fn is_payable(&self) -> bool {
         let (odra_attrs, _) = attr::partition_attributes(self.attrs()).unwrap_or_default();
-        odra_attrs.iter().any(OdraAttribute::is_payable)
+        odra_attrs
+            .iter()
+            .any(|attr| attr.is_payable() || attr.is_receive())
+    }
+
+    /// Returns true if the entrypoint can be called in a batch executed by `multicall`.
+    ///
+    /// The constructor and payable entrypoints cannot be batched.
+    pub fn is_batchable(&self) -> bool {
+        !self.is_constructor() && !self.is_payable() && self.name_str() != MULTICALL_NAME
+    }
+
+    /// Returns true if the entrypoint is called when native tokens are transferred to the contract.
+    pub fn is_receive(&self) -> bool {
+        let (odra_attrs, _) = attr::partition_attributes(self.attrs()).unwrap_or_default();
+        odra_attrs.iter().any(OdraAttribute::is_receive)
+    }
+
+    /// Returns true if the entrypoint is called instead of an entrypoint the contract does not define.
+    pub fn is_fallback(&self) -> bool {
+        let (odra_attrs, _) = attr::partition_attributes(self.attrs()).unwrap_or_default();
+        odra_attrs.iter().any(OdraAttribute::is_fallback)
     }
 
     pub fn is_non_reentrant(&self) -> bool {
@@ -477,6 +1147,23 @@ impl FnIR {
         odra_attrs.iter().any(OdraAttribute::is_non_reentrant)
     }
 
+    /// Returns true if the entrypoint is marked with `#[odra(view)]`,
+    /// so it must not modify the state of the contract.
+    pub fn is_view(&self) -> bool {
+        let (odra_attrs, _) = attr::partition_attributes(self.attrs()).unwrap_or_default();
+        odra_attrs.iter().any(OdraAttribute::is_view)
+    }
+
+    /// Returns true if the entrypoint may modify the state, i.e. takes `&mut self` and is not a view.
+    pub fn is_mutable_entrypoint(&self) -> bool {
+        self.is_mut() && !self.is_view()
+    }
+
+    pub fn guards(&self) -> Vec<EntrypointGuard> {
+        let (odra_attrs, _) = attr::partition_attributes(self.attrs()).unwrap_or_default();
+        odra_attrs.iter().flat_map(OdraAttribute::guards).collect()
+    }
+
     pub fn arg_names(&self) -> Vec<Ident> {
         utils::syn::function_arg_names(self.sig())
     }
@@ -528,6 +1215,16 @@ impl FnIR {
         self.name_str() == CONSTRUCTOR_NAME
     }
 
+    /// Returns the signature of the function with the docs and Odra attributes.
+    pub fn delegated_signature(&self) -> syn::TraitItemFn {
+        let sig = self.sig();
+        let attrs = self
+            .attrs()
+            .into_iter()
+            .filter(|attr| attr.path().is_ident("doc") || attr.path().is_ident("odra"));
+        parse_quote!(#(#attrs)* #sig;)
+    }
+
     pub fn is_pub(&self) -> bool {
         match self {
             FnIR::Impl(ir) => ir.is_pub(),
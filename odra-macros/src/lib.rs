@@ -3,9 +3,11 @@
 #![feature(box_patterns, result_flattening)]
 use crate::utils::IntoCode;
 use ast::*;
+use ir::delegate::DelegatedModuleInput;
 use ir::{ModuleImplIR, ModuleStructIR, TypeIR};
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
+use quote::ToTokens;
 
 mod ast;
 mod ir;
@@ -31,9 +33,17 @@ macro_rules! span_error {
 /// The macro produces all the required code to use the module as a standalone smart contract.
 #[proc_macro_attribute]
 pub fn module(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let attr: TokenStream2 = attr.into();
-    let item: TokenStream2 = item.into();
+    module_code(attr.into(), item.into())
+}
+
+fn module_code(attr: TokenStream2, item: TokenStream2) -> TokenStream {
     if let Ok(ir) = ModuleImplIR::try_from((&attr, &item)) {
+        if matches!(ir.delegated_module(), Ok(Some(_))) {
+            return DelegatedModuleItem::try_from((&attr, &item)).into_code();
+        }
+        if ir.is_generic() {
+            return GenericModuleImplItem::try_from(&ir).into_code();
+        }
         return ModuleImplItem::try_from(&ir).into_code();
     }
     if let Ok(ir) = ModuleStructIR::try_from((&attr, &item)) {
@@ -42,6 +52,22 @@ pub fn module(attr: TokenStream, item: TokenStream) -> TokenStream {
     span_error!(item, "Struct or impl block expected")
 }
 
+/// Expands a module delegating all the entry points of a submodule.
+///
+/// Called by the macro of the submodule with the signatures of its entry points,
+/// not intended to be used directly.
+#[doc(hidden)]
+#[proc_macro]
+pub fn delegated_module(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DelegatedModuleInput);
+    match ModuleImplIR::try_from((&input.attr, &input.item))
+        .and_then(|ir| ir.resolve_delegated_module(input.functions))
+    {
+        Ok(item) => module_code(input.attr, item.into_token_stream()),
+        Err(err) => err.to_compile_error().into()
+    }
+}
+
 /// Implements boilerplate for a type to be used in an Odra module.
 ///
 /// This macro implements serialization and deserialization for the type, as well as
@@ -83,6 +109,26 @@ pub fn external_contract(attr: TokenStream, item: TokenStream) -> TokenStream {
     )
 }
 
+/// Defines an interface of a contract or implements it for a module.
+///
+/// Applied to a trait, the macro generates references to any contract implementing
+/// the interface, which implement the trait themselves.
+///
+/// Applied to an implementation of the trait for a module, the macro generates
+/// the entry points of the interface. The module lists the interface in
+/// `#[odra::module(implements = [Interface])]` to export the entry points
+/// along with its own, the names of the implemented interfaces are included in the contract schema.
+#[proc_macro_attribute]
+pub fn interface(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attr: TokenStream2 = attr.into();
+    let item: TokenStream2 = item.into();
+    match ModuleImplIR::try_from((&attr, &item)).and_then(ModuleImplIR::into_interface) {
+        Ok(ir) if ir.is_interface_impl() => InterfaceImplItem::try_from(&ir).into_code(),
+        Ok(ir) => InterfaceItem::try_from(&ir).into_code(),
+        Err(err) => err.to_compile_error().into()
+    }
+}
+
 /// This macro is used to implement the boilerplate code for the event and contract schema.
 #[proc_macro_attribute]
 pub fn event(_attr: TokenStream, input: TokenStream) -> TokenStream {
@@ -72,6 +72,53 @@ pub mod mock {
         ModuleImplIR::try_from((&attr, &module)).unwrap()
     }
 
+    pub fn module_view() -> ModuleImplIR {
+        let module = quote! {
+            impl Counter {
+                #[odra(view)]
+                pub fn count(&self) -> u32 {
+                    self.count.get_or_default()
+                }
+
+                pub fn log(&self) {
+                    self.env().emit_event(Touched { count: self.count() });
+                }
+            }
+        };
+
+        let attr = quote!();
+        ModuleImplIR::try_from((&attr, &module)).unwrap()
+    }
+
+    pub fn module_guards() -> ModuleImplIR {
+        let module = quote! {
+            impl Token {
+                #[odra(only_owner)]
+                pub fn pause(&mut self) {
+                    self.paused.set(true);
+                }
+
+                #[odra(only_owner = ownable)]
+                pub fn unpause(&mut self) {
+                    self.paused.set(false);
+                }
+
+                #[odra(view)]
+                pub fn is_paused(&self) -> bool {
+                    self.paused.get_or_default()
+                }
+
+                #[odra(only_role(access_control, "MINTER"), guard = Self::not_paused)]
+                pub fn mint(&mut self, amount: U256) {
+                    self.total_supply.add(amount);
+                }
+            }
+        };
+
+        let attr = quote!();
+        ModuleImplIR::try_from((&attr, &module)).unwrap()
+    }
+
     pub fn module_delegation() -> ModuleImplIR {
         let module = quote! {
             impl Erc20 {
@@ -176,6 +223,17 @@ pub mod mock {
         ModuleStructIR::try_from((&attr, &module)).unwrap()
     }
 
+    pub fn generic_module_definition() -> ModuleStructIR {
+        let module = quote!(
+            pub struct Registry<T: ToBytes> {
+                items: Mapping<u32, T>,
+                count: Var<u32>
+            }
+        );
+        let attr = quote!();
+        ModuleStructIR::try_from((&attr, &module)).unwrap()
+    }
+
     pub fn custom_struct() -> TypeIR {
         let ty = quote!(
             struct MyType {
@@ -224,6 +282,147 @@ pub mod mock {
         TypeIR::try_from(&ty).unwrap()
     }
 
+    pub fn module_multicall() -> ModuleImplIR {
+        let module = quote! {
+            impl Counter {
+                pub fn init(&mut self, value: u32) {
+                    self.value.set(value);
+                }
+
+                pub fn add(&mut self, value: u32) {
+                    self.value.add(value);
+                }
+
+                pub fn count(&self) -> u32 {
+                    self.value.get_or_default()
+                }
+
+                #[odra(payable)]
+                pub fn deposit(&mut self) {
+                }
+            }
+        };
+
+        let attr = quote!(multicall);
+        ModuleImplIR::try_from((&attr, &module)).unwrap()
+    }
+
+    pub fn module_receive() -> ModuleImplIR {
+        let module = quote! {
+            impl DonationBox {
+                #[odra(receive)]
+                pub fn donate(&mut self) {
+                }
+
+                #[odra(fallback)]
+                pub fn fallback(&mut self) {
+                }
+            }
+        };
+
+        let attr = quote!();
+        ModuleImplIR::try_from((&attr, &module)).unwrap()
+    }
+
+    pub fn module_factory() -> ModuleImplIR {
+        let module = quote! {
+            impl Counter {
+                pub fn init(&mut self, value: u32) {
+                    self.value.set(value);
+                }
+
+                pub fn count(&self) -> u32 {
+                    self.value.get_or_default()
+                }
+            }
+        };
+
+        let attr = quote!(factory);
+        ModuleImplIR::try_from((&attr, &module)).unwrap()
+    }
+
+    pub fn module_factory_of() -> ModuleImplIR {
+        let module = quote! {
+            impl Deployer {
+                pub fn init(&mut self) {}
+
+                pub fn deploy(&mut self) -> Address {
+                    self.env().new_contract(&self.template(), None)
+                }
+            }
+        };
+
+        let attr = quote!(factory_of = [Counter, super::token::Erc20]);
+        ModuleImplIR::try_from((&attr, &module)).unwrap()
+    }
+
+    pub fn generic_module_impl() -> ModuleImplIR {
+        let module = quote! {
+            impl<T: ToBytes> Registry<T> {
+                pub fn add(&mut self, item: T) -> u32 {
+                    let id = self.count.get_or_default();
+                    self.items.set(&id, item);
+                    self.count.set(id + 1);
+                    id
+                }
+
+                pub fn get(&self, id: u32) -> Option<T> {
+                    self.items.get(&id)
+                }
+            }
+        };
+
+        let attr =
+            quote!(instances = [NumberRegistry = Registry<u32>, NameRegistry = Registry<String>]);
+        ModuleImplIR::try_from((&attr, &module)).unwrap()
+    }
+
+    pub fn interface() -> ModuleImplIR {
+        let interface = quote!(
+            pub trait Countable {
+                /// Returns the value.
+                fn value(&self) -> u32;
+
+                fn increment(&mut self, by: u32);
+            }
+        );
+        let attr = quote!();
+        ModuleImplIR::try_from((&attr, &interface))
+            .and_then(ModuleImplIR::into_interface)
+            .unwrap()
+    }
+
+    pub fn interface_impl() -> ModuleImplIR {
+        let module = quote! {
+            impl Countable for Counter {
+                /// Returns the value.
+                fn value(&self) -> u32 {
+                    self.value.get_or_default()
+                }
+
+                fn increment(&mut self, by: u32) {
+                    self.value.add(by);
+                }
+            }
+        };
+        let attr = quote!();
+        ModuleImplIR::try_from((&attr, &module))
+            .and_then(ModuleImplIR::into_interface)
+            .unwrap()
+    }
+
+    pub fn module_implementing_interface() -> ModuleImplIR {
+        let module = quote! {
+            impl Counter {
+                pub fn reset(&mut self) {
+                    self.value.set(0);
+                }
+            }
+        };
+        let attr = quote!(implements = [Countable]);
+        ModuleImplIR::try_from((&attr, &module)).unwrap()
+    }
+
     pub fn ext_contract() -> ModuleImplIR {
         let ext = quote!(
             pub trait Token {
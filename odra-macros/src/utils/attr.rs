@@ -16,6 +16,10 @@ pub fn no_mangle() -> syn::Attribute {
     parse_quote!(#[no_mangle])
 }
 
+pub fn doc_hidden() -> syn::Attribute {
+    parse_quote!(#[doc(hidden)])
+}
+
 pub fn inline() -> syn::Attribute {
     parse_quote!(#[inline])
 }
@@ -147,6 +147,18 @@ pub fn vec<T: ToTokens>(content: T) -> syn::Expr {
     parse_quote!(odra::prelude::vec![#content])
 }
 
+/// Extends the vector with the items returned by the given expressions.
+pub fn extend_vec<T: ToTokens>(vec: T, extensions: &[syn::Expr]) -> syn::Expr {
+    if extensions.is_empty() {
+        return parse_quote!(#vec);
+    }
+    parse_quote!({
+        let mut items = #vec;
+        #(items.extend(#extensions);)*
+        items
+    })
+}
+
 pub fn clone<T: ToTokens>(caller: &T) -> syn::Expr {
     parse_quote!(#caller.clone())
 }
@@ -179,6 +191,12 @@ pub fn new_entry_point(name: String, args: Vec<syn::PatType>, is_payable: bool)
     }
 }
 
+pub fn with_entry_point_kind(entry_point: syn::Expr, kind: &str) -> syn::Expr {
+    let ty = super::ty::odra_entry_point_kind();
+    let kind = quote::format_ident!("{}", kind);
+    parse_quote!(#entry_point.with_kind(#ty::#kind))
+}
+
 fn new_entry_point_arg(arg: &syn::PatType) -> syn::Expr {
     let ty = super::ty::odra_entry_point_arg();
     let arg_ty = super::ty::unreferenced_ty(&arg.ty);
@@ -24,6 +24,14 @@ pub fn underscored_env() -> syn::Ident {
     format_ident!("__env")
 }
 
+pub fn call() -> syn::Ident {
+    format_ident!("call")
+}
+
+pub fn template_export() -> syn::Ident {
+    format_ident!("__odra_template_export")
+}
+
 pub fn exec_env() -> syn::Ident {
     format_ident!("exec_env")
 }
@@ -110,3 +118,11 @@ pub fn from() -> syn::Ident {
 pub fn error() -> syn::Ident {
     format_ident!("error")
 }
+
+pub fn receive_entry_point() -> syn::Ident {
+    format_ident!("__odra_receive")
+}
+
+pub fn fallback_entry_point() -> syn::Ident {
+    format_ident!("__odra_fallback")
+}
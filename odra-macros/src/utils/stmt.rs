@@ -54,6 +54,10 @@ pub fn new_rc(var_ident: &syn::Ident, env_ident: &syn::Ident) -> syn::Stmt {
     parse_quote!(let #var_ident = Rc::new(#env_ident);)
 }
 
+pub fn new_read_only_rc(var_ident: &syn::Ident, env_ident: &syn::Ident) -> syn::Stmt {
+    parse_quote!(let #var_ident = Rc::new(#env_ident.into_read_only());)
+}
+
 pub fn new_mut_vec_with_capacity<E: AsExpr>(ident: &syn::Ident, capacity_expr: &E) -> syn::Stmt {
     let ty = super::ty::vec();
     let expr = capacity_expr.as_expr();
@@ -218,3 +218,85 @@ pub fn transform_variants<F: Fn(String, Fields, u16, Vec<String>) -> TokenStream
 
     quote::quote!(odra::prelude::vec![#(#variants)*])
 }
+
+/// Returns the type arguments of the given type, which must be the given generic type.
+pub fn type_args(ty: &syn::Type, ident: &syn::Ident) -> syn::Result<Vec<syn::Type>> {
+    let err = || syn::Error::new_spanned(ty, format!("Expected `{}` with type arguments", ident));
+    let segment = match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path.segments.last(),
+        _ => None
+    }
+    .filter(|segment| &segment.ident == ident)
+    .ok_or_else(err)?;
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .map(|arg| match arg {
+                syn::GenericArgument::Type(ty) => Ok(ty.clone()),
+                _ => Err(err())
+            })
+            .collect(),
+        _ => Err(err())
+    }
+}
+
+pub fn type_params(generics: &syn::Generics) -> Vec<syn::Ident> {
+    generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect()
+}
+
+/// Checks if the given type refers to any of the given type parameters.
+pub fn uses_type_params(ty: &syn::Type, params: &[syn::Ident]) -> bool {
+    struct Visitor<'a> {
+        params: &'a [syn::Ident],
+        found: bool
+    }
+
+    impl<'ast> syn::visit::Visit<'ast> for Visitor<'_> {
+        fn visit_path(&mut self, path: &'ast syn::Path) {
+            if let (None, Some(segment)) = (path.leading_colon, path.segments.first()) {
+                self.found |= self.params.contains(&segment.ident);
+            }
+            syn::visit::visit_path(self, path);
+        }
+    }
+
+    let mut visitor = Visitor {
+        params,
+        found: false
+    };
+    syn::visit::Visit::visit_type(&mut visitor, ty);
+    visitor.found
+}
+
+/// Replaces the type parameters with concrete types.
+pub struct SubstituteTypeParams<'a> {
+    substitutions: &'a [(syn::Ident, syn::Type)]
+}
+
+impl<'a> SubstituteTypeParams<'a> {
+    pub fn new(substitutions: &'a [(syn::Ident, syn::Type)]) -> Self {
+        Self { substitutions }
+    }
+}
+
+impl syn::visit_mut::VisitMut for SubstituteTypeParams<'_> {
+    fn visit_type_mut(&mut self, ty: &mut syn::Type) {
+        if let syn::Type::Path(syn::TypePath { qself: None, path }) = ty {
+            let substitution = path.get_ident().and_then(|ident| {
+                self.substitutions
+                    .iter()
+                    .find(|(param, _)| param == ident)
+                    .map(|(_, concrete)| concrete.clone())
+            });
+            if let Some(concrete) = substitution {
+                *ty = concrete;
+                return;
+            }
+        }
+        syn::visit_mut::visit_type_mut(self, ty);
+    }
+}
@@ -73,6 +73,14 @@ pub fn module() -> syn::Type {
     parse_quote!(Module)
 }
 
+pub fn owner_guard() -> syn::Type {
+    parse_quote!(odra::module::OwnerGuard)
+}
+
+pub fn role_guard() -> syn::Type {
+    parse_quote!(odra::module::RoleGuard)
+}
+
 pub fn entry_points() -> syn::Type {
     parse_quote!(odra::casper_types::EntryPoints)
 }
@@ -233,6 +241,14 @@ pub fn odra_entry_point() -> syn::Type {
     parse_quote!(odra::entry_point_callback::EntryPoint)
 }
 
+pub fn odra_entry_point_kind() -> syn::Type {
+    parse_quote!(odra::entry_point_callback::EntryPointKind)
+}
+
+pub fn multicall_call() -> syn::Type {
+    parse_quote!(odra::multicall::Call)
+}
+
 pub fn odra_entry_point_arg() -> syn::Type {
     parse_quote!(odra::entry_point_callback::Argument)
 }
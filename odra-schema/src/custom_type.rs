@@ -146,6 +146,8 @@ impl_schema_errors!(
     Bytes
 );
 
+impl_schema_errors!(OdraError, ExecutionError);
+
 impl<T: CLTyped> SchemaErrors for Option<T> {}
 
 impl<T: CLTyped> SchemaErrors for Vec<T> {}
@@ -91,6 +91,22 @@ pub fn entry_point<T: NamedCLTyped>(
     }
 }
 
+/// Records the guards of the entrypoint in its description.
+///
+/// Guards are checked by the contract itself, e.g. `owner` for `#[odra(only_owner)]`,
+/// so the entrypoint stays public and the guards are appended to the description.
+pub fn with_guards(entry_point: Entrypoint, guards: Vec<&str>) -> Entrypoint {
+    let guards = format!("Guarded by: {}.", guards.join(", "));
+    let description = match entry_point.description.as_deref() {
+        None | Some("") => guards,
+        Some(description) => format!("{} {}", description, guards)
+    };
+    Entrypoint {
+        description: Some(description),
+        ..entry_point
+    }
+}
+
 /// Creates a new struct member.
 pub fn struct_member<T: NamedCLTyped>(name: &str) -> StructMember {
     StructMember {
@@ -312,6 +328,24 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_entry_point_with_guards() {
+        let entry_point = super::entry_point::<()>("mint", "Mints tokens.", true, vec![]);
+        let entry_point = super::with_guards(entry_point, vec!["role:MINTER", "guard:not_paused"]);
+        assert_eq!(
+            entry_point.description,
+            Some("Mints tokens. Guarded by: role:MINTER, guard:not_paused.".to_string())
+        );
+        assert!(matches!(entry_point.access, Access::Public));
+
+        let entry_point = super::entry_point::<()>("pause", "", true, vec![]);
+        let entry_point = super::with_guards(entry_point, vec!["owner"]);
+        assert_eq!(
+            entry_point.description,
+            Some("Guarded by: owner.".to_string())
+        );
+    }
+
     #[test]
     fn test_struct_member() {
         let member = super::struct_member::<u32>("member1");
@@ -0,0 +1,166 @@
+//! Assertion helpers backing the `assert_*` macros exported by this crate.
+//!
+//! Failure messages decode errors and events using the contract schema, so a failed
+//! assertion shows `Transfer { from: .., amount: 10 }` or `InsufficientBalance` instead of
+//! raw bytes and error codes.
+use crate::decoder::Schema;
+use odra_core::casper_types::bytesrepr::{Bytes, ToBytes};
+use odra_core::casper_types::U512;
+use odra_core::host::{HostEnv, HostRef};
+use odra_core::prelude::*;
+use odra_core::Addressable;
+use odra_schema::casper_contract_schema::CustomType;
+use odra_schema::{SchemaCustomTypes, SchemaErrors};
+
+/// An event expected to be emitted by a contract, along with the schema needed to render it.
+pub struct ExpectedEvent {
+    bytes: Bytes,
+    types: fn() -> Vec<Option<CustomType>>
+}
+
+impl ExpectedEvent {
+    /// Creates a new expected event.
+    pub fn new<T: ToBytes + SchemaCustomTypes>(event: &T) -> Self {
+        Self {
+            bytes: Bytes::from(event.to_bytes().expect("Couldn't serialize event")),
+            types: T::schema_types
+        }
+    }
+}
+
+/// Asserts the result of a contract call is the given error.
+///
+/// The error type schema is used to print the error names in the failure message.
+#[track_caller]
+pub fn assert_reverts<T, E: Into<OdraError> + SchemaErrors>(result: OdraResult<T>, expected: E) {
+    let expected = expected.into();
+    match result {
+        Ok(_) => panic!(
+            "expected the call to revert with `{}`, but it succeeded",
+            describe_error::<E>(&expected)
+        ),
+        Err(error) if error != expected => panic!(
+            "expected the call to revert with `{}`, but it reverted with `{}`",
+            describe_error::<E>(&expected),
+            describe_error::<E>(&error)
+        ),
+        Err(_) => {}
+    }
+}
+
+/// Asserts the given event was emitted by the contract.
+#[track_caller]
+pub fn assert_emitted<R: HostRef>(contract: &R, event: ExpectedEvent) {
+    let emitted = contract.env().events(contract);
+    if emitted.contains(&event.bytes) {
+        return;
+    }
+
+    let schema = schema(core::slice::from_ref(&event));
+    panic!(
+        "expected `{}` to be emitted by {}\n{}",
+        schema.describe_event(&event.bytes),
+        contract.address().to_string(),
+        describe_events(&schema, &emitted)
+    );
+}
+
+/// Asserts the given events were emitted by the contract in the given order.
+///
+/// Other events may be emitted in between the expected ones.
+#[track_caller]
+pub fn assert_events_in_order<R: HostRef>(contract: &R, events: &[ExpectedEvent]) {
+    let emitted = contract.env().events(contract);
+    let mut position = 0;
+    for (idx, event) in events.iter().enumerate() {
+        match emitted[position..].iter().position(|e| e == &event.bytes) {
+            Some(offset) => position += offset + 1,
+            None => {
+                let schema = schema(events);
+                let found_earlier = emitted[..position].contains(&event.bytes);
+                panic!(
+                    "expected event #{} `{}` to be emitted by {} after position {}{}\n{}",
+                    idx,
+                    schema.describe_event(&event.bytes),
+                    contract.address().to_string(),
+                    position,
+                    if found_earlier {
+                        ", but it was emitted earlier"
+                    } else {
+                        ""
+                    },
+                    describe_events(&schema, &emitted)
+                );
+            }
+        }
+    }
+}
+
+/// Asserts the CSPR balance of the given address changes by `delta` while executing `f`.
+///
+/// Returns the result of `f`. Note that the account sending a deploy pays for the gas,
+/// so on the Casper backend assert on accounts other than the caller.
+#[track_caller]
+pub fn assert_balance_change<A: Addressable, T, F: FnOnce() -> T>(
+    env: &HostEnv,
+    address: &A,
+    delta: i128,
+    f: F
+) -> T {
+    let before = env.balance_of(address);
+    let result = f();
+    let after = env.balance_of(address);
+
+    let expected_after = if delta.is_negative() {
+        before.checked_sub(U512::from(delta.unsigned_abs()))
+    } else {
+        before.checked_add(U512::from(delta.unsigned_abs()))
+    };
+    if expected_after != Some(after) {
+        let actual = if after >= before {
+            format!("+{}", after - before)
+        } else {
+            format!("-{}", before - after)
+        };
+        panic!(
+            "expected the balance of {} to change by {:+}, but it changed by {} (before: {}, after: {})",
+            address.address().to_string(),
+            delta,
+            actual,
+            before,
+            after
+        );
+    }
+    result
+}
+
+fn describe_error<E: SchemaErrors>(error: &OdraError) -> String {
+    match error {
+        OdraError::ExecutionError(ExecutionError::User(code)) => E::schema_errors()
+            .into_iter()
+            .find(|e| e.discriminant == *code)
+            .map(|e| format!("{} (user error {})", e.name, code))
+            .unwrap_or_else(|| format!("user error {}", code)),
+        OdraError::ExecutionError(error) => format!("{:?} (code {})", error, error.code()),
+        OdraError::VmError(error) => format!("VmError::{:?}", error)
+    }
+}
+
+fn schema(events: &[ExpectedEvent]) -> Schema {
+    let mut schema = Schema::default();
+    for event in events {
+        schema.extend((event.types)());
+    }
+    schema
+}
+
+fn describe_events(schema: &Schema, events: &[Bytes]) -> String {
+    if events.is_empty() {
+        return "no events were emitted".to_string();
+    }
+    let mut result = String::from("emitted events:");
+    for (idx, bytes) in events.iter().enumerate() {
+        result.push_str(&format!("\n  {}: {}", idx, schema.describe_event(bytes)));
+    }
+    result
+}
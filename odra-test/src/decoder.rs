@@ -0,0 +1,190 @@
+//! Schema-driven decoding of raw values, used to render readable assertion messages.
+use odra_core::casper_types::bytesrepr::FromBytes;
+use odra_core::casper_types::{AsymmetricType, PublicKey, URef, U128, U256, U512};
+use odra_core::prelude::*;
+use odra_schema::casper_contract_schema::{CustomType, NamedCLType, StructMember};
+
+const EVENT_PREFIX: &str = "event_";
+
+/// A set of custom types used to decode raw events.
+#[derive(Default)]
+pub(crate) struct Schema {
+    types: BTreeMap<String, CustomType>
+}
+
+impl Schema {
+    /// Adds the given custom types to the schema.
+    pub fn extend(&mut self, types: Vec<Option<CustomType>>) {
+        for ty in types.into_iter().flatten() {
+            let name = match &ty {
+                CustomType::Struct { name, .. } => name.0.clone(),
+                CustomType::Enum { name, .. } => name.0.clone()
+            };
+            self.types.insert(name, ty);
+        }
+    }
+
+    /// Renders a serialized event in a human-readable form.
+    ///
+    /// If the event is not described by the schema, only its name and size are rendered.
+    pub fn describe_event(&self, bytes: &[u8]) -> String {
+        let (name, rest) = match String::from_bytes(bytes) {
+            Ok((name, rest)) if name.starts_with(EVENT_PREFIX) => (name, rest),
+            _ => return format!("<malformed event {}>", hex(bytes))
+        };
+        let name = &name[EVENT_PREFIX.len()..];
+        match self.decode_custom(name, rest) {
+            Some((value, [])) => value,
+            _ => format!(
+                "{} <{} bytes, not described by the schema>",
+                name,
+                rest.len()
+            )
+        }
+    }
+
+    fn decode<'a>(&self, ty: &NamedCLType, bytes: &'a [u8]) -> Option<(String, &'a [u8])> {
+        match ty {
+            NamedCLType::Bool => display::<bool>(bytes),
+            NamedCLType::I32 => display::<i32>(bytes),
+            NamedCLType::I64 => display::<i64>(bytes),
+            NamedCLType::U8 => display::<u8>(bytes),
+            NamedCLType::U32 => display::<u32>(bytes),
+            NamedCLType::U64 => display::<u64>(bytes),
+            NamedCLType::U128 => display::<U128>(bytes),
+            NamedCLType::U256 => display::<U256>(bytes),
+            NamedCLType::U512 => display::<U512>(bytes),
+            NamedCLType::Unit => Some(("()".to_string(), bytes)),
+            NamedCLType::String => String::from_bytes(bytes)
+                .ok()
+                .map(|(value, rest)| (format!("{:?}", value), rest)),
+            NamedCLType::Key => Address::from_bytes(bytes)
+                .ok()
+                .map(|(value, rest)| (value.to_string(), rest)),
+            NamedCLType::URef => URef::from_bytes(bytes)
+                .ok()
+                .map(|(value, rest)| (value.to_formatted_string(), rest)),
+            NamedCLType::PublicKey => PublicKey::from_bytes(bytes)
+                .ok()
+                .map(|(value, rest)| (value.to_hex(), rest)),
+            NamedCLType::Option(ty) => {
+                let (tag, rest) = u8::from_bytes(bytes).ok()?;
+                match tag {
+                    0 => Some(("None".to_string(), rest)),
+                    1 => self
+                        .decode(ty, rest)
+                        .map(|(value, rest)| (format!("Some({})", value), rest)),
+                    _ => None
+                }
+            }
+            NamedCLType::Result { ok, err } => {
+                let (tag, rest) = u8::from_bytes(bytes).ok()?;
+                let (ty, variant) = match tag {
+                    0 => (err, "Err"),
+                    1 => (ok, "Ok"),
+                    _ => return None
+                };
+                self.decode(ty, rest)
+                    .map(|(value, rest)| (format!("{}({})", variant, value), rest))
+            }
+            NamedCLType::List(ty) => {
+                let (len, mut rest) = u32::from_bytes(bytes).ok()?;
+                let mut items = Vec::new();
+                for _ in 0..len {
+                    let (value, remainder) = self.decode(ty, rest)?;
+                    items.push(value);
+                    rest = remainder;
+                }
+                Some((format!("[{}]", items.join(", ")), rest))
+            }
+            NamedCLType::Map { key, value } => {
+                let (len, mut rest) = u32::from_bytes(bytes).ok()?;
+                let mut entries = Vec::new();
+                for _ in 0..len {
+                    let (k, remainder) = self.decode(key, rest)?;
+                    let (v, remainder) = self.decode(value, remainder)?;
+                    entries.push(format!("{}: {}", k, v));
+                    rest = remainder;
+                }
+                Some((format!("{{{}}}", entries.join(", ")), rest))
+            }
+            NamedCLType::ByteArray(len) => {
+                let len = *len as usize;
+                if bytes.len() < len {
+                    return None;
+                }
+                Some((hex(&bytes[..len]), &bytes[len..]))
+            }
+            NamedCLType::Tuple1(types) => self.decode_tuple(types, bytes),
+            NamedCLType::Tuple2(types) => self.decode_tuple(types, bytes),
+            NamedCLType::Tuple3(types) => self.decode_tuple(types, bytes),
+            NamedCLType::Custom(name) => self.decode_custom(name, bytes)
+        }
+    }
+
+    fn decode_tuple<'a>(
+        &self,
+        types: &[Box<NamedCLType>],
+        mut bytes: &'a [u8]
+    ) -> Option<(String, &'a [u8])> {
+        let mut items = Vec::new();
+        for ty in types {
+            let (value, rest) = self.decode(ty, bytes)?;
+            items.push(value);
+            bytes = rest;
+        }
+        Some((format!("({})", items.join(", ")), bytes))
+    }
+
+    fn decode_custom<'a>(&self, name: &str, bytes: &'a [u8]) -> Option<(String, &'a [u8])> {
+        match self.types.get(name)? {
+            CustomType::Struct { members, .. } => self.decode_struct(name, members, bytes),
+            CustomType::Enum { variants, .. } => {
+                let (tag, rest) = u8::from_bytes(bytes).ok()?;
+                let variant = variants.iter().find(|v| v.discriminant == tag as u16)?;
+                match &variant.ty.0 {
+                    NamedCLType::Unit => Some((format!("{}::{}", name, variant.name), rest)),
+                    // Variants with named fields are described as standalone structs.
+                    NamedCLType::Custom(ty) if ty == &format!("{}::{}", name, variant.name) => {
+                        self.decode_custom(ty, rest)
+                    }
+                    ty => self.decode(ty, rest).map(|(value, rest)| {
+                        (format!("{}::{}({})", name, variant.name, value), rest)
+                    })
+                }
+            }
+        }
+    }
+
+    fn decode_struct<'a>(
+        &self,
+        name: &str,
+        members: &[StructMember],
+        mut bytes: &'a [u8]
+    ) -> Option<(String, &'a [u8])> {
+        if members.is_empty() {
+            return Some((name.to_string(), bytes));
+        }
+        let mut fields = Vec::new();
+        for member in members {
+            let (value, rest) = self.decode(&member.ty.0, bytes)?;
+            fields.push(format!("{}: {}", member.name, value));
+            bytes = rest;
+        }
+        Some((format!("{} {{ {} }}", name, fields.join(", ")), bytes))
+    }
+}
+
+fn display<T: FromBytes + ToString>(bytes: &[u8]) -> Option<(String, &[u8])> {
+    T::from_bytes(bytes)
+        .ok()
+        .map(|(value, rest)| (value.to_string(), rest))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let mut result = String::from("0x");
+    for byte in bytes {
+        result.push_str(&format!("{:02x}", byte));
+    }
+    result
+}
@@ -15,35 +15,167 @@
 //! ```
 #![no_std]
 
+extern crate std;
+
 #[cfg(target_arch = "wasm32")]
 compile_error!("odra-test is not meant to be compiled for wasm32");
 
 use odra_casper_test_vm::{CasperHost, CasperVm};
-use odra_core::host::HostEnv;
+use odra_core::host::{GenesisBalances, HostEnv};
 use odra_core::prelude::String;
 use odra_vm::{OdraVm, OdraVmHost};
 
+pub use odra_casper_test_vm::{costs, CasperVmConfig};
+
+pub mod assertions;
+mod decoder;
+
 /// Returns the host environment for the testing purpose.
 ///
 /// Two environments are supported: [odra-vm](OdraVmHost) and [casper](CasperHost).
 pub fn env() -> HostEnv {
-    extern crate std;
+    env_with_config(CasperVmConfig::default())
+}
+
+/// Returns the host environment for the testing purpose with the given balances of the genesis accounts.
+///
+/// The backend is selected the same way as in [env].
+pub fn env_with_balances(genesis_balances: GenesisBalances) -> HostEnv {
+    env_with_config(CasperVmConfig::default().with_genesis_balances(genesis_balances))
+}
 
+/// Returns the host environment for the testing purpose with the given configuration.
+///
+/// The backend is selected the same way as in [env]. The chainspec, gas costs, gas price
+/// and deploy size limit apply to the casper backend only, the odra-vm backend
+/// uses just the genesis balances.
+///
+/// # Example
+///
+/// ```no_run
+/// let config = odra_test::CasperVmConfig::new()
+///     .with_chainspec("resources/chainspec.toml")
+///     .with_gas_price(2);
+/// let env = odra_test::env_with_config(config);
+/// ```
+pub fn env_with_config(config: CasperVmConfig) -> HostEnv {
     let backend: String = std::env::var("ODRA_BACKEND").unwrap_or_default();
     match backend.as_str() {
-        "casper" => casper_env(),
-        _ => odra_env()
+        "casper" => casper_env(config),
+        _ => odra_env(config.genesis_balances().clone())
     }
 }
 
-fn casper_env() -> HostEnv {
-    let vm = CasperVm::new();
+/// Returns the host environment backed by the [OdraVm], regardless of the `ODRA_BACKEND` variable.
+pub fn odra_vm_env() -> HostEnv {
+    odra_env(CasperVmConfig::default().genesis_balances().clone())
+}
+
+/// Returns the host environment backed by the [CasperVm], regardless of the `ODRA_BACKEND` variable.
+///
+/// The wasm files of the deployed contracts must be built beforehand.
+pub fn casper_vm_env() -> HostEnv {
+    casper_env(CasperVmConfig::default())
+}
+
+fn casper_env(config: CasperVmConfig) -> HostEnv {
+    let vm = CasperVm::new_with_config(config);
     let host_env = CasperHost::new(vm);
     HostEnv::new(host_env)
 }
 
-fn odra_env() -> HostEnv {
-    let vm = OdraVm::new();
+fn odra_env(genesis_balances: GenesisBalances) -> HostEnv {
+    let vm = OdraVm::new_with_balances(genesis_balances);
     let host_env = OdraVmHost::new(vm);
     HostEnv::new(host_env)
 }
+
+/// Asserts a contract call reverts with the given error.
+///
+/// The call must return an [OdraResult](odra_core::prelude::OdraResult), so use the `try_` variant
+/// of a contract method.
+///
+/// # Example
+///
+/// ```ignore
+/// odra_test::assert_reverts!(token.try_transfer(&alice, &amount), Error::InsufficientBalance);
+/// ```
+#[macro_export]
+macro_rules! assert_reverts {
+    ($call:expr, $error:expr $(,)?) => {
+        $crate::assertions::assert_reverts($call, $error)
+    };
+}
+
+/// Asserts the given event was emitted by the contract.
+///
+/// # Example
+///
+/// ```ignore
+/// odra_test::assert_emitted!(token, Transfer { from, to, amount });
+/// ```
+#[macro_export]
+macro_rules! assert_emitted {
+    ($contract:expr, $event:expr $(,)?) => {
+        $crate::assertions::assert_emitted(
+            &$contract,
+            $crate::assertions::ExpectedEvent::new(&$event)
+        )
+    };
+}
+
+/// Asserts the given events were emitted by the contract in the given order.
+///
+/// # Example
+///
+/// ```ignore
+/// odra_test::assert_events_in_order!(token, [Mint { .. }, Transfer { .. }]);
+/// ```
+#[macro_export]
+macro_rules! assert_events_in_order {
+    ($contract:expr, [$($event:expr),+ $(,)?] $(,)?) => {
+        $crate::assertions::assert_events_in_order(
+            &$contract,
+            &[$($crate::assertions::ExpectedEvent::new(&$event)),+]
+        )
+    };
+}
+
+/// Asserts the CSPR balance of the given address changes by the given delta
+/// while executing the closure. Evaluates to the closure result.
+///
+/// # Example
+///
+/// ```ignore
+/// odra_test::assert_balance_change!(env, alice, 100, || wallet.withdraw(&100.into()));
+/// ```
+#[macro_export]
+macro_rules! assert_balance_change {
+    ($env:expr, $address:expr, $delta:expr, $f:expr $(,)?) => {
+        $crate::assertions::assert_balance_change(&$env, &$address, $delta, $f)
+    };
+}
+
+/// Writes the gas profile of the given environment to a file.
+///
+/// If the path has the `json` extension, the profile is written as JSON, otherwise
+/// as folded stacks, which can be turned into a flamegraph, e.g. with `inferno-flamegraph`.
+///
+/// Nested calls and host operations are profiled only by the OdraVm, the CasperVm
+/// profile has a single frame per deploy.
+///
+/// # Example
+///
+/// ```no_run
+/// let env = odra_test::env();
+/// // Deploy and call your contracts here.
+/// odra_test::write_gas_profile(&env, "mint.folded").unwrap();
+/// ```
+pub fn write_gas_profile<P: AsRef<std::path::Path>>(env: &HostEnv, path: P) -> std::io::Result<()> {
+    let profile = env.gas_profile();
+    let content = match path.as_ref().extension().and_then(|ext| ext.to_str()) {
+        Some("json") => profile.to_json(),
+        _ => profile.to_folded()
+    };
+    std::fs::write(path, content)
+}
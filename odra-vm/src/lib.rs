@@ -11,4 +11,4 @@ mod odra_vm_host;
 mod vm;
 
 pub use odra_vm_host::OdraVmHost;
-pub use vm::OdraVm;
+pub use vm::{GasCosts, OdraVm};
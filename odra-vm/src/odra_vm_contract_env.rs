@@ -1,10 +1,14 @@
+use crate::vm::gas::HostOperation;
 use crate::vm::OdraVm;
+use crate::OdraVmHost;
 use blake2::digest::VariableOutput;
 use blake2::{Blake2b, Blake2b512, Blake2bVar, Blake2s256, Digest};
 use odra_core::casper_types::{
     bytesrepr::{Bytes, ToBytes},
-    CLValue, U512
+    CLValue, RuntimeArgs, U512
 };
+use odra_core::factory::ContractTemplate;
+use odra_core::host::HostEnv;
 use odra_core::prelude::*;
 use odra_core::{casper_types, CallDef, ContractContext};
 use std::collections::hash_map::DefaultHasher;
@@ -56,6 +60,14 @@ impl ContractContext for OdraVmContractEnv {
         self.vm.borrow().call_contract(address, call_def)
     }
 
+    fn new_contract(&self, template: &ContractTemplate, init_args: Option<RuntimeArgs>) -> Address {
+        let host_env = HostEnv::new(OdraVmHost::new(self.vm.clone()));
+        let entry_points_caller = template.entry_points_caller(&host_env);
+        self.vm
+            .borrow()
+            .new_contract(template.name(), entry_points_caller, init_args)
+    }
+
     fn get_block_time(&self) -> u64 {
         self.vm.borrow().get_block_time()
     }
@@ -97,6 +109,7 @@ impl ContractContext for OdraVmContractEnv {
     }
 
     fn hash(&self, bytes: &[u8]) -> [u8; 32] {
+        self.vm.borrow().charge_gas(HostOperation::Hash);
         let mut result = [0u8; 32];
         let mut hasher = <Blake2bVar as VariableOutput>::new(32).expect("should create hasher");
         let _ = hasher.write(bytes);
@@ -105,6 +118,10 @@ impl ContractContext for OdraVmContractEnv {
             .expect("should copy hash to the result array");
         result
     }
+
+    fn log(&self, message: &str) {
+        self.vm.borrow().log(message)
+    }
 }
 
 impl OdraVmContractEnv {
@@ -1,4 +1,5 @@
 use crate::odra_vm_contract_env::OdraVmContractEnv;
+use crate::vm::gas::HostOperation;
 use crate::OdraVm;
 use odra_core::casper_types::{bytesrepr::Bytes, PublicKey, RuntimeArgs, U512};
 use odra_core::entry_point_callback::EntryPointsCaller;
@@ -7,12 +8,15 @@ use odra_core::{
     host::{HostContext, HostEnv},
     CallDef, ContractContext, ContractEnv
 };
-use odra_core::{EventError, GasReport, VmError};
+use odra_core::{DeployReport, EventError, GasProfile, GasReport, VmError};
+use std::cell::Cell;
 
 /// HostContext utilizing the Odra in-memory virtual machine.
 pub struct OdraVmHost {
     vm: Rc<RefCell<OdraVm>>,
-    contract_env: Rc<ContractEnv>
+    contract_env: Rc<ContractEnv>,
+    gas_report: RefCell<GasReport>,
+    last_call_gas_cost: Cell<u64>
 }
 
 impl HostContext for OdraVmHost {
@@ -32,10 +36,22 @@ impl HostContext for OdraVmHost {
         self.vm.borrow().get_account(index)
     }
 
+    fn new_account(&self) -> Address {
+        self.vm.borrow().new_account()
+    }
+
+    fn account(&self, label: &str) -> Address {
+        self.vm.borrow().account(label)
+    }
+
     fn balance_of(&self, address: &Address) -> U512 {
         self.vm.borrow().balance_of(address)
     }
 
+    fn set_balance(&self, address: &Address, amount: U512) {
+        self.vm.borrow().set_balance(*address, amount)
+    }
+
     fn advance_block_time(&self, time_diff: u64) {
         self.vm.borrow().advance_block_time_by(time_diff)
     }
@@ -56,20 +72,27 @@ impl HostContext for OdraVmHost {
         &self,
         address: &Address,
         call_def: CallDef,
-        _use_proxy: bool
+        use_proxy: bool
     ) -> OdraResult<Bytes> {
-        let mut opt_result: Option<Bytes> = None;
-        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-            opt_result = Some(self.vm.borrow().call_contract(*address, call_def));
-        }));
-
-        match opt_result {
-            Some(result) => Ok(result),
-            None => {
-                let error = self.vm.borrow().error();
-                Err(error.unwrap_or(OdraError::VmError(VmError::Panic)))
-            }
-        }
+        // Drop the gas used outside of any call.
+        self.vm.borrow().take_gas_used();
+        self.vm.borrow().charge_gas_for(
+            address,
+            call_def.entry_point(),
+            HostOperation::Call { proxy: use_proxy }
+        );
+        let result = self.execute_call(address, call_def.clone());
+
+        let gas = self.vm.borrow().take_gas_used();
+        self.last_call_gas_cost.set(gas);
+        self.gas_report
+            .borrow_mut()
+            .push(DeployReport::ContractCall {
+                gas: U512::from(gas),
+                contract_address: *address,
+                call_def
+            });
+        result
     }
 
     fn new_contract(
@@ -78,27 +101,45 @@ impl HostContext for OdraVmHost {
         init_args: RuntimeArgs,
         entry_points_caller: EntryPointsCaller
     ) -> OdraResult<Address> {
+        self.vm.borrow().take_gas_used();
         let address = self
             .vm
             .borrow()
             .register_contract(name, entry_points_caller.clone());
+        self.vm
+            .borrow()
+            .charge_gas_for(&address, "init", HostOperation::Deploy);
 
         if entry_points_caller
             .entry_points()
             .iter()
             .any(|ep| ep.name == "init")
         {
-            self.call_contract(
+            let result = self.execute_call(
                 &address,
-                CallDef::new(String::from("init"), true, init_args),
-                false
-            )?;
+                CallDef::new(String::from("init"), true, init_args)
+            );
+            self.report_deploy(name);
+            result?;
             self.vm.borrow().post_install(address);
+        } else {
+            self.report_deploy(name);
         }
 
         Ok(address)
     }
 
+    fn install_wasm(
+        &self,
+        _wasm_path: &str,
+        _args: RuntimeArgs,
+        _package_hash_key: &str
+    ) -> OdraResult<Address> {
+        Err(OdraError::VmError(VmError::UnsupportedOperation(
+            String::from("install_wasm")
+        )))
+    }
+
     fn register_contract(
         &self,
         address: Address,
@@ -113,13 +154,16 @@ impl HostContext for OdraVmHost {
     }
 
     fn gas_report(&self) -> GasReport {
-        // For OdraVM there is no gas, so nothing to report.c
-        GasReport::default()
+        // The gas is estimated from the executed host operations, see [GasCosts](crate::GasCosts).
+        self.gas_report.borrow().clone()
+    }
+
+    fn gas_profile(&self) -> GasProfile {
+        self.vm.borrow().gas_profile()
     }
 
     fn last_call_gas_cost(&self) -> u64 {
-        // For OdraVM there is no gas, so nothing to return.
-        0
+        self.last_call_gas_cost.get()
     }
 
     fn sign_message(&self, message: &Bytes, address: &Address) -> Bytes {
@@ -131,6 +175,15 @@ impl HostContext for OdraVmHost {
     }
 
     fn transfer(&self, to: Address, amount: U512) -> OdraResult<()> {
+        if to.is_contract() {
+            let receive_call = self.vm.borrow().receive_call(&to, amount);
+            return match receive_call {
+                Some(call_def) => self.call_contract(&to, call_def, true).map(|_| ()),
+                None => Err(OdraError::ExecutionError(
+                    ExecutionError::TransferToContract
+                ))
+            };
+        }
         let caller = self.caller();
         self.vm
             .borrow()
@@ -142,6 +195,35 @@ impl OdraVmHost {
     /// Creates a new `OdraVmHost` instance.
     pub fn new(vm: Rc<RefCell<OdraVm>>) -> Rc<RefCell<Self>> {
         let contract_env = Rc::new(ContractEnv::new(0, OdraVmContractEnv::new(vm.clone())));
-        Rc::new(RefCell::new(Self { vm, contract_env }))
+        Rc::new(RefCell::new(Self {
+            vm,
+            contract_env,
+            gas_report: RefCell::new(GasReport::default()),
+            last_call_gas_cost: Cell::new(0)
+        }))
+    }
+
+    fn execute_call(&self, address: &Address, call_def: CallDef) -> OdraResult<Bytes> {
+        let mut opt_result: Option<Bytes> = None;
+        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            opt_result = Some(self.vm.borrow().call_contract(*address, call_def));
+        }));
+
+        match opt_result {
+            Some(result) => Ok(result),
+            None => {
+                let error = self.vm.borrow().error();
+                Err(error.unwrap_or(OdraError::VmError(VmError::Panic)))
+            }
+        }
+    }
+
+    fn report_deploy(&self, name: &str) {
+        let gas = self.vm.borrow().take_gas_used();
+        self.last_call_gas_cost.set(gas);
+        self.gas_report.borrow_mut().push(DeployReport::WasmDeploy {
+            gas: U512::from(gas),
+            file_name: format!("{}.wasm", name)
+        });
     }
 }
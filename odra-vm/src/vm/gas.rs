@@ -0,0 +1,174 @@
+//! Approximation of the gas used by the contracts executed by the OdraVm.
+use odra_core::prelude::*;
+use odra_core::{GasCategory, GasProfile};
+
+/// A host operation the [OdraVm](super::OdraVm) charges gas for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum HostOperation {
+    /// A call sent by an account. If `proxy` is true, the call is executed through the proxy caller.
+    Call { proxy: bool },
+    /// A call made by a contract to another contract.
+    NestedCall,
+    /// An installation of a contract.
+    Deploy,
+    /// A read of a contract variable.
+    StorageRead,
+    /// A write of `bytes` bytes to a contract variable.
+    StorageWrite { bytes: usize },
+    /// A read of a dictionary item.
+    DictionaryRead,
+    /// A write of `bytes` bytes to a dictionary item.
+    DictionaryWrite { bytes: usize },
+    /// An emission of an event of `bytes` bytes.
+    Event { bytes: usize },
+    /// A transfer of native tokens to an account.
+    TransferToAccount,
+    /// A transfer of native tokens to a contract.
+    TransferToContract,
+    /// A computation of a hash.
+    Hash
+}
+
+/// Gas costs of the host operations executed by the [OdraVm](super::OdraVm).
+///
+/// The default costs are calibrated against the CasperVm running the default chainspec:
+/// the estimates of the representative calls checked by the `gas_estimation` test of the examples
+/// stay within 30% of the measured gas. The wasm execution and the size of the installed wasm
+/// are not taken into account, so the estimation should be used to spot expensive paths,
+/// not to predict the exact cost of a deploy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GasCosts {
+    /// The base cost of a call sent by an account.
+    pub call: u64,
+    /// The additional cost of a call executed through the proxy caller,
+    /// i.e. a call that returns a value or attaches tokens.
+    pub proxy_call: u64,
+    /// The cost of a call made by a contract to another contract.
+    pub nested_call: u64,
+    /// The base cost of a contract installation, the constructor is charged separately.
+    pub deploy: u64,
+    /// The cost of a read of a contract variable.
+    pub storage_read: u64,
+    /// The base cost of a write to a contract variable.
+    pub storage_write: u64,
+    /// The cost of a read of a dictionary item.
+    pub dictionary_read: u64,
+    /// The base cost of a write to a dictionary item.
+    pub dictionary_write: u64,
+    /// The cost of every byte written to the storage.
+    pub storage_byte: u64,
+    /// The base cost of an event emission.
+    pub event: u64,
+    /// The cost of every byte of an emitted event.
+    pub event_byte: u64,
+    /// The cost of a transfer of native tokens to an account.
+    pub transfer_to_account: u64,
+    /// The cost of a transfer of native tokens to a contract.
+    pub transfer_to_contract: u64,
+    /// The cost of a hash computation.
+    pub hash: u64
+}
+
+impl Default for GasCosts {
+    fn default() -> Self {
+        Self {
+            call: 150_000_000,
+            proxy_call: 280_000_000,
+            nested_call: 300_000_000,
+            deploy: 150_000_000_000,
+            storage_read: 50_000_000,
+            storage_write: 10_000_000,
+            dictionary_read: 50_000_000,
+            dictionary_write: 20_000_000,
+            storage_byte: 200_000,
+            event: 200_000_000,
+            event_byte: 500_000,
+            transfer_to_account: 2_500_000_000,
+            transfer_to_contract: 5_470_000_000,
+            hash: 1_200_000
+        }
+    }
+}
+
+impl HostOperation {
+    /// Returns the category the gas of the operation is attributed to.
+    pub fn category(&self) -> GasCategory {
+        match self {
+            HostOperation::Call { .. } | HostOperation::NestedCall => GasCategory::Calls,
+            HostOperation::Deploy => GasCategory::Deploy,
+            HostOperation::StorageRead
+            | HostOperation::StorageWrite { .. }
+            | HostOperation::DictionaryRead
+            | HostOperation::DictionaryWrite { .. } => GasCategory::Storage,
+            HostOperation::Event { .. } => GasCategory::Events,
+            HostOperation::TransferToAccount | HostOperation::TransferToContract => {
+                GasCategory::Transfers
+            }
+            HostOperation::Hash => GasCategory::Crypto
+        }
+    }
+}
+
+impl GasCosts {
+    /// Returns the gas cost of the given operation.
+    pub(crate) fn cost_of(&self, operation: HostOperation) -> u64 {
+        match operation {
+            HostOperation::Call { proxy: false } => self.call,
+            HostOperation::Call { proxy: true } => self.call.saturating_add(self.proxy_call),
+            HostOperation::NestedCall => self.nested_call,
+            HostOperation::Deploy => self.deploy,
+            HostOperation::StorageRead => self.storage_read,
+            HostOperation::StorageWrite { bytes } => self
+                .storage_write
+                .saturating_add(per_byte(self.storage_byte, bytes)),
+            HostOperation::DictionaryRead => self.dictionary_read,
+            HostOperation::DictionaryWrite { bytes } => self
+                .dictionary_write
+                .saturating_add(per_byte(self.storage_byte, bytes)),
+            HostOperation::Event { bytes } => {
+                self.event.saturating_add(per_byte(self.event_byte, bytes))
+            }
+            HostOperation::TransferToAccount => self.transfer_to_account,
+            HostOperation::TransferToContract => self.transfer_to_contract,
+            HostOperation::Hash => self.hash
+        }
+    }
+}
+
+/// Accumulates the gas charged for the host operations.
+#[derive(Default)]
+pub(crate) struct GasMeter {
+    costs: GasCosts,
+    used: u64,
+    profile: GasProfile
+}
+
+impl GasMeter {
+    /// Charges the gas for the operation executed in the given call stack.
+    pub fn charge(&mut self, stack: Vec<String>, operation: HostOperation) {
+        let cost = self.costs.cost_of(operation);
+        self.used = self.used.saturating_add(cost);
+        self.profile.record(stack, operation.category(), cost);
+    }
+
+    /// Returns the gas used since the last call and resets the meter.
+    pub fn take_used(&mut self) -> u64 {
+        core::mem::take(&mut self.used)
+    }
+
+    pub fn set_costs(&mut self, costs: GasCosts) {
+        self.costs = costs;
+    }
+
+    pub fn costs(&self) -> GasCosts {
+        self.costs
+    }
+
+    pub fn profile(&self) -> &GasProfile {
+        &self.profile
+    }
+}
+
+fn per_byte(cost: u64, bytes: usize) -> u64 {
+    cost.saturating_mul(bytes as u64)
+}
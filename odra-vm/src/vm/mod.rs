@@ -2,9 +2,11 @@
 #![allow(unused_imports)]
 #![allow(unused_variables)]
 mod balance;
+pub(crate) mod gas;
 mod odra_vm;
 mod odra_vm_state;
 mod storage;
 mod utils;
 
+pub use self::gas::GasCosts;
 pub use self::odra_vm::OdraVm;
@@ -6,11 +6,13 @@ use std::sync::{Arc, RwLock};
 use anyhow::Result;
 use odra_core::callstack::CallstackElement;
 use odra_core::casper_types::bytesrepr::{deserialize, deserialize_from_slice, serialize};
-use odra_core::casper_types::{CLType, CLValue};
+use odra_core::casper_types::{CLType, CLValue, RuntimeArgs};
 use odra_core::entry_point_callback::EntryPointsCaller;
+use odra_core::host::GenesisBalances;
 use odra_core::prelude::*;
 use odra_core::CallDef;
 use odra_core::EventError;
+use odra_core::GasProfile;
 use odra_core::VmError;
 use odra_core::{
     callstack,
@@ -21,6 +23,7 @@ use odra_core::{
 };
 use odra_core::{ContractContainer, ContractRegister};
 
+use super::gas::{GasCosts, GasMeter, HostOperation};
 use super::odra_vm_state::OdraVmState;
 const NAMED_KEY_PREFIX: &str = "NAMED_KEY";
 
@@ -28,7 +31,8 @@ const NAMED_KEY_PREFIX: &str = "NAMED_KEY";
 #[derive(Default)]
 pub struct OdraVm {
     state: Arc<RwLock<OdraVmState>>,
-    contract_register: Arc<RwLock<ContractRegister>>
+    contract_register: Arc<RwLock<ContractRegister>>,
+    gas_meter: Arc<RwLock<GasMeter>>
 }
 
 impl OdraVm {
@@ -37,10 +41,24 @@ impl OdraVm {
         Rc::new(RefCell::new(Self::default()))
     }
 
+    /// Creates a new instance of OdraVm with the given balances of the genesis accounts.
+    pub fn new_with_balances(genesis_balances: GenesisBalances) -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Self {
+            state: Arc::new(RwLock::new(OdraVmState::new(&genesis_balances))),
+            contract_register: Default::default(),
+            gas_meter: Default::default()
+        }))
+    }
+
     /// Adds a new contract to the virtual machine.
     pub fn register_contract(&self, name: &str, entry_points_caller: EntryPointsCaller) -> Address {
         // Create a new address.
-        let address = { self.state.write().unwrap().next_contract_address() };
+        let address = {
+            let mut state = self.state.write().unwrap();
+            let address = state.next_contract_address();
+            state.set_contract_name(address, name);
+            address
+        };
         // Register new contract under the new address.
         {
             let contract = ContractContainer::new(entry_points_caller);
@@ -57,6 +75,24 @@ impl OdraVm {
         address
     }
 
+    /// Installs a new contract on behalf of the currently executing contract.
+    ///
+    /// If `init_args` are given, the constructor is called by the current contract.
+    pub fn new_contract(
+        &self,
+        name: &str,
+        entry_points_caller: EntryPointsCaller,
+        init_args: Option<RuntimeArgs>
+    ) -> Address {
+        self.charge_gas(HostOperation::Deploy);
+        let address = self.register_contract(name, entry_points_caller);
+        if let Some(init_args) = init_args {
+            self.call_contract(address, CallDef::new("init", true, init_args));
+        }
+        self.post_install(address);
+        address
+    }
+
     pub(crate) fn post_install(&self, address: Address) {
         self.contract_register
             .write()
@@ -69,19 +105,21 @@ impl OdraVm {
     /// Returns the result of the call as [Bytes].
     /// If the call fails, the virtual machine is in error state, all the changes are reverted.
     pub fn call_contract(&self, address: Address, call_def: CallDef) -> Bytes {
+        if !self.state.read().unwrap().is_in_caller_context() {
+            self.charge_gas(HostOperation::NestedCall);
+        }
         self.prepare_call(address, &call_def);
         // Call contract from register.
         if call_def.amount() > U512::zero() {
+            self.charge_gas(HostOperation::TransferToContract);
             let status = self.checked_transfer_tokens(&self.caller(), &address, &call_def.amount());
             if let Err(err) = status {
                 self.revert(err);
             }
         }
-        let result = self
-            .contract_register
-            .read()
-            .unwrap()
-            .call(&address, call_def);
+        // The register is not locked during the call, so the called contract can install new contracts.
+        let contract = self.contract_register.read().unwrap().get(&address);
+        let result = contract.and_then(|contract| contract.call(call_def));
 
         match result {
             Err(err) => self.revert(err),
@@ -100,7 +138,11 @@ impl OdraVm {
         state.set_error(error.clone());
         state.clear_callstack();
         if state.is_in_caller_context() {
-            state.restore_snapshot();
+            // The contracts installed during the call are reverted along with the state.
+            let mut contract_register = self.contract_register.write().unwrap();
+            for address in state.restore_snapshot() {
+                contract_register.remove(&address);
+            }
         }
         drop(state);
 
@@ -152,6 +194,9 @@ impl OdraVm {
     ///
     /// If the global state write fails, the virtual machine is in error state.
     pub fn set_var(&self, key: &[u8], value: Bytes) {
+        self.charge_gas(HostOperation::StorageWrite {
+            bytes: key.len() + value.len()
+        });
         self.state.write().unwrap().set_var(key, value);
     }
 
@@ -160,6 +205,7 @@ impl OdraVm {
     /// Returns `None` if the variable does not exist.
     /// If the global state read fails, the virtual machine is in error state.
     pub fn get_var(&self, key: &[u8]) -> Option<Bytes> {
+        self.charge_gas(HostOperation::StorageRead);
         let result = { self.state.read().unwrap().get_var(key) };
         match result {
             Ok(result) => result,
@@ -187,6 +233,9 @@ impl OdraVm {
 
     /// Sets the value of the dictionary item.
     pub fn set_dict_value(&self, dict: &str, key: &[u8], value: CLValue) {
+        self.charge_gas(HostOperation::DictionaryWrite {
+            bytes: key.len() + value.inner_bytes().len()
+        });
         self.state.write().unwrap().set_dict_value(
             dict.as_bytes(),
             key,
@@ -196,6 +245,7 @@ impl OdraVm {
 
     /// Removes the dictionary from the global state.
     pub fn remove_dictionary(&self, dictionary_name: &str) {
+        self.charge_gas(HostOperation::StorageWrite { bytes: 0 });
         self.state
             .write()
             .unwrap()
@@ -207,6 +257,7 @@ impl OdraVm {
     /// Returns `None` if the dictionary or the key does not exist.
     /// If the dictionary or the key does not exist, the virtual machine is in error state.
     pub fn get_dict_value(&self, dict: &str, key: &[u8]) -> Option<Bytes> {
+        self.charge_gas(HostOperation::DictionaryRead);
         let result = {
             self.state
                 .read()
@@ -227,6 +278,9 @@ impl OdraVm {
 
     /// Writes an event data to the global state.
     pub fn emit_event(&self, event_data: &Bytes) {
+        self.charge_gas(HostOperation::Event {
+            bytes: event_data.len()
+        });
         self.state.write().unwrap().emit_event(event_data);
     }
 
@@ -268,21 +322,57 @@ impl OdraVm {
         self.state.read().unwrap().accounts.get(n).cloned().unwrap()
     }
 
+    /// Creates a new account with the default balance.
+    pub fn new_account(&self) -> Address {
+        self.state.write().unwrap().new_account()
+    }
+
+    /// Gets the address of the account with the given label, creates the account if it does not exist.
+    pub fn account(&self, label: &str) -> Address {
+        self.state.write().unwrap().account(label)
+    }
+
+    /// Prints a debug message tagged with the current contract, entry point and the call depth.
+    pub fn log(&self, message: &str) {
+        println!("{}", self.state.read().unwrap().format_log(message));
+    }
+
     /// Reads the balance of the given address from the global state.
     pub fn balance_of(&self, address: &Address) -> U512 {
         self.state.read().unwrap().balance_of(address)
     }
 
+    /// Overwrites the balance of the given address in the global state.
+    pub fn set_balance(&self, address: Address, amount: U512) {
+        self.state.write().unwrap().set_balance(address, amount)
+    }
+
     /// Updates the balances of the given address and the current address in the global state.
     ///
     /// If the global state write fails(wrong address, the passed amount exceeds the disposable balance),
     /// the virtual machine reverts.
+    ///
+    /// A transfer to a contract calls its receive entry point (`#[odra(receive)]`) with the transferred
+    /// amount attached. If the contract does not define one, the virtual machine reverts.
     pub fn transfer_tokens(&self, to: &Address, amount: &U512) {
         if amount.is_zero() {
             return;
         }
 
+        if to.is_contract() {
+            match self.receive_call(to, *amount) {
+                Some(call_def) => {
+                    self.call_contract(*to, call_def);
+                    return;
+                }
+                None => self.revert(OdraError::ExecutionError(
+                    ExecutionError::TransferToContract
+                ))
+            }
+        }
+
         let from = &self.self_address();
+        self.charge_gas(HostOperation::TransferToAccount);
 
         let mut transfer_error = None;
         {
@@ -319,6 +409,20 @@ impl OdraVm {
         Ok(())
     }
 
+    /// Returns the call of the receive entry point of the contract at the given address
+    /// transferring `amount`, if the contract defines one.
+    pub fn receive_call(&self, address: &Address, amount: U512) -> Option<CallDef> {
+        if !address.is_contract() {
+            return None;
+        }
+        self.contract_register
+            .read()
+            .unwrap()
+            .get(address)
+            .ok()
+            .and_then(|contract| contract.receive_call(amount))
+    }
+
     /// Reads the balance of the current contract from the global state.
     pub fn self_balance(&self) -> U512 {
         let address = self.self_address();
@@ -352,6 +456,46 @@ impl OdraVm {
         signature.into()
     }
 
+    /// Overrides the costs used to estimate the gas of the host operations.
+    pub fn set_gas_costs(&self, costs: GasCosts) {
+        self.gas_meter.write().unwrap().set_costs(costs);
+    }
+
+    /// Returns the costs used to estimate the gas of the host operations.
+    pub fn gas_costs(&self) -> GasCosts {
+        self.gas_meter.read().unwrap().costs()
+    }
+
+    /// Returns the gas estimated since the last invocation and resets the gas meter.
+    pub fn take_gas_used(&self) -> u64 {
+        self.gas_meter.write().unwrap().take_used()
+    }
+
+    /// Returns the gas profile of all the calls and deploys executed so far.
+    pub fn gas_profile(&self) -> GasProfile {
+        self.gas_meter.read().unwrap().profile().clone()
+    }
+
+    /// Charges the gas for the operation executed by the currently executing contract.
+    pub(crate) fn charge_gas(&self, operation: HostOperation) {
+        let stack = self.state.read().unwrap().call_frames();
+        self.gas_meter.write().unwrap().charge(stack, operation);
+    }
+
+    /// Charges the gas for the operation executed on behalf of the given entry point.
+    pub(crate) fn charge_gas_for(
+        &self,
+        address: &Address,
+        entry_point: &str,
+        operation: HostOperation
+    ) {
+        let frame = self.state.read().unwrap().call_frame(address, entry_point);
+        self.gas_meter
+            .write()
+            .unwrap()
+            .charge(vec![frame], operation);
+    }
+
     fn prepare_call(&self, address: Address, call_def: &CallDef) {
         let mut state = self.state.write().unwrap();
         // If only one address on the call_stack, record snapshot.
@@ -389,7 +533,8 @@ mod tests {
     use odra_core::callstack::CallstackElement;
     use odra_core::casper_types::bytesrepr::{Bytes, ToBytes};
     use odra_core::{
-        entry_point_callback::{EntryPoint, EntryPointsCaller},
+        entry_point_callback::{EntryPoint, EntryPointKind, EntryPointsCaller},
+        factory::ContractTemplate,
         utils::serialize
     };
 
@@ -397,11 +542,11 @@ mod tests {
 
     use odra_core::casper_types::bytesrepr::FromBytes;
     use odra_core::casper_types::{CLValue, RuntimeArgs, U512};
-    use odra_core::host::HostEnv;
-    use odra_core::{prelude::*, CallDef, VmError};
+    use odra_core::host::{GenesisBalances, HostContext, HostEnv};
+    use odra_core::{prelude::*, CallDef, DeployReport, VmError};
 
     use crate::vm::utils;
-    use crate::{OdraVm, OdraVmHost};
+    use crate::{GasCosts, OdraVm, OdraVmHost};
 
     const TEST_ENTRY_POINT: &str = "abc";
 
@@ -481,6 +626,44 @@ mod tests {
         instance.transfer_tokens(&to, &amount);
     }
 
+    #[test]
+    fn test_account_management() {
+        // given a vm with a custom balance of the first account
+        let instance = OdraVm::new_with_balances(
+            GenesisBalances::new(U512::from(1_000)).with_balance(0, U512::from(10))
+        );
+        let instance = instance.borrow();
+        assert_eq!(
+            instance.balance_of(&instance.get_account(0)),
+            U512::from(10)
+        );
+        assert_eq!(
+            instance.balance_of(&instance.get_account(1)),
+            U512::from(1_000)
+        );
+
+        // when a new account is created
+        let account = instance.new_account();
+
+        // then it is funded with the default balance and available under the next index
+        assert_eq!(instance.get_account(20), account);
+        assert_eq!(instance.balance_of(&account), U512::from(1_000));
+
+        // when labelled accounts are requested
+        let alice = instance.account("alice");
+
+        // then the same label yields the same account
+        assert_eq!(instance.account("alice"), alice);
+        assert_ne!(instance.account("bob"), alice);
+        assert_ne!(alice, account);
+
+        // when the balance is set
+        instance.set_balance(alice, U512::from(42));
+
+        // then the balance is updated
+        assert_eq!(instance.balance_of(&alice), U512::from(42));
+    }
+
     #[test]
     fn test_call_non_existing_contract() {
         // given an empty vm
@@ -693,11 +876,256 @@ mod tests {
         instance.call_contract(contract_address, call_def);
     }
 
+    #[test]
+    fn test_log_is_tagged_with_contract_and_call_depth() {
+        // given an instance with a registered contract
+        let instance = OdraVm::default();
+        let contract_address = instance.register_contract("Counter", test_caller(TEST_ENTRY_POINT));
+
+        // when a message is logged outside of a contract
+        let account = instance.get_account(0);
+        let message = instance.state.read().unwrap().format_log("hello");
+
+        // then it is tagged with the account
+        assert_eq!(message, format!("[{} depth=0] hello", account.to_string()));
+
+        // when the contract is being executed
+        let call_def = CallDef::new(TEST_ENTRY_POINT, false, RuntimeArgs::new());
+        let element = CallstackElement::new_contract_call(contract_address, call_def);
+        instance
+            .state
+            .write()
+            .unwrap()
+            .push_callstack_element(element);
+        let message = instance.state.read().unwrap().format_log("hello");
+
+        // then the message is tagged with the contract name, the entry point and the call depth
+        assert_eq!(message, "[Counter::abc depth=1] hello");
+    }
+
+    #[test]
+    fn test_gas_is_estimated_from_host_operations() {
+        // given an instance with custom gas costs
+        let instance = OdraVm::default();
+        instance.set_gas_costs(GasCosts {
+            storage_read: 1,
+            storage_write: 10,
+            storage_byte: 100,
+            event: 1_000,
+            event_byte: 10_000,
+            ..GasCosts::default()
+        });
+
+        // when a value is written and read, and an event is emitted
+        let value = 32u8.to_bytes().map(Bytes::from).unwrap();
+        instance.set_var(b"key", value.clone());
+        instance.get_var(b"key");
+        instance.emit_event(&value);
+
+        // then the gas is charged for every operation, and for every written byte
+        assert_eq!(instance.take_gas_used(), 10 + 4 * 100 + 1 + 1_000 + 10_000);
+        // then the gas meter is reset
+        assert_eq!(instance.take_gas_used(), 0);
+    }
+
+    #[test]
+    fn test_gas_report() {
+        // given a host with a deployed contract
+        let vm = OdraVm::new();
+        let costs = vm.borrow().gas_costs();
+        let host = OdraVmHost::new(vm);
+        let caller = test_caller(TEST_ENTRY_POINT);
+        let address = host
+            .borrow()
+            .new_contract("Contract", RuntimeArgs::new(), caller)
+            .unwrap();
+        assert_eq!(host.borrow().last_call_gas_cost(), costs.deploy);
+
+        // when the contract is called through the proxy and directly
+        let call_def = CallDef::new(TEST_ENTRY_POINT, false, RuntimeArgs::new());
+        host.borrow()
+            .call_contract(&address, call_def.clone(), true)
+            .unwrap();
+        assert_eq!(
+            host.borrow().last_call_gas_cost(),
+            costs.call + costs.proxy_call
+        );
+        host.borrow()
+            .call_contract(&address, call_def, false)
+            .unwrap();
+        assert_eq!(host.borrow().last_call_gas_cost(), costs.call);
+
+        // then the deploy and the calls are reported
+        let report = host.borrow().gas_report().into_iter().collect::<Vec<_>>();
+        assert_eq!(report.len(), 3);
+        assert!(matches!(report[0], DeployReport::WasmDeploy { .. }));
+        assert!(matches!(
+            &report[2],
+            DeployReport::ContractCall { gas, .. } if *gas == U512::from(costs.call)
+        ));
+    }
+
+    #[test]
+    fn test_gas_profile_attributes_gas_to_nested_calls() {
+        // given an instance with two registered contracts
+        let instance = OdraVm::default();
+        let costs = instance.gas_costs();
+        let token = instance.register_contract("Token", test_caller(TEST_ENTRY_POINT));
+        let hook = instance.register_contract("Hook", test_caller(TEST_ENTRY_POINT));
+
+        // when the first contract writes a value, and calls the second one emitting an event
+        for (address, entry_point) in [(token, "mint"), (hook, "notify")] {
+            let call_def = CallDef::new(entry_point, false, RuntimeArgs::new());
+            let element = CallstackElement::new_contract_call(address, call_def);
+            instance
+                .state
+                .write()
+                .unwrap()
+                .push_callstack_element(element);
+            instance.set_var(b"key", Bytes::from(vec![1u8]));
+        }
+        instance.emit_event(&Bytes::from(vec![1u8]));
+
+        // then the gas is attributed to the call stacks and the categories
+        let write = costs.storage_write + 4 * costs.storage_byte;
+        let event = costs.event + costs.event_byte;
+        assert_eq!(
+            instance.gas_profile().to_folded(),
+            format!(
+                "Token::mint;storage {}\nToken::mint;Hook::notify;storage {}\nToken::mint;Hook::notify;events {}\n",
+                write, write, event
+            )
+        );
+    }
+
+    #[test]
+    fn test_nested_call_of_unknown_entry_point_calls_fallback() {
+        // given a contract with a fallback entry point
+        let vm = OdraVm::new();
+        let env = HostEnv::new(OdraVmHost::new(vm.clone()));
+        let fallback =
+            EntryPoint::new(String::from("fallback"), vec![]).with_kind(EntryPointKind::Fallback);
+        let target = EntryPointsCaller::new(env.clone(), vec![fallback], |_, call_def| {
+            match call_def.entry_point() {
+                "fallback" => Ok(Bytes::new()),
+                name => Err(OdraError::VmError(VmError::NoSuchMethod(name.to_string())))
+            }
+        });
+        let target = vm.borrow().register_contract("Target", target);
+        // and a contract calling an unknown entry point of the given contract
+        let entry_point = EntryPoint::new(String::from(TEST_ENTRY_POINT), vec![]);
+        let caller = EntryPointsCaller::new(env.clone(), vec![entry_point], |env, call_def| {
+            let target = call_def.get::<Address>("target").unwrap();
+            env.call_contract::<()>(target, CallDef::new("unknown", true, RuntimeArgs::new()));
+            Ok(Bytes::new())
+        });
+        let caller = vm.borrow().register_contract("Caller", caller);
+
+        // when the contract calls the unknown entry point
+        let mut args = RuntimeArgs::new();
+        args.insert("target", target).unwrap();
+        let result =
+            env.raw_call_contract(caller, CallDef::new(TEST_ENTRY_POINT, true, args), false);
+
+        // then the fallback entry point is called
+        assert_eq!(result, Ok(Bytes::new()));
+    }
+
+    #[test]
+    fn test_nested_transfer_calls_receive() {
+        // given a contract with a receive entry point, and a contract with some tokens
+        let vm = OdraVm::new();
+        let env = HostEnv::new(OdraVmHost::new(vm.clone()));
+        let receive = EntryPoint::new_payable(String::from("receive"), vec![])
+            .with_kind(EntryPointKind::Receive);
+        let target =
+            EntryPointsCaller::new(env.clone(), vec![receive], |_, call_def| {
+                match call_def.entry_point() {
+                    "receive" => Ok(Bytes::new()),
+                    name => Err(OdraError::VmError(VmError::NoSuchMethod(name.to_string())))
+                }
+            });
+        let target = vm.borrow().register_contract("Target", target);
+        let entry_point = EntryPoint::new(String::from(TEST_ENTRY_POINT), vec![]);
+        let caller = EntryPointsCaller::new(env.clone(), vec![entry_point], |env, call_def| {
+            let target = call_def.get::<Address>("target").unwrap();
+            env.transfer_tokens(&target, &U512::from(100));
+            Ok(Bytes::new())
+        });
+        let caller = vm.borrow().register_contract("Caller", caller);
+        vm.borrow().set_balance(caller, U512::from(100));
+
+        // when the contract transfers the tokens to the contract
+        let mut args = RuntimeArgs::new();
+        args.insert("target", target).unwrap();
+        let result =
+            env.raw_call_contract(caller, CallDef::new(TEST_ENTRY_POINT, true, args), false);
+
+        // then the receive entry point gets the tokens
+        assert_eq!(result, Ok(Bytes::new()));
+        assert_eq!(vm.borrow().balance_of(&target), U512::from(100));
+        assert_eq!(vm.borrow().balance_of(&caller), U512::zero());
+    }
+
+    #[test]
+    fn test_reverted_call_uninstalls_new_contracts() {
+        // given a contract installing a new contract and reverting
+        let vm = OdraVm::new();
+        let env = HostEnv::new(OdraVmHost::new(vm.clone()));
+        fn child(env: &HostEnv) -> EntryPointsCaller {
+            let entry_point = EntryPoint::new(String::from(TEST_ENTRY_POINT), vec![]);
+            EntryPointsCaller::new(env.clone(), vec![entry_point], |_, _| Ok(Bytes::new()))
+        }
+        let entry_point = EntryPoint::new(String::from(TEST_ENTRY_POINT), vec![]);
+        let factory = EntryPointsCaller::new(env.clone(), vec![entry_point], |env, _| {
+            env.new_contract(&ContractTemplate::new("Child", child), None);
+            env.revert(ExecutionError::User(1))
+        });
+        let factory = vm.borrow().register_contract("Factory", factory);
+
+        // when the contract is called
+        let call_def = CallDef::new(TEST_ENTRY_POINT, true, RuntimeArgs::new());
+        let result = env.raw_call_contract(factory, call_def, false);
+
+        // then the call fails and the new contract is not installed
+        assert_eq!(
+            result,
+            Err(OdraError::ExecutionError(ExecutionError::User(1)))
+        );
+        let address = utils::contract_address_from_u32(2);
+        assert!(vm
+            .borrow()
+            .contract_register
+            .read()
+            .unwrap()
+            .get(&address)
+            .is_err());
+        // and the address is assigned to the next installed contract
+        assert_eq!(vm.borrow().register_contract("Child", child(&env)), address);
+    }
+
     fn push_address(vm: &OdraVm, address: &Address) {
         let element = CallstackElement::new_account(*address);
         vm.state.write().unwrap().push_callstack_element(element);
     }
 
+    #[test]
+    fn test_install_wasm_is_unsupported() {
+        // given a host env backed by the OdraVm
+        let env = HostEnv::new(OdraVmHost::new(OdraVm::new()));
+
+        // when a wasm file is installed
+        let result = env.install_wasm("counter.wasm", RuntimeArgs::new(), "counter");
+
+        // then an error is returned
+        assert_eq!(
+            result,
+            Err(OdraError::VmError(VmError::UnsupportedOperation(
+                String::from("install_wasm")
+            )))
+        );
+    }
+
     fn test_call_result() -> Bytes {
         vec![1, 1, 0, 0].into()
     }
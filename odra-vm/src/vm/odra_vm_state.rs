@@ -9,7 +9,10 @@ use odra_core::casper_types::{
     bytesrepr::{Bytes, FromBytes, ToBytes},
     PublicKey, SecretKey, U512
 };
-use odra_core::crypto::generate_key_pairs;
+use odra_core::crypto::{
+    generate_indexed_key_pair, generate_key_pairs, generate_labelled_key_pair
+};
+use odra_core::host::GenesisBalances;
 use odra_core::prelude::*;
 use odra_core::EventError;
 use std::collections::BTreeMap;
@@ -19,10 +22,14 @@ pub struct OdraVmState {
     callstack: Callstack,
     events: BTreeMap<Address, Vec<Bytes>>,
     contract_counter: u32,
+    contract_counter_snapshot: Option<u32>,
     pub error: Option<OdraError>,
     block_time: u64,
     pub accounts: Vec<Address>,
-    key_pairs: BTreeMap<Address, (SecretKey, PublicKey)>
+    key_pairs: BTreeMap<Address, (SecretKey, PublicKey)>,
+    labelled_accounts: BTreeMap<String, Address>,
+    default_balance: U512,
+    contract_names: BTreeMap<Address, String>
 }
 
 impl OdraVmState {
@@ -131,6 +138,52 @@ impl OdraVmState {
         utils::contract_address_from_u32(self.contract_counter)
     }
 
+    pub fn set_contract_name(&mut self, address: Address, name: &str) {
+        self.contract_names.insert(address, name.to_string());
+    }
+
+    /// Formats a debug message, tagging it with the current contract, entry point and the call depth.
+    pub fn format_log(&self, message: &str) -> String {
+        // The first element of the callstack is always the account that initiated the call.
+        let depth = self.callstack.size().saturating_sub(1);
+        match self.callstack.current() {
+            CallstackElement::ContractCall { address, call_def } => {
+                format!(
+                    "[{} depth={}] {}",
+                    self.call_frame(address, call_def.entry_point()),
+                    depth,
+                    message
+                )
+            }
+            CallstackElement::Account(address) => {
+                format!("[{} depth={}] {}", address.to_string(), depth, message)
+            }
+        }
+    }
+
+    /// Returns the name of a call frame in the form of `Contract::entry_point`.
+    pub fn call_frame(&self, address: &Address, entry_point: &str) -> String {
+        let contract = self
+            .contract_names
+            .get(address)
+            .cloned()
+            .unwrap_or_else(|| address.to_string());
+        format!("{}::{}", contract, entry_point)
+    }
+
+    /// Returns the frames of the contract calls on the callstack, starting from the outermost one.
+    pub fn call_frames(&self) -> Vec<String> {
+        self.callstack
+            .iter()
+            .filter_map(|element| match element {
+                CallstackElement::ContractCall { address, call_def } => {
+                    Some(self.call_frame(address, call_def.entry_point()))
+                }
+                CallstackElement::Account(_) => None
+            })
+            .collect()
+    }
+
     pub fn get_contract_namespace(&self) -> String {
         self.contract_counter.to_string()
     }
@@ -162,14 +215,29 @@ impl OdraVmState {
 
     pub fn take_snapshot(&mut self) {
         self.storage.take_snapshot();
+        self.contract_counter_snapshot = Some(self.contract_counter);
     }
 
     pub fn drop_snapshot(&mut self) {
         self.storage.drop_snapshot();
+        self.contract_counter_snapshot = None;
     }
 
-    pub fn restore_snapshot(&mut self) {
+    /// Restores the state from the snapshot.
+    ///
+    /// Returns the addresses of the contracts installed since the snapshot was taken.
+    pub fn restore_snapshot(&mut self) -> Vec<Address> {
         self.storage.restore_snapshot();
+        let mut reverted_contracts = vec![];
+        if let Some(counter) = self.contract_counter_snapshot.take() {
+            while self.contract_counter > counter {
+                let address = utils::contract_address_from_u32(self.contract_counter);
+                self.contract_names.remove(&address);
+                reverted_contracts.push(address);
+                self.contract_counter -= 1;
+            }
+        }
+        reverted_contracts
     }
 
     pub fn block_time(&self) -> u64 {
@@ -206,6 +274,28 @@ impl OdraVmState {
         self.storage.transfer(from, to, amount)
     }
 
+    pub fn new_account(&mut self) -> Address {
+        let (address, key_pair) = generate_indexed_key_pair(self.accounts.len());
+        self.add_account(address, key_pair);
+        address
+    }
+
+    pub fn account(&mut self, label: &str) -> Address {
+        if let Some(address) = self.labelled_accounts.get(label) {
+            return *address;
+        }
+        let (address, key_pair) = generate_labelled_key_pair(label);
+        self.add_account(address, key_pair);
+        self.labelled_accounts.insert(label.to_string(), address);
+        address
+    }
+
+    fn add_account(&mut self, address: Address, key_pair: (SecretKey, PublicKey)) {
+        self.accounts.push(address);
+        self.key_pairs.insert(address, key_pair);
+        self.set_balance(address, self.default_balance);
+    }
+
     pub fn public_key(&self, address: &Address) -> PublicKey {
         let (_, public_key) = self.key_pairs.get(address).unwrap();
         public_key.clone()
@@ -217,14 +307,16 @@ impl OdraVmState {
     }
 }
 
-impl Default for OdraVmState {
-    fn default() -> Self {
-        let accounts: Vec<Address> = Vec::new();
+impl OdraVmState {
+    pub fn new(genesis_balances: &GenesisBalances) -> Self {
         let key_pairs = generate_key_pairs(20);
         let accounts: Vec<Address> = key_pairs.keys().copied().collect();
         let mut balances = BTreeMap::<Address, AccountBalance>::new();
-        for address in accounts.clone() {
-            balances.insert(address, 100_000_000_000_000_000u64.into());
+        for (index, address) in accounts.iter().enumerate() {
+            balances.insert(
+                *address,
+                AccountBalance::new(genesis_balances.balance(index))
+            );
         }
 
         let mut backend = OdraVmState {
@@ -232,12 +324,22 @@ impl Default for OdraVmState {
             callstack: Default::default(),
             events: Default::default(),
             contract_counter: 0,
+            contract_counter_snapshot: None,
             error: None,
             block_time: 0,
             accounts: accounts.clone(),
-            key_pairs
+            key_pairs,
+            labelled_accounts: BTreeMap::new(),
+            default_balance: genesis_balances.default_balance(),
+            contract_names: BTreeMap::new()
         };
         backend.push_callstack_element(CallstackElement::Account(*accounts.first().unwrap()));
         backend
     }
 }
+
+impl Default for OdraVmState {
+    fn default() -> Self {
+        Self::new(&GenesisBalances::default())
+    }
+}
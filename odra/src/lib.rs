@@ -38,7 +38,8 @@
 #![no_std]
 
 pub use odra_core::{
-    args, contract_def, entry_point_callback, host, module, prelude, uints, utils
+    args, contract_def, entry_point_callback, factory, host, module, multicall, prelude, uints,
+    utils
 };
 /// Macros for creating Odra modules that store values in named keys or dictionaries.
 pub mod named_keys {
@@ -60,3 +61,40 @@ pub use odra_schema as schema;
 
 #[cfg(target_arch = "wasm32")]
 pub use odra_casper_wasm_env;
+
+/// Prints a debug message from inside a contract.
+///
+/// The first argument is the contract env, the rest are [format](prelude::format) arguments.
+/// The odra-vm backend prints the message tagged with the contract, the entry point and the call depth,
+/// the casper backend prints it using the host `print` function.
+///
+/// In wasm, the macro compiles to nothing unless the `debug-log` feature is enabled.
+/// The host `print` function is available only in the test environment, so never enable
+/// the feature for contracts deployed to a live network.
+///
+/// # Example
+///
+/// ```ignore
+/// odra::log!(self.env(), "balance of {:?}: {}", owner, balance);
+/// ```
+#[cfg(any(not(target_arch = "wasm32"), feature = "debug-log"))]
+#[macro_export]
+macro_rules! log {
+    ($env:expr, $($arg:tt)*) => {
+        $env.log($crate::prelude::format!($($arg)*))
+    };
+}
+
+/// Prints a debug message from inside a contract.
+///
+/// Compiled without the `debug-log` feature, so the message is discarded.
+#[cfg(all(target_arch = "wasm32", not(feature = "debug-log")))]
+#[macro_export]
+macro_rules! log {
+    ($env:expr, $($arg:tt)*) => {{
+        let _ = &$env;
+        if false {
+            let _ = $crate::prelude::format!($($arg)*);
+        }
+    }};
+}